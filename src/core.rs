@@ -1,56 +1,258 @@
-#![allow(arithmetic_overflow)]
+#![allow(
+    arithmetic_overflow,
+    clippy::assign_op_pattern,
+    clippy::bool_assert_comparison,
+    clippy::needless_return
+)]
+use std::mem;
+
 use crate::{error::CoreError, traits::Bus};
-use std::{cell::RefCell, cell::RefMut, rc::Rc};
 
-use self::{
+pub use self::{
     addressing::{Mode, Offset},
+    bus_trace::{AccessKind, BusCycle, BusTrace},
     flags::Flags,
+    instructions::{Instruction, CMOS_INSTRUCTIONS, NMOS_INSTRUCTIONS, W65C816_INSTRUCTIONS},
+    movie::{Divergence, DivergenceReason, Line, Movie},
+    outcome::{HaltReason, StepOutcome},
+    policy::{OpcodePolicy, Trap, TrapHandler},
+    power::PowerOn,
+    run::{RunOutcome, StopReason},
+    save::STATE_VERSION,
+    state::CpuState,
+    trace::Tracer,
+    variant::Variant,
+};
+use self::{
+    movie::MovieMode,
+    port::IoPort,
+    rewind::Rewind,
+    tick::{Access, Replay},
 };
 mod addressing;
+mod bus_trace;
+mod cmos;
+mod decimal;
 mod flags;
-
+mod instructions;
+mod movie;
+mod outcome;
+mod policy;
+mod port;
+mod power;
+mod rewind;
+mod run;
+mod save;
+mod state;
+mod tick;
+mod trace;
+mod undocumented;
+mod variant;
+mod w65c816;
+
+/// A CPU core driving a bus of type `B`, which it owns.
 #[derive(Debug)]
-pub struct Core {
+pub struct Core<B: Bus> {
     acc: u8,
     idx: u8,
     idy: u8,
     sp: u8,
     pc: u16,
+    acc_hi: u8,
+    idx_hi: u8,
+    idy_hi: u8,
+    sp_hi: u8,
+    dp: u16,
+    dbr: u8,
+    pbr: u8,
+    emulation: bool,
     status: Flags,
-    bus: Rc<RefCell<dyn Bus>>,
-    halted: bool,
+    bus: B,
+    variant: Variant,
+    undocumented: bool,
+    magic: u8,
+    unstable_magic: Option<u8>,
+    /// The opcode being decoded, for reporting an invalid addressing mode.
+    opcode: u8,
+    opcode_policy: OpcodePolicy<B>,
+    dry_run: bool,
+    port: IoPort,
+    halt: Option<HaltReason>,
+    waiting: bool,
+    jammed: Option<u8>,
+    irq_line: bool,
+    nmi_line: bool,
+    rdy_line: bool,
+    so_line: bool,
+    so_level: bool,
+    nmi_level: bool,
+    nmi_pending: bool,
+    irq_pending: bool,
+    cycles: u64,
+    stolen: u64,
+    replay: Replay,
+    rewind: Option<Rewind>,
+    movie: Option<MovieMode>,
+    tracer: Option<Tracer>,
+    bus_trace: Option<BusTrace>,
+    /// What the next bus access is for. Reset to data after every access.
+    access: AccessKind,
+    /// Set while the input lines are sampled at the end of a cycle.
+    clocking: bool,
 }
 
-impl Core {
-    pub fn new<B: Bus>(bus: B, program: Vec<u8>) -> Result<Self, CoreError> {
+/// A core whose bus type is only known at run time, for when several
+/// kinds of system share the same code. Every bus access goes through
+/// dynamic dispatch.
+pub type DynCore = Core<Box<dyn Bus>>;
+
+const NMI_VECTOR: u16 = 0xfffa;
+const IRQ_VECTOR: u16 = 0xfffe;
+const RESET_VECTOR: u16 = 0xfffc;
+
+/// The 6507 only has 13 address pins.
+const MOS6507_ADDR_MASK: u32 = 0x1fff;
+
+impl<B: Bus> Core<B> {
+    pub fn new(bus: B, program: Vec<u8>) -> Result<Self, CoreError> {
+        Self::with_variant(bus, program, Variant::default())
+    }
+
+    pub fn with_variant(bus: B, program: Vec<u8>, variant: Variant) -> Result<Self, CoreError> {
         let mut core = Self {
             acc: 0,
             idx: 0,
             idy: 0,
-            sp: 0,
+            sp: 0xff,
             pc: 0,
+            acc_hi: 0,
+            idx_hi: 0,
+            idy_hi: 0,
+            sp_hi: 0x01,
+            dp: 0,
+            dbr: 0,
+            pbr: 0,
+            emulation: true,
             status: Flags::new(),
-            bus: Rc::new(RefCell::new(bus)),
-            halted: true,
+            bus,
+            variant,
+            undocumented: variant.is_nmos(),
+            magic: 0xee,
+            unstable_magic: None,
+            opcode: 0,
+            opcode_policy: OpcodePolicy::default(),
+            dry_run: false,
+            port: IoPort::default(),
+            halt: None,
+            waiting: false,
+            jammed: None,
+            irq_line: false,
+            nmi_line: false,
+            rdy_line: true,
+            so_line: false,
+            so_level: false,
+            nmi_level: false,
+            nmi_pending: false,
+            irq_pending: false,
+            cycles: 0,
+            stolen: 0,
+            replay: Replay::default(),
+            rewind: None,
+            movie: None,
+            tracer: None,
+            bus_trace: None,
+            access: AccessKind::Data,
+            clocking: false,
         };
 
-        core.bus.borrow_mut().load_rom(program)?;
-        core.reset();
+        core.bus.load_rom(program)?;
+        core.reset_port();
+
+        // start as if already reset, without spending any cycles; use
+        // power_on for the real sequence
+        let addr = core.address_lines(RESET_VECTOR as u32) as u16;
+        let low = core.bus.read(addr);
+        let addr = core.address_lines(RESET_VECTOR as u32 + 1) as u16;
+        let high = core.bus.read(addr);
+        core.pc = core.addr_from_bytes(low, high);
 
         Ok(core)
     }
 
+    /// Pulls the RESET line. Takes 7 cycles, like an interrupt whose
+    /// stack pushes are turned into reads: SP ends up 3 lower, I is set,
+    /// and the new PC is read from the vector at $FFFC. A, X and Y keep
+    /// their values. The CMOS chips also clear D, and the 65C816 returns
+    /// to emulation mode.
     pub fn reset(&mut self) {
-        self.acc = 0;
-        self.idx = 0;
-        self.idy = 0;
-        self.sp = 0xff;
-
-        let mut bus = self.bus.borrow_mut();
-        let low = bus.read(0xfffc);
-        let high = bus.read(0xfffd);
+        self.replay = Replay::default();
+        self.halt = None;
+        self.jammed = None;
+        self.waiting = false;
+        self.nmi_pending = false;
+        self.irq_pending = false;
+        self.dry_run = false;
+
+        self.idx_hi = 0;
+        self.idy_hi = 0;
+        self.sp_hi = 0x01;
+        self.dp = 0;
+        self.dbr = 0;
+        self.pbr = 0;
+        self.emulation = true;
+        self.status.set_memory_8bit(true);
+        self.status.set_index_8bit(true);
+        self.reset_port();
+
+        self.read_as(AccessKind::Dummy, self.program_addr());
+        self.read_as(AccessKind::Dummy, self.program_addr());
+        for _ in 0..3 {
+            self.dummy_read(self.stack_addr());
+            self.sp = self.sp.wrapping_sub(1);
+        }
+
+        self.status.set_interrupt(true);
+        if self.variant.is_cmos() {
+            self.status.set_decimal(false);
+        }
+
+        let low = self.read_as(AccessKind::Vector, RESET_VECTOR as u32);
+        let high = self.read_as(AccessKind::Vector, RESET_VECTOR as u32 + 1);
         self.pc = self.addr_from_bytes(low, high);
-        self.halted = false;
+    }
+
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Whether a 65C816 is running in 6502 emulation mode. Always true
+    /// for the other variants.
+    pub fn emulation_mode(&self) -> bool {
+        self.emulation
+    }
+
+    /// Enables or disables decoding of the undocumented NMOS opcodes.
+    /// When disabled, they are handled by the [`OpcodePolicy`] like any
+    /// other unknown opcode.
+    pub fn set_undocumented_opcodes(&mut self, enabled: bool) {
+        self.undocumented = enabled;
+    }
+
+    /// Sets the value that the unstable XAA and LXA opcodes OR into the
+    /// accumulator. Real chips vary between $00, $EE and $FF depending on
+    /// manufacturer and temperature.
+    pub fn set_magic_constant(&mut self, magic: u8) {
+        self.magic = magic;
+    }
+
+    /// Sets the value that the unstable SHA, SHX, SHY and TAS stores AND
+    /// their value with in place of the high byte of the address plus
+    /// one, and that LAS ANDs into the byte it loads. `None`, the default,
+    /// keeps the common behavior. Some chips drop the `H + 1` term, for
+    /// example when RDY is pulled low during the instruction, which
+    /// `Some(0xff)` models.
+    pub fn set_unstable_magic(&mut self, magic: Option<u8>) {
+        self.unstable_magic = magic;
     }
 
     pub fn dump_status_byte(&self) -> u8 {
@@ -61,40 +263,353 @@ impl Core {
         self.status
     }
 
-    pub fn run(&mut self) {
-        while !self.halted {
-            self.step();
+    /// Why the core stopped, or `None` while it is running.
+    pub fn halt_reason(&self) -> Option<HaltReason> {
+        self.halt
+    }
+
+    /// Steps until the core halts, or until RDY holds it in the middle of
+    /// an instruction. Stopping at STP is not an error; the other reasons
+    /// are returned as the error of the step that caused them.
+    pub fn run(&mut self) -> Result<(), CoreError> {
+        while self.halt.is_none() {
+            if self.step()?.stalled {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Executes one instruction, or one cycle of sleep after WAI. An
+    /// instruction already started with [`Core::tick`] is finished instead.
+    ///
+    /// If RDY holds the CPU, the step spends a single stolen cycle and
+    /// returns with [`StepOutcome::stalled`] set. The next step carries
+    /// on with the same instruction.
+    pub fn step(&mut self) -> Result<StepOutcome, CoreError> {
+        let (mut outcome, finished) = self.advance(usize::MAX)?;
+        outcome.stalled = !finished;
+        Ok(outcome)
+    }
+
+    fn execute(&mut self) -> StepOutcome {
+        let (started, stolen) = self.replay.started();
+        let address = self.program_addr();
+        let opcode = self.dispatch();
+        StepOutcome {
+            opcode,
+            address,
+            cycles: (self.cycles - started) as u32,
+            stolen: (self.stolen - stolen) as u32,
+            stalled: false,
         }
     }
 
-    pub fn step(&mut self) {
-        let byte = self.fetch();
-        self.decode(byte);
+    /// Turns the outcome of a finished instruction into the error of the
+    /// halt it caused, if any.
+    fn finish_step(&self, outcome: StepOutcome) -> Result<StepOutcome, CoreError> {
+        let StepOutcome {
+            opcode, address, ..
+        } = outcome;
+        match self.halt {
+            Some(HaltReason::Jammed) => Err(CoreError::Jam { opcode, address }),
+            Some(HaltReason::IllegalOpcode) => Err(CoreError::IllegalOpcode { opcode, address }),
+            Some(HaltReason::InvalidAddressingMode(opcode)) => {
+                Err(CoreError::InvalidAddressingMode {
+                    instruction: self.variant.instructions()[opcode as usize].mnemonic,
+                    opcode,
+                    address,
+                })
+            }
+            Some(HaltReason::Stopped) | None => Ok(outcome),
+        }
+    }
+
+    /// Runs the next instruction or interrupt sequence and returns its
+    /// opcode.
+    fn dispatch(&mut self) -> u8 {
+        if let Some(opcode) = self.jammed {
+            self.dummy_read(0xffff);
+            return opcode;
+        }
+
+        if self.waiting {
+            // WAI sleeps until an interrupt arrives, even a masked one
+            if !self.decide(|core| core.nmi_pending || core.irq_asserted()) {
+                self.dummy_read(self.pc);
+                return 0xcb;
+            }
+            self.waiting = false;
+            let irq = self.decide(|core| core.irq_asserted());
+            self.irq_pending = irq && !self.status.interrupt();
+        }
+
+        if self.take_nmi() {
+            self.interrupt(NMI_VECTOR);
+        } else if self.irq_pending {
+            self.interrupt(IRQ_VECTOR);
+        } else {
+            let interrupt_disable = self.status.interrupt();
+            let byte = self.fetch_opcode();
+            self.decode(byte);
+
+            // CLI, SEI and PLP change the I flag after the interrupt poll,
+            // so the instruction following them still sees the old value
+            let interrupt_disable = match byte {
+                0x28 | 0x58 | 0x78 => interrupt_disable,
+                _ => self.status.interrupt(),
+            };
+            self.irq_pending = self.irq_asserted() && !interrupt_disable;
+            return byte;
+        }
+
+        self.irq_pending = false;
+        0x00
+    }
+
+    /// Drives the IRQ input. The line is level-triggered: as long as it is
+    /// asserted and the interrupt disable flag is clear, the core will keep
+    /// taking interrupts between instructions.
+    pub fn set_irq(&mut self, asserted: bool) {
+        self.irq_line = asserted;
+    }
+
+    /// Drives the NMI input. The line is edge-triggered: an interrupt is
+    /// latched only when it goes from released to asserted.
+    pub fn set_nmi(&mut self, asserted: bool) {
+        self.nmi_line = asserted;
+        self.sample_nmi();
+    }
+
+    /// Drives the SO (set overflow) input. The pin is active low and
+    /// edge-triggered: when it goes from released to asserted, the V
+    /// flag is set at the end of the current cycle, so an instruction
+    /// sees it from its next cycle on. Only the 6502 and 65C02 have it.
+    pub fn set_so(&mut self, asserted: bool) {
+        self.so_line = asserted;
+        self.sample_so();
+    }
+
+    /// Drives the RDY input. While it is low (`false`), the CPU stops on
+    /// its next read cycle and repeats that read until it goes high again,
+    /// leaving the bus to a DMA controller. The NMOS chips ignore RDY
+    /// during write cycles; the CMOS ones stop on those too.
+    ///
+    /// Every cycle stolen this way counts as a clock cycle. [`Core::tick`]
+    /// spends its cycle waiting, and [`Core::step`] and the run methods
+    /// return after one stolen cycle with the instruction marked as
+    /// stalled, so the caller gets control back to release the line. The
+    /// reset sequence and trap handlers don't wait on RDY.
+    pub fn set_rdy(&mut self, ready: bool) {
+        self.rdy_line = ready;
+    }
+
+    pub fn bus(&self) -> &B {
+        &self.bus
+    }
+
+    pub fn bus_mut(&mut self) -> &mut B {
+        &mut self.bus
+    }
+
+    /// Gives the bus back, dropping the core.
+    pub fn into_bus(self) -> B {
+        self.bus
+    }
+
+    fn irq_asserted(&mut self) -> bool {
+        self.variant.has_interrupts() && self.line_level(Line::Irq)
+    }
+
+    fn sample_nmi(&mut self) {
+        let level = self.variant.has_interrupts() && self.line_level(Line::Nmi);
+        if level && !self.nmi_level {
+            self.nmi_pending = true;
+        }
+        self.nmi_level = level;
+    }
+
+    fn sample_so(&mut self) {
+        let level = self.variant.has_so_pin() && self.line_level(Line::So);
+        if level && !self.so_level {
+            self.status.set_overflow(true);
+            self.replay.overflow_set();
+        }
+        self.so_level = level;
     }
 
-    pub fn get_bus(&self) -> RefMut<dyn Bus> {
-        self.bus.borrow_mut()
+    /// Logs a completed access for [`Core::tick`].
+    fn record(&mut self, byte: u8) {
+        // V set by SO in the middle of a ticked instruction is replayed
+        // along with this access
+        if self.replay.record(byte) {
+            self.status.set_overflow(true);
+        }
     }
 
-    fn clock_bus(&self) {
-        let mut bus = self.bus.borrow_mut();
-        bus.on_clock();
+    fn ready(&mut self) -> bool {
+        self.line_level(Line::Rdy)
     }
 
-    fn read_bus(&self, addr: u16) -> u8 {
-        let byte = self.bus.borrow_mut().read(addr);
+    /// Holds the CPU for one cycle if RDY is low, repeating the read of
+    /// `addr`. Returns the byte read if it did, in which case the access
+    /// waits for the next tick or step and the rest of the instruction is
+    /// discarded for now.
+    fn stall(&mut self, addr: u32, kind: AccessKind) -> Option<u8> {
+        if !self.replay.can_stall() || self.ready() {
+            return None;
+        }
+
+        let addr = self.address_lines(addr);
+        let byte = self.bus.read_access(addr, kind, self.cycles);
+        self.log_access(addr, byte, false, kind);
         self.clock_bus();
-        byte
+        self.stolen += 1;
+        self.replay.stall();
+        self.hold();
+        Some(byte)
+    }
+
+    fn clock_bus(&mut self) {
+        self.cycles += 1;
+        self.bus.on_clock();
+        self.port.tick();
+        self.clocking = true;
+        self.sample_nmi();
+        self.sample_so();
+        self.clocking = false;
+    }
+
+    fn read_bus(&mut self, addr: u16) -> u8 {
+        self.read_long(addr as u32)
     }
 
     fn write_bus(&mut self, addr: u16, byte: u8) {
-        self.bus.borrow_mut().write(addr, byte);
+        self.write_long(addr as u32, byte);
+    }
+
+    /// The address that actually appears on the pins. The 6507 drops
+    /// the top three bits, but lets the bus see the full address first.
+    fn address_lines(&mut self, addr: u32) -> u32 {
+        if self.variant != Variant::Mos6507 {
+            return addr;
+        }
+
+        self.bus.unmasked_address(addr as u16);
+        addr & MOS6507_ADDR_MASK
+    }
+
+    fn read_long(&mut self, addr: u32) -> u8 {
+        let kind = mem::replace(&mut self.access, AccessKind::Data);
+        // a halted core no longer drives the bus
+        if self.halt.is_some() {
+            return 0;
+        }
+
+        match self.replay.next_access() {
+            Access::Replayed(byte, overflow) => {
+                if overflow {
+                    self.status.set_overflow(true);
+                }
+                return byte;
+            }
+            Access::Discarded => {
+                self.hold();
+                return 0;
+            }
+            Access::Real => {}
+        }
+
+        if let Some(byte) = self.stall(addr, kind) {
+            return byte;
+        }
+
+        let addr = self.address_lines(addr);
+        let byte = if self.is_port(addr) {
+            self.read_port(addr, kind)
+        } else {
+            let byte = self.bus.read_access(addr, kind, self.cycles);
+            self.movie_read(addr, byte)
+        };
+        self.log_access(addr, byte, false, kind);
+        self.clock_bus();
+        self.record(byte);
+        byte
+    }
+
+    fn write_long(&mut self, addr: u32, byte: u8) {
+        let kind = mem::replace(&mut self.access, AccessKind::Data);
+        if self.dry_run {
+            self.read_as(kind, addr);
+            return;
+        }
+
+        if self.halt.is_some() {
+            return;
+        }
+
+        match self.replay.next_access() {
+            Access::Replayed(_, overflow) => {
+                if overflow {
+                    self.status.set_overflow(true);
+                }
+                return;
+            }
+            Access::Discarded => {
+                self.hold();
+                return;
+            }
+            Access::Real => {}
+        }
+
+        if self.variant.is_cmos() && self.stall(addr, kind).is_some() {
+            return;
+        }
+
+        let addr = self.address_lines(addr);
+        self.save_page(addr);
+        if self.is_port(addr) {
+            self.write_port(addr, byte, kind);
+        } else {
+            self.bus.write_access(addr, byte, kind, self.cycles);
+        }
+        self.log_access(addr, byte, true, kind);
         self.clock_bus();
+        self.record(0);
+    }
+
+    /// The address of the next instruction byte, including the
+    /// 65C816 program bank.
+    fn program_addr(&self) -> u32 {
+        ((self.pbr as u32) << 16) | self.pc as u32
+    }
+
+    /// A bus cycle whose data the CPU ignores. Devices with read side
+    /// effects still see it.
+    fn dummy_read(&mut self, addr: u16) {
+        self.read_as(AccessKind::Dummy, addr as u32);
+    }
+
+    /// Reads for something other than the data of an instruction.
+    fn read_as(&mut self, kind: AccessKind, addr: u32) -> u8 {
+        self.access = kind;
+        self.read_long(addr)
+    }
+
+    fn write_as(&mut self, kind: AccessKind, addr: u32, byte: u8) {
+        self.access = kind;
+        self.write_long(addr, byte);
+    }
+
+    fn fetch_opcode(&mut self) -> u8 {
+        let byte = self.read_as(AccessKind::Opcode, self.program_addr());
+        self.pc = self.pc.wrapping_add(1);
+        byte
     }
 
     fn fetch(&mut self) -> u8 {
-        let byte = self.read_bus(self.pc);
-        self.pc += 1;
+        let byte = self.read_as(AccessKind::Operand, self.program_addr());
+        self.pc = self.pc.wrapping_add(1);
         byte
     }
 
@@ -115,32 +630,99 @@ impl Core {
     fn shift_byte_right(&mut self, byte: u8) -> u8 {
         let shifted = byte >> 1;
         self.status.set_carry((byte & 0x1) != 0);
-        self.clock_bus();
         shifted
     }
 
     fn shift_byte_left(&mut self, byte: u8) -> u8 {
         let shifted = byte << 1;
         self.status.set_carry((byte & 0x80) != 0);
-        self.clock_bus();
         shifted
     }
 
+    fn stack_addr(&self) -> u16 {
+        self.addr_from_bytes(self.sp, 0x01)
+    }
+
     fn push_stack(&mut self, byte: u8) {
+        self.write_as(AccessKind::Stack, self.stack_addr() as u32, byte);
         self.sp = self.sp.wrapping_sub(1);
-        let addr = self.addr_from_bytes(self.sp, 0x01);
-        self.write_bus(addr, byte);
     }
 
     fn pull_stack(&mut self) -> u8 {
-        let addr = self.addr_from_bytes(self.sp, 0x01);
         self.sp = self.sp.wrapping_add(1);
-        // TODO(dogue): why are we clocking the bus here, but not in push_stack?
-        self.clock_bus();
-        self.read_bus(addr)
+        self.read_as(AccessKind::Stack, self.stack_addr() as u32)
+    }
+
+    /// The first two cycles of PLA, PLP, RTS and RTI: the byte after the
+    /// opcode is read and discarded, then the stack is read while the
+    /// stack pointer is incremented.
+    fn pull_prologue(&mut self) {
+        self.dummy_read(self.pc);
+        self.dummy_read(self.stack_addr());
+    }
+
+    /// Shared by IRQ and NMI. Takes 7 cycles, like BRK, but pushes the
+    /// status byte with the B flag clear.
+    fn interrupt(&mut self, vector: u16) {
+        // the opcode fetch and the following read are discarded
+        self.read_as(AccessKind::Opcode, self.program_addr());
+        self.read_as(AccessKind::Dummy, self.program_addr());
+        if self.variant == Variant::W65C816 {
+            self.push_vector_816(vector, false);
+        } else {
+            self.push_vector(vector, false);
+        }
+    }
+
+    fn push_vector(&mut self, vector: u16, brk: bool) {
+        let (pcl, pch) = self.bytes_from_addr(self.pc);
+        self.push_stack(pch);
+        self.push_stack(pcl);
+
+        // an NMI arriving while the return address is pushed hijacks the vector
+        let vector = if vector != NMI_VECTOR && self.take_nmi() {
+            NMI_VECTOR
+        } else {
+            vector
+        };
+
+        self.push_stack(self.status.stack_byte(brk));
+        self.status.set_interrupt(true);
+        if self.variant.is_cmos() {
+            self.status.set_decimal(false);
+        }
+
+        let adl = self.read_as(AccessKind::Vector, vector as u32);
+        let adh = self.read_as(AccessKind::Vector, vector as u32 + 1);
+        self.pc = self.addr_from_bytes(adl, adh);
+    }
+
+    /// Stops the core until the next reset. A halted core no longer
+    /// drives the bus, so an instruction that halts halfway through
+    /// doesn't finish its accesses.
+    fn halt(&mut self, reason: HaltReason) {
+        self.halt = Some(reason);
+    }
+
+    /// Halts on an addressing mode an instruction doesn't support. The
+    /// decode tables never produce one, so this only catches core bugs.
+    fn invalid_mode<T: Default>(&mut self) -> T {
+        self.halt(HaltReason::InvalidAddressingMode(self.opcode));
+        T::default()
     }
 
     fn decode(&mut self, byte: u8) {
+        self.opcode = byte;
+        match self.variant {
+            Variant::Cmos65C02 => self.decode_cmos(byte),
+            Variant::W65C816 => self.decode_816(byte),
+            Variant::Nmos6502 | Variant::Ricoh2A03 | Variant::Mos6510 | Variant::Mos6507 => {
+                self.decode_nmos(byte)
+            }
+        }
+    }
+
+    fn decode_nmos(&mut self, byte: u8) {
         match byte {
             0x00 => self.brk(),
             0x01 => self.ora(Mode::IndexedIndirect),
@@ -178,6 +760,7 @@ impl Core {
             0x39 => self.and(Mode::Absolute(Offset::Y)),
             0x3D => self.and(Mode::Absolute(Offset::X)),
             0x3E => self.rol(Mode::Absolute(Offset::X)),
+            0x40 => self.rti(),
             0x41 => self.eor(Mode::IndexedIndirect),
             0x45 => self.eor(Mode::ZeroPage(Offset::None)),
             0x46 => self.lsr(Mode::ZeroPage(Offset::None)),
@@ -280,7 +863,7 @@ impl Core {
             0xE6 => self.inc(Mode::ZeroPage(Offset::None)),
             0xE8 => self.inx(),
             0xE9 => self.sbc(Mode::Immediate),
-            0xEA => self.clock_bus(), // NOP
+            0xEA => self.dummy_read(self.pc), // NOP
             0xEC => self.cpx(Mode::Absolute(Offset::None)),
             0xED => self.sbc(Mode::Absolute(Offset::None)),
             0xEE => self.inc(Mode::Absolute(Offset::None)),
@@ -292,13 +875,14 @@ impl Core {
             0xF9 => self.sbc(Mode::Absolute(Offset::Y)),
             0xFD => self.sbc(Mode::Absolute(Offset::X)),
             0xFE => self.inc(Mode::Absolute(Offset::X)),
-            _ => self.halted = true,
+            _ if self.undocumented => self.decode_undocumented(byte),
+            _ => self.unknown_opcode(byte, HaltReason::IllegalOpcode),
         }
     }
 }
 
 // status flags
-impl Core {
+impl<B: Bus> Core<B> {
     fn set_nz(&mut self, byte: u8) {
         self.set_zero(byte).set_negative(byte);
     }
@@ -324,94 +908,171 @@ impl Core {
     fn check_overflow(&self, original: u8, value: u8, result: u8) -> bool {
         ((original ^ value) & 0x80 == 0) && ((original ^ result) & 0x80 != 0)
     }
+
+    /// returns (result: u8, carry: bool, overflow: bool) of A + byte + C
+    fn binary_add(&self, byte: u8) -> (u8, bool, bool) {
+        let sum = self.acc as u16 + byte as u16 + self.status.carry() as u16;
+        let res = sum as u8;
+        (res, sum > 0xff, self.check_overflow(self.acc, byte, res))
+    }
+
+    fn add_with_carry(&mut self, byte: u8) {
+        let (res, carry, overflow) = self.binary_add(byte);
+        self.acc = res;
+        self.status.set_carry(carry);
+        self.status.set_overflow(overflow);
+        self.set_nz(self.acc);
+    }
 }
 
 // addressing helpers
-impl Core {
+impl<B: Bus> Core<B> {
+    fn branch(&mut self, condition: bool) {
+        let offset = self.fetch() as i8;
+
+        if !condition {
+            return;
+        }
+
+        // the next opcode is read while the offset is added
+        self.dummy_read(self.pc);
+
+        let target = self.pc.wrapping_add(offset as u16);
+        let (_, pch) = self.bytes_from_addr(self.pc);
+        let (tl, th) = self.bytes_from_addr(target);
+
+        if th != pch {
+            // the low byte is added before the page is fixed up
+            self.dummy_read(self.addr_from_bytes(tl, pch));
+        }
+
+        self.pc = target;
+    }
+
     /// returns (address: u16, page_crossed: bool)
     fn get_absolute(&mut self, offset: Offset) -> (u16, bool) {
-        let mut page_crossed = false;
-        match offset {
-            Offset::None => {
-                let low = self.fetch();
-                let high = self.fetch();
-                let addr = self.addr_from_bytes(low, high);
-                (addr, page_crossed)
-            }
-            Offset::X => {
-                let low = self.fetch();
-                let high = self.fetch();
-                let addr = self.addr_from_bytes(low, high);
-                let addr = addr + self.idx as u16;
-                if self.page_crossed(low, self.idx) {
-                    self.clock_bus();
-                    page_crossed = true;
-                }
-                (addr, page_crossed)
-            }
-            Offset::Y => {
-                let low = self.fetch();
-                let high = self.fetch();
-                let addr = self.addr_from_bytes(low, high);
-                let addr = addr + self.idy as u16;
-                if self.page_crossed(low, self.idy) {
-                    self.clock_bus();
-                    page_crossed = true;
-                }
-                (addr, page_crossed)
-            }
+        self.get_absolute_indexed(offset, false)
+    }
+
+    /// Like [`get_absolute`][Self::get_absolute], but the indexed modes
+    /// always spend the fix-up cycle, as stores and read-modify-write
+    /// instructions do.
+    fn get_absolute_store(&mut self, offset: Offset) -> (u16, bool) {
+        self.get_absolute_indexed(offset, true)
+    }
+
+    fn get_absolute_indexed(&mut self, offset: Offset, store: bool) -> (u16, bool) {
+        let low = self.fetch();
+        let high = self.fetch();
+        let index = match offset {
+            Offset::None => return (self.addr_from_bytes(low, high), false),
+            Offset::X => self.idx,
+            Offset::Y => self.idy,
+        };
+
+        self.add_index(low, high, index, store)
+    }
+
+    /// Adds an index register to a base address. The chip adds it to the
+    /// low byte first and reads from the result while it carries into the
+    /// high byte, so crossing a page (or any store) costs a dummy read.
+    fn add_index(&mut self, low: u8, high: u8, index: u8, store: bool) -> (u16, bool) {
+        let addr = self.addr_from_bytes(low, high).wrapping_add(index as u16);
+        let page_crossed = self.page_crossed(low, index);
+
+        if page_crossed || store {
+            let dummy = if page_crossed && self.variant.is_cmos() {
+                // the 65C02 reads the last operand byte instead
+                self.pc.wrapping_sub(1)
+            } else {
+                self.addr_from_bytes(low.wrapping_add(index), high)
+            };
+            self.dummy_read(dummy);
         }
+
+        (addr, page_crossed)
     }
 
     fn get_zeropage(&mut self, offset: Offset) -> u16 {
-        match offset {
-            Offset::None => {
-                let low = self.fetch();
-                let addr = self.addr_from_bytes(low, 0x00);
-                addr
-            }
-            Offset::X => {
-                let low = self.fetch().wrapping_add(self.idx);
-                self.clock_bus();
-                let addr = self.addr_from_bytes(low, 0x00);
-                addr
-            }
-            Offset::Y => {
-                let low = self.fetch().wrapping_add(self.idy);
-                self.clock_bus();
-                let addr = self.addr_from_bytes(low, 0x00);
-                addr
-            }
+        let byte = self.fetch();
+        let index = match offset {
+            Offset::None => return self.addr_from_bytes(byte, 0x00),
+            Offset::X => self.idx,
+            Offset::Y => self.idy,
+        };
+
+        // the unindexed address is read while the index is added
+        self.dummy_read(self.addr_from_bytes(byte, 0x00));
+        self.addr_from_bytes(byte.wrapping_add(index), 0x00)
+    }
+
+    /// Resolves the target of a read-modify-write instruction.
+    /// Indexed modes always spend the page-crossing cycle.
+    fn rmw_address(&mut self, mode: Mode) -> u16 {
+        match mode {
+            Mode::ZeroPage(offset) => self.get_zeropage(offset),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            Mode::IndexedIndirect => self.get_indexed_indirect(),
+            Mode::IndirectIndexed => self.get_indirect_indexed_store().0,
+            _ => self.invalid_mode(),
+        }
+    }
+
+    /// ASL, LSR, ROL and ROR with abs,X only spend the fix-up cycle on a
+    /// page crossing on the 65C02.
+    fn shift_address(&mut self, offset: Offset) -> u16 {
+        if self.variant.is_cmos() {
+            self.get_absolute(offset).0
+        } else {
+            self.get_absolute_store(offset).0
+        }
+    }
+
+    /// The cycle between the read and the write of a read-modify-write
+    /// instruction. The NMOS chip writes the unmodified value back, the
+    /// 65C02 reads it a second time instead.
+    fn modify_cycle(&mut self, addr: u16, byte: u8) {
+        if self.variant.is_cmos() {
+            self.dummy_read(addr);
+        } else {
+            self.write_as(AccessKind::Dummy, addr as u32, byte);
         }
     }
 
     fn get_indexed_indirect(&mut self) -> u16 {
-        let byte = self.fetch().wrapping_add(self.idx);
-        let addr = self.addr_from_bytes(byte, 0x00);
-        let low = self.read_bus(addr);
-        let high = self.read_bus(addr + 1);
-        self.clock_bus();
-        let indirect = self.addr_from_bytes(low, high);
-        indirect
+        let byte = self.fetch();
+        self.dummy_read(self.addr_from_bytes(byte, 0x00));
+        let byte = byte.wrapping_add(self.idx);
+        let low = self.read_bus(self.addr_from_bytes(byte, 0x00));
+        let high = self.read_bus(self.addr_from_bytes(byte.wrapping_add(1), 0x00));
+        self.addr_from_bytes(low, high)
+    }
+
+    fn get_zeropage_indirect(&mut self) -> u16 {
+        let byte = self.fetch();
+        let low = self.read_bus(self.addr_from_bytes(byte, 0x00));
+        let high = self.read_bus(self.addr_from_bytes(byte.wrapping_add(1), 0x00));
+        self.addr_from_bytes(low, high)
     }
 
     fn get_indirect_indexed(&mut self) -> (u16, bool) {
-        let mut page_crossed = false;
+        self.get_indirect_indexed_with(false)
+    }
+
+    fn get_indirect_indexed_store(&mut self) -> (u16, bool) {
+        self.get_indirect_indexed_with(true)
+    }
+
+    fn get_indirect_indexed_with(&mut self, store: bool) -> (u16, bool) {
         let byte = self.fetch();
-        let addr = self.addr_from_bytes(byte, 0x00);
-        let low = self.read_bus(addr);
-        let high = self.read_bus(addr + 1);
-        if self.page_crossed(low, self.idy) {
-            self.clock_bus();
-            page_crossed = true;
-        }
-        let indirect = self.addr_from_bytes(low, high) + self.idy as u16;
-        (indirect, page_crossed)
+        let low = self.read_bus(self.addr_from_bytes(byte, 0x00));
+        let high = self.read_bus(self.addr_from_bytes(byte.wrapping_add(1), 0x00));
+        self.add_index(low, high, self.idy, store)
     }
 }
 
 // instructions
-impl Core {
+impl<B: Bus> Core<B> {
     fn adc(&mut self, mode: Mode) {
         let byte = match mode {
             Mode::Immediate => self.fetch(),
@@ -431,18 +1092,18 @@ impl Core {
                 let addr = self.get_indirect_indexed().0;
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for ADC"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
-        let carry_bit = self.status.carry() as u8;
-
-        let (partial, carry1) = byte.overflowing_add(carry_bit);
-        let (res, carry2) = self.acc.overflowing_add(partial);
-        let overflow = self.check_overflow(self.acc, byte, res);
-        self.acc = res;
-        self.status.set_carry(carry1 || carry2);
-        self.status.set_overflow(overflow);
-        self.set_nz(self.acc);
+        if self.decimal_mode() {
+            self.adc_decimal(byte);
+        } else {
+            self.add_with_carry(byte);
+        }
     }
 
     fn and(&mut self, mode: Mode) {
@@ -464,7 +1125,11 @@ impl Core {
                 let addr = self.get_indirect_indexed().0;
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for AND"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
         self.acc &= byte;
@@ -474,132 +1139,53 @@ impl Core {
     fn asl(&mut self, mode: Mode) {
         match mode {
             Mode::Accumulator => {
-                let byte = self.acc;
-                self.acc = self.shift_byte_left(byte);
+                self.dummy_read(self.pc);
+                self.acc = self.shift_byte_left(self.acc);
                 self.set_nz(self.acc);
             }
             Mode::ZeroPage(offset) => {
                 let addr = self.get_zeropage(offset);
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let byte = self.shift_byte_left(byte);
                 self.write_bus(addr, byte);
                 self.set_nz(byte);
             }
             Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
+                let addr = self.shift_address(offset);
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let byte = self.shift_byte_left(byte);
                 self.write_bus(addr, byte);
                 self.set_nz(byte);
             }
-            _ => unimplemented!("invalid addressing mode for ASL"),
+            _ => self.invalid_mode(),
         }
     }
 
     fn bcc(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if self.status.carry() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(!self.status.carry());
     }
 
     fn bcs(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if !self.status.carry() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(self.status.carry());
     }
 
     fn beq(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if !self.status.zero() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(self.status.zero());
     }
 
     fn bit(&mut self, mode: Mode) {
         let addr = match mode {
-            Mode::ZeroPage(_) => self.get_zeropage(Offset::None),
-            Mode::Absolute(_) => self.get_absolute(Offset::None).0,
-            _ => unimplemented!("invalid addressing mode for BIT"),
+            Mode::Immediate => {
+                // the 65C02 immediate form only affects Z
+                let byte = self.fetch();
+                self.status.set_zero(byte & self.acc == 0);
+                return;
+            }
+            Mode::ZeroPage(offset) => self.get_zeropage(offset),
+            Mode::Absolute(offset) => self.get_absolute(offset).0,
+            _ => return self.invalid_mode(),
         };
 
         let byte = self.read_bus(addr);
@@ -610,197 +1196,49 @@ impl Core {
     }
 
     fn bmi(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if !self.status.negative() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(self.status.negative());
     }
 
     fn bne(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if self.status.zero() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(!self.status.zero());
     }
 
     fn bpl(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if self.status.negative() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(!self.status.negative());
     }
 
     fn brk(&mut self) {
-        let (pcl, pch) = self.bytes_from_addr(self.pc);
-        let mut status = self.status;
-        self.clock_bus();
-        status.set_break(true);
-        self.push_stack(pch);
-        self.push_stack(pcl);
-        self.push_stack(status.as_byte());
-
-        let adl = self.read_bus(0xFFFE);
-        let adh = self.read_bus(0xFFFF);
-        self.pc = self.addr_from_bytes(adl, adh);
+        // BRK skips over its padding byte, so the return address is PC + 2
+        self.fetch();
+        self.push_vector(IRQ_VECTOR, true);
     }
 
     fn bvc(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if self.status.overflow() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(!self.status.overflow());
     }
 
     fn bvs(&mut self) {
-        let offset = self.fetch();
-
-        dbg!(offset);
-
-        if !self.status.overflow() {
-            return;
-        }
-
-        self.clock_bus();
-
-        if offset & (1 << 7) != 0 {
-            // discard the sign bit
-            let offset = offset & 0x7F;
-            let (_, page_crossed) = (self.pc as u8).overflowing_sub(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc -= offset as u16;
-        } else {
-            let (_, page_crossed) = (self.pc as u8).overflowing_add(offset);
-
-            if page_crossed {
-                self.clock_bus();
-            }
-
-            self.pc += offset as u16;
-        }
+        self.branch(self.status.overflow());
     }
 
     fn clc(&mut self) {
         self.status.set_carry(false);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn cld(&mut self) {
         self.status.set_decimal(false);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn cli(&mut self) {
         self.status.set_interrupt(false);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn clv(&mut self) {
         self.status.set_overflow(false);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn cmp(&mut self, mode: Mode) {
@@ -822,7 +1260,11 @@ impl Core {
                 let (addr, _) = self.get_indirect_indexed();
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for CMP"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
         let res = self.acc.wrapping_sub(byte);
@@ -841,7 +1283,7 @@ impl Core {
                 let (addr, _) = self.get_absolute(Offset::None);
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for CPX"),
+            _ => return self.invalid_mode(),
         };
 
         let res = self.idx.wrapping_sub(byte);
@@ -860,7 +1302,7 @@ impl Core {
                 let (addr, _) = self.get_absolute(Offset::None);
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for CPY"),
+            _ => return self.invalid_mode(),
         };
 
         let res = self.idy.wrapping_sub(byte);
@@ -871,32 +1313,26 @@ impl Core {
     fn dec(&mut self, mode: Mode) {
         let addr = match mode {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
-                addr
-            }
-            _ => unimplemented!("invalid addressing mode for DEC"),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            _ => return self.invalid_mode(),
         };
 
         let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
         let byte = byte.wrapping_sub(1);
-        self.clock_bus();
         self.write_bus(addr, byte);
         self.set_nz(byte);
     }
 
     fn dex(&mut self) {
         self.idx = self.idx.wrapping_sub(1);
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idx);
     }
 
     fn dey(&mut self) {
         self.idy = self.idy.wrapping_sub(1);
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idy);
     }
 
@@ -919,7 +1355,11 @@ impl Core {
                 let (addr, _) = self.get_indirect_indexed();
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for EOR"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
         self.acc = self.acc ^ byte;
@@ -929,32 +1369,26 @@ impl Core {
     fn inc(&mut self, mode: Mode) {
         let addr = match mode {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
-                addr
-            }
-            _ => unimplemented!("invalid addressing mode for INC"),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            _ => return self.invalid_mode(),
         };
 
         let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
         let byte = byte.wrapping_add(1);
-        self.clock_bus();
         self.write_bus(addr, byte);
         self.set_nz(byte);
     }
 
     fn inx(&mut self) {
         self.idx = self.idx.wrapping_add(1);
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idx);
     }
 
     fn iny(&mut self) {
         self.idy = self.idy.wrapping_add(1);
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idy);
     }
 
@@ -966,12 +1400,30 @@ impl Core {
                 let i_high = self.fetch();
                 let indirect = self.addr_from_bytes(i_low, i_high);
 
+                let t_low = self.read_bus(indirect);
+                let t_high = if self.variant.is_cmos() {
+                    self.dummy_read(self.pc.wrapping_sub(1));
+                    self.read_bus(indirect.wrapping_add(1))
+                } else {
+                    // the NMOS chip doesn't carry into the high byte,
+                    // so JMP ($xxFF) reads its high byte from $xx00
+                    let (low, high) = self.bytes_from_addr(indirect);
+                    self.read_bus(self.addr_from_bytes(low.wrapping_add(1), high))
+                };
+                self.addr_from_bytes(t_low, t_high)
+            }
+            Mode::AbsoluteIndexedIndirect => {
+                let i_low = self.fetch();
+                let i_high = self.fetch();
+                let indirect = self.addr_from_bytes(i_low, i_high);
+                let indirect = indirect.wrapping_add(self.idx as u16);
+                self.dummy_read(self.pc.wrapping_sub(1));
+
                 let t_low = self.read_bus(indirect);
                 let t_high = self.read_bus(indirect.wrapping_add(1));
-                let addr = self.addr_from_bytes(t_low, t_high);
-                addr
+                self.addr_from_bytes(t_low, t_high)
             }
-            _ => unimplemented!("invalid addressing mode for JMP"),
+            _ => return self.invalid_mode(),
         };
 
         self.pc = addr;
@@ -979,11 +1431,15 @@ impl Core {
 
     fn jsr(&mut self) {
         let adl = self.fetch();
-        let adh = self.fetch();
+        self.dummy_read(self.stack_addr());
+
+        // PC still points at the high byte of the target, so the pushed
+        // return address is one less than the next instruction
         let (pcl, pch) = self.bytes_from_addr(self.pc);
         self.push_stack(pch);
         self.push_stack(pcl);
-        self.clock_bus();
+
+        let adh = self.fetch();
         self.pc = self.addr_from_bytes(adl, adh);
     }
 
@@ -998,7 +1454,8 @@ impl Core {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
             Mode::IndexedIndirect => self.get_indexed_indirect(),
             Mode::IndirectIndexed => self.get_indirect_indexed().0,
-            _ => unimplemented!(),
+            Mode::ZeroPageIndirect => self.get_zeropage_indirect(),
+            _ => return self.invalid_mode(),
         };
 
         self.acc = self.read_bus(addr);
@@ -1014,7 +1471,7 @@ impl Core {
             }
             Mode::Absolute(offset) => self.get_absolute(offset).0,
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            _ => unimplemented!(),
+            _ => return self.invalid_mode(),
         };
 
         self.idx = self.read_bus(addr);
@@ -1030,7 +1487,7 @@ impl Core {
             }
             Mode::Absolute(offset) => self.get_absolute(offset).0,
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            _ => unimplemented!(),
+            _ => return self.invalid_mode(),
         };
 
         self.idy = self.read_bus(addr);
@@ -1040,29 +1497,27 @@ impl Core {
     fn lsr(&mut self, mode: Mode) {
         match mode {
             Mode::Accumulator => {
-                let byte = self.acc;
-                self.acc = self.shift_byte_right(byte);
+                self.dummy_read(self.pc);
+                self.acc = self.shift_byte_right(self.acc);
                 self.set_nz(self.acc);
             }
             Mode::ZeroPage(offset) => {
                 let addr = self.get_zeropage(offset);
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let byte = self.shift_byte_right(byte);
                 self.write_bus(addr, byte);
                 self.set_nz(byte);
             }
             Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                // shitty little hack to keep the clock cycles correct
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
+                let addr = self.shift_address(offset);
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let byte = self.shift_byte_right(byte);
                 self.write_bus(addr, byte);
                 self.set_nz(byte);
             }
-            _ => unimplemented!("invalid addressing mode for LSR"),
+            _ => self.invalid_mode(),
         }
     }
 
@@ -1085,7 +1540,11 @@ impl Core {
                 let (addr, _) = self.get_indirect_indexed();
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for EOR"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
         self.acc = self.acc | byte;
@@ -1093,25 +1552,25 @@ impl Core {
     }
 
     fn pha(&mut self) {
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.push_stack(self.acc);
     }
 
     fn php(&mut self) {
-        self.clock_bus();
-        self.push_stack(self.status.as_byte());
+        self.dummy_read(self.pc);
+        self.push_stack(self.status.stack_byte(true));
     }
 
     fn pla(&mut self) {
-        self.clock_bus();
+        self.pull_prologue();
         self.acc = self.pull_stack();
         self.set_nz(self.acc);
     }
 
     fn plp(&mut self) {
-        self.clock_bus();
+        self.pull_prologue();
         let byte = self.pull_stack();
-        self.status.from_byte(byte);
+        self.status.set_from_stack(byte);
     }
 
     fn rol(&mut self, mode: Mode) {
@@ -1119,6 +1578,7 @@ impl Core {
             Mode::Accumulator => {
                 // shift_byte_left() clobbers the carry flag
                 let carry = self.status.carry() as u8;
+                self.dummy_read(self.pc);
                 self.acc = self.shift_byte_left(self.acc) | carry;
                 self.set_nz(self.acc);
             }
@@ -1126,22 +1586,21 @@ impl Core {
                 let addr = self.get_zeropage(offset);
                 let carry = self.status.carry() as u8;
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let shifted = self.shift_byte_left(byte) | carry;
                 self.write_bus(addr, shifted);
-                self.set_negative(shifted);
+                self.set_nz(shifted);
             }
             Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
+                let addr = self.shift_address(offset);
                 let carry = self.status.carry() as u8;
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let shifted = self.shift_byte_left(byte) | carry;
                 self.write_bus(addr, shifted);
-                self.set_negative(shifted);
+                self.set_nz(shifted);
             }
-            _ => unimplemented!("invalid addressng mode for ROR"),
+            _ => self.invalid_mode(),
         }
     }
 
@@ -1150,6 +1609,7 @@ impl Core {
             Mode::Accumulator => {
                 // shift_byte_right() clobbers the carry flag
                 let carry = self.status.carry() as u8;
+                self.dummy_read(self.pc);
                 self.acc = self.shift_byte_right(self.acc) | carry << 7;
                 self.set_nz(self.acc);
             }
@@ -1157,32 +1617,42 @@ impl Core {
                 let addr = self.get_zeropage(offset);
                 let carry = self.status.carry() as u8;
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let shifted = self.shift_byte_right(byte) | carry << 7;
                 self.write_bus(addr, shifted);
-                self.set_negative(shifted);
+                self.set_nz(shifted);
             }
             Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset == Offset::X && !crossed {
-                    self.clock_bus();
-                }
+                let addr = self.shift_address(offset);
                 let carry = self.status.carry() as u8;
                 let byte = self.read_bus(addr);
+                self.modify_cycle(addr, byte);
                 let shifted = self.shift_byte_right(byte) | carry << 7;
                 self.write_bus(addr, shifted);
-                self.set_negative(shifted);
+                self.set_nz(shifted);
             }
-            _ => unimplemented!("invalid addressng mode for ROR"),
+            _ => self.invalid_mode(),
         }
     }
 
-    fn _rti() {}
+    fn rti(&mut self) {
+        self.pull_prologue();
+        let byte = self.pull_stack();
+        self.status.set_from_stack(byte);
+        let adl = self.pull_stack();
+        let adh = self.pull_stack();
+        self.pc = self.addr_from_bytes(adl, adh);
+    }
 
     fn rts(&mut self) {
+        self.pull_prologue();
         let adl = self.pull_stack();
         let adh = self.pull_stack();
-        self.clock_bus();
         self.pc = self.addr_from_bytes(adl, adh);
+
+        // JSR pushed the address of its last byte
+        self.dummy_read(self.pc);
+        self.pc = self.pc.wrapping_add(1);
     }
 
     fn sbc(&mut self, mode: Mode) {
@@ -1204,126 +1674,103 @@ impl Core {
                 let addr = self.get_indirect_indexed().0;
                 self.read_bus(addr)
             }
-            _ => unimplemented!("invalid addressing mode for SBC"),
+            Mode::ZeroPageIndirect => {
+                let addr = self.get_zeropage_indirect();
+                self.read_bus(addr)
+            }
+            _ => return self.invalid_mode(),
         };
 
-        let carry_bit = self.status.carry() as u8;
-
-        let (partial, carry1) = byte.overflowing_sub(carry_bit);
-        let (res, carry2) = self.acc.overflowing_sub(partial);
-        let overflow = self.check_overflow(self.acc, byte, res);
-        self.acc = res;
-        self.status.set_carry(carry1 || carry2);
-        self.status.set_overflow(overflow);
-        self.set_nz(self.acc);
+        if self.decimal_mode() {
+            self.sbc_decimal(byte);
+        } else {
+            // A - M - (1 - C) is the same as A + !M + C
+            self.add_with_carry(!byte);
+        }
     }
 
     fn sec(&mut self) {
         self.status.set_carry(true);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn sed(&mut self) {
         self.status.set_decimal(true);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn sei(&mut self) {
         self.status.set_interrupt(true);
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn sta(&mut self, mode: Mode) {
         let addr = match mode {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset != Offset::None && !crossed {
-                    self.clock_bus();
-                }
-                addr
-            }
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
             Mode::IndexedIndirect => self.get_indexed_indirect(),
-            Mode::IndirectIndexed => {
-                let (addr, _) = self.get_indirect_indexed();
-                self.clock_bus();
-                addr
-            }
-            _ => unimplemented!("invalid addressing mode for STA"),
+            Mode::IndirectIndexed => self.get_indirect_indexed_store().0,
+            Mode::ZeroPageIndirect => self.get_zeropage_indirect(),
+            _ => return self.invalid_mode(),
         };
 
-        self.clock_bus();
-        self.get_bus().write(addr, self.acc);
+        self.write_bus(addr, self.acc);
     }
 
     fn stx(&mut self, mode: Mode) {
         let addr = match mode {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset != Offset::None && !crossed {
-                    self.clock_bus();
-                }
-                addr
-            }
-            _ => unimplemented!("invalid addressing mode for STA"),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            _ => return self.invalid_mode(),
         };
 
-        self.clock_bus();
-        self.get_bus().write(addr, self.idx);
+        self.write_bus(addr, self.idx);
     }
 
     fn sty(&mut self, mode: Mode) {
         let addr = match mode {
             Mode::ZeroPage(offset) => self.get_zeropage(offset),
-            Mode::Absolute(offset) => {
-                let (addr, crossed) = self.get_absolute(offset);
-                if offset != Offset::None && !crossed {
-                    self.clock_bus();
-                }
-                addr
-            }
-            _ => unimplemented!("invalid addressing mode for STA"),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            _ => return self.invalid_mode(),
         };
 
-        self.clock_bus();
-        self.get_bus().write(addr, self.idy);
+        self.write_bus(addr, self.idy);
     }
 
     // I hate this abbreviation with my entire soul,
     // but consistency or something...
     fn tax(&mut self) {
         self.idx = self.acc;
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idx);
     }
 
     fn tay(&mut self) {
         self.idy = self.acc;
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.idy);
     }
 
     fn tsx(&mut self) {
         self.idx = self.sp;
-        self.clock_bus();
-        self.set_nz(self.idy);
+        self.dummy_read(self.pc);
+        self.set_nz(self.idx);
     }
 
     fn txa(&mut self) {
         self.acc = self.idx;
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.acc);
     }
 
     fn txs(&mut self) {
         self.sp = self.idx;
-        self.clock_bus();
+        self.dummy_read(self.pc);
     }
 
     fn tya(&mut self) {
         self.acc = self.idy;
-        self.clock_bus();
+        self.dummy_read(self.pc);
         self.set_nz(self.acc);
     }
 }
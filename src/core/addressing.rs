@@ -1,18 +1,52 @@
-#[allow(dead_code)]
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+//! Addressing modes, as decoded from an opcode.
+
+/// How an instruction finds its operand.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Mode {
+    /// `A`
     Accumulator,
+    /// `abs`, `abs,X` and `abs,Y`
     Absolute(Offset),
+    /// `#imm`
     Immediate,
     Implied,
+    /// `(abs)`, used by `JMP`
     Indirect,
+    /// `(zp,X)`
     IndexedIndirect,
+    /// `(zp),Y`
     IndirectIndexed,
+    /// 8-bit branch offset
     Relative,
+    /// `zp`, `zp,X` and `zp,Y`; the direct page on the 65C816
     ZeroPage(Offset),
+    /// `(zp)`, 65C02 only
+    ZeroPageIndirect,
+    /// `(abs,X)`, used by the 65C02 `JMP`
+    AbsoluteIndexedIndirect,
+    /// `zp,rel`, the bit to test and the branch offset of the Rockwell
+    /// `BBR` and `BBS`
+    ZeroPageRelative,
+    /// `long` and `long,X`, 65C816 only
+    AbsoluteLong(Offset),
+    /// `[abs]`, used by the 65C816 `JML`
+    AbsoluteIndirectLong,
+    /// `[dp]`, 65C816 only
+    IndirectLong,
+    /// `[dp],Y`, 65C816 only
+    IndirectLongIndexed,
+    /// `sr,S`, 65C816 only
+    StackRelative,
+    /// `(sr,S),Y`, 65C816 only
+    StackRelativeIndirectIndexed,
+    /// 16-bit branch offset used by `BRL` and `PER`
+    RelativeLong,
+    /// source and destination banks of `MVN` and `MVP`
+    BlockMove,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// The index register added to the address, if any.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum Offset {
     None,
     X,
@@ -0,0 +1,214 @@
+//! A log of every bus cycle, for finding where the core puts an access
+//! on the wrong cycle.
+//!
+//! Each entry is one clock cycle: the address on the bus, the data, the
+//! direction and what the CPU was doing with it. Transistor-level
+//! simulations like Visual6502 and perfect6502 give the same information
+//! (with SYNC standing in for [`AccessKind::Opcode`]), and so do the
+//! per-cycle lists of the SingleStepTests, so a trace can be diffed
+//! against them line by line.
+//!
+//! A trace exports as CSV for reading and diffing, or as a compact binary
+//! log of five bytes per cycle, in the container format of the save
+//! states.
+
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::{error::StateError, traits::Bus};
+
+use super::{
+    save::{chunks, container, Writer},
+    Core,
+};
+
+const MAGIC: &[u8; 4] = b"MOSB";
+const CYCLES_CHUNK: &[u8; 4] = b"CYCL";
+
+/// Set in the flags of a binary record when its cycle doesn't follow the
+/// one before, and the full cycle number comes next.
+const CYCLE_FOLLOWS: u8 = 0x80;
+const WRITE: u8 = 0x08;
+
+/// What the CPU uses a bus cycle for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    /// Fetching an opcode, the cycle where the 6502 raises SYNC. The
+    /// first cycle of an interrupt sequence is one too, although the
+    /// opcode is thrown away.
+    Opcode,
+    /// Fetching an operand byte of the instruction.
+    Operand,
+    /// Reading or writing the data an instruction works on, including
+    /// the pointers of indirect addressing modes.
+    Data,
+    /// Pushing to or pulling from the stack.
+    Stack,
+    /// Reading the reset or an interrupt vector.
+    Vector,
+    /// A cycle whose data the CPU ignores, or the write of the unmodified
+    /// value by an NMOS read-modify-write instruction.
+    Dummy,
+}
+
+impl AccessKind {
+    const ALL: [AccessKind; 6] = [
+        AccessKind::Opcode,
+        AccessKind::Operand,
+        AccessKind::Data,
+        AccessKind::Stack,
+        AccessKind::Vector,
+        AccessKind::Dummy,
+    ];
+
+    fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+impl fmt::Display for AccessKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AccessKind::Opcode => "sync",
+            AccessKind::Operand => "operand",
+            AccessKind::Data => "data",
+            AccessKind::Stack => "stack",
+            AccessKind::Vector => "vector",
+            AccessKind::Dummy => "dummy",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// One clock cycle on the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BusCycle {
+    /// The value of [`Core::cycles`] when the cycle started.
+    pub cycle: u64,
+    /// The address on the pins, including the 65C816 bank.
+    pub address: u32,
+    /// The byte read or written. Cycles stolen by RDY repeat the read
+    /// the CPU is waiting to make.
+    pub data: u8,
+    pub write: bool,
+    pub kind: AccessKind,
+}
+
+/// Every bus cycle since [`Core::start_bus_trace`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BusTrace {
+    cycles: Vec<BusCycle>,
+}
+
+impl BusTrace {
+    pub fn cycles(&self) -> &[BusCycle] {
+        &self.cycles
+    }
+
+    /// Writes the trace as CSV, one cycle per line after a header:
+    ///
+    /// ```text
+    /// cycle,address,data,rw,kind
+    /// 0,0000,A9,R,sync
+    /// ```
+    pub fn write_csv(&self, mut out: impl Write) -> io::Result<()> {
+        writeln!(out, "cycle,address,data,rw,kind")?;
+        for cycle in &self.cycles {
+            let rw = if cycle.write { 'W' } else { 'R' };
+            writeln!(
+                out,
+                "{},{:04X},{:02X},{rw},{}",
+                cycle.cycle, cycle.address, cycle.data, cycle.kind
+            )?;
+        }
+        out.flush()
+    }
+
+    /// Encodes the trace in the binary format. Each cycle takes a flags
+    /// byte with the kind and direction, the data and a 24-bit address.
+    /// The cycle number is only stored where it doesn't follow on from
+    /// the cycle before, such as at the start or after a rewind.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        let mut next = None;
+        for cycle in &self.cycles {
+            let mut flags = cycle.kind.index();
+            if cycle.write {
+                flags |= WRITE;
+            }
+            if next != Some(cycle.cycle) {
+                flags |= CYCLE_FOLLOWS;
+            }
+            w.u8(flags);
+            w.u8(cycle.data);
+            w.u16(cycle.address as u16);
+            w.u8((cycle.address >> 16) as u8);
+            if flags & CYCLE_FOLLOWS != 0 {
+                w.u64(cycle.cycle);
+            }
+            next = Some(cycle.cycle + 1);
+        }
+        container(MAGIC, &[(CYCLES_CHUNK, &w.into_bytes())])
+    }
+
+    /// Decodes a trace written by [`BusTrace::to_bytes`].
+    pub fn from_bytes(data: &[u8]) -> Result<Self, StateError> {
+        let r = &mut chunks(MAGIC, data)?.reader(CYCLES_CHUNK, "CYCL")?;
+        let mut cycles = Vec::new();
+        let mut next = 0;
+        while !r.is_empty() {
+            let flags = r.u8()?;
+            let data = r.u8()?;
+            let address = r.u16()? as u32 | (r.u8()? as u32) << 16;
+            let cycle = match flags & CYCLE_FOLLOWS {
+                0 => next,
+                _ => r.u64()?,
+            };
+            let kind = *AccessKind::ALL
+                .get((flags & 0x07) as usize)
+                .ok_or(StateError::Invalid("unknown access kind"))?;
+            cycles.push(BusCycle {
+                cycle,
+                address,
+                data,
+                write: flags & WRITE != 0,
+                kind,
+            });
+            next = cycle + 1;
+        }
+        Ok(Self { cycles })
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Starts logging every bus cycle, throwing away any trace already
+    /// in progress. The trace grows by one entry per cycle until it is
+    /// stopped.
+    pub fn start_bus_trace(&mut self) {
+        self.bus_trace = Some(BusTrace::default());
+    }
+
+    /// Stops logging and returns the trace, if one was running.
+    pub fn stop_bus_trace(&mut self) -> Option<BusTrace> {
+        self.bus_trace.take()
+    }
+
+    /// The trace in progress.
+    pub fn bus_trace(&self) -> Option<&BusTrace> {
+        self.bus_trace.as_ref()
+    }
+
+    pub(super) fn log_access(&mut self, address: u32, data: u8, write: bool, kind: AccessKind) {
+        if let Some(trace) = &mut self.bus_trace {
+            trace.cycles.push(BusCycle {
+                cycle: self.cycles,
+                address,
+                data,
+                write,
+                kind,
+            });
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! The 65C02 instruction set.
+//!
+//! The CMOS chip keeps every documented NMOS opcode and assigns most of
+//! the remaining ones. This covers the WDC additions, the Rockwell bit
+//! manipulation instructions and WAI/STP. The leftover opcodes are NOPs
+//! of varying length and duration.
+
+use crate::traits::Bus;
+
+use super::{
+    addressing::{Mode, Offset},
+    Core, HaltReason,
+};
+
+impl<B: Bus> Core<B> {
+    pub(super) fn decode_cmos(&mut self, byte: u8) {
+        match byte {
+            0x04 => self.tsb(Mode::ZeroPage(Offset::None)),
+            0x0C => self.tsb(Mode::Absolute(Offset::None)),
+            0x12 => self.ora(Mode::ZeroPageIndirect),
+            0x14 => self.trb(Mode::ZeroPage(Offset::None)),
+            0x1A => self.inc_acc(),
+            0x1C => self.trb(Mode::Absolute(Offset::None)),
+            0x32 => self.and(Mode::ZeroPageIndirect),
+            0x34 => self.bit(Mode::ZeroPage(Offset::X)),
+            0x3A => self.dec_acc(),
+            0x3C => self.bit(Mode::Absolute(Offset::X)),
+            0x52 => self.eor(Mode::ZeroPageIndirect),
+            0x5A => self.phy(),
+            0x64 => self.stz(Mode::ZeroPage(Offset::None)),
+            0x72 => self.adc(Mode::ZeroPageIndirect),
+            0x74 => self.stz(Mode::ZeroPage(Offset::X)),
+            0x7A => self.ply(),
+            0x7C => self.jmp(Mode::AbsoluteIndexedIndirect),
+            0x80 => self.branch(true),
+            0x89 => self.bit(Mode::Immediate),
+            0x92 => self.sta(Mode::ZeroPageIndirect),
+            0x9C => self.stz(Mode::Absolute(Offset::None)),
+            0x9E => self.stz(Mode::Absolute(Offset::X)),
+            0xB2 => self.lda(Mode::ZeroPageIndirect),
+            0xCB => self.wai(),
+            0xD2 => self.cmp(Mode::ZeroPageIndirect),
+            0xDA => self.phx(),
+            0xDB => self.stp(),
+            0xF2 => self.sbc(Mode::ZeroPageIndirect),
+            0xFA => self.plx(),
+            _ if byte & 0x0F == 0x07 => self.rmb_smb(byte),
+            _ if byte & 0x0F == 0x0F => self.bbr_bbs(byte),
+            _ if byte & 0x07 == 0x03 => {} // single cycle NOPs in columns 3 and B
+            0x02 | 0x22 | 0x42 | 0x62 | 0x82 | 0xC2 | 0xE2 => self.nop(Mode::Immediate),
+            0x44 => self.nop(Mode::ZeroPage(Offset::None)),
+            0x54 | 0xD4 | 0xF4 => self.nop(Mode::ZeroPage(Offset::X)),
+            0x5C => self.nop_eight_cycles(),
+            0xDC | 0xFC => self.nop(Mode::Absolute(Offset::None)),
+            _ => self.decode_nmos(byte),
+        }
+    }
+
+    fn bbr_bbs(&mut self, byte: u8) {
+        let bit = (byte >> 4) & 0x07;
+        let set = byte & 0x80 != 0;
+        let addr = self.get_zeropage(Offset::None);
+        let value = self.read_bus(addr);
+        self.dummy_read(addr);
+        self.branch((value & (1 << bit) != 0) == set);
+    }
+
+    fn dec_acc(&mut self) {
+        self.acc = self.acc.wrapping_sub(1);
+        self.dummy_read(self.pc);
+        self.set_nz(self.acc);
+    }
+
+    fn inc_acc(&mut self) {
+        self.acc = self.acc.wrapping_add(1);
+        self.dummy_read(self.pc);
+        self.set_nz(self.acc);
+    }
+
+    /// $5C fetches an absolute address but only reads the top page of
+    /// memory at its low byte, five times over.
+    fn nop_eight_cycles(&mut self) {
+        let (addr, _) = self.get_absolute(Offset::None);
+        let (low, _) = self.bytes_from_addr(addr);
+        for _ in 0..5 {
+            self.dummy_read(self.addr_from_bytes(low, 0xff));
+        }
+    }
+
+    fn phx(&mut self) {
+        self.dummy_read(self.pc);
+        self.push_stack(self.idx);
+    }
+
+    fn phy(&mut self) {
+        self.dummy_read(self.pc);
+        self.push_stack(self.idy);
+    }
+
+    fn plx(&mut self) {
+        self.pull_prologue();
+        self.idx = self.pull_stack();
+        self.set_nz(self.idx);
+    }
+
+    fn ply(&mut self) {
+        self.pull_prologue();
+        self.idy = self.pull_stack();
+        self.set_nz(self.idy);
+    }
+
+    fn rmb_smb(&mut self, byte: u8) {
+        let bit = (byte >> 4) & 0x07;
+        let addr = self.get_zeropage(Offset::None);
+        let value = self.read_bus(addr);
+        self.modify_cycle(addr, value);
+        let value = if byte & 0x80 != 0 {
+            value | (1 << bit)
+        } else {
+            value & !(1 << bit)
+        };
+        self.write_bus(addr, value);
+    }
+
+    pub(super) fn stp(&mut self) {
+        self.dummy_read(self.pc);
+        self.dummy_read(self.pc);
+        self.halt(HaltReason::Stopped);
+    }
+
+    fn stz(&mut self, mode: Mode) {
+        let addr = match mode {
+            Mode::ZeroPage(offset) => self.get_zeropage(offset),
+            Mode::Absolute(offset) => self.get_absolute_store(offset).0,
+            _ => return self.invalid_mode(),
+        };
+
+        self.write_bus(addr, 0x00);
+    }
+
+    fn trb(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.status.set_zero(byte & self.acc == 0);
+        self.modify_cycle(addr, byte);
+        self.write_bus(addr, byte & !self.acc);
+    }
+
+    fn tsb(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.status.set_zero(byte & self.acc == 0);
+        self.modify_cycle(addr, byte);
+        self.write_bus(addr, byte | self.acc);
+    }
+
+    pub(super) fn wai(&mut self) {
+        self.dummy_read(self.pc);
+        self.dummy_read(self.pc);
+        self.waiting = true;
+    }
+}
@@ -0,0 +1,91 @@
+//! Binary-coded decimal arithmetic for ADC and SBC.
+//!
+//! The algorithms follow Bruce Clark's "Decimal Mode" tutorial on 6502.org.
+//! On the NMOS 6502 only the accumulator and carry are meaningful; N, V and
+//! Z are left over from the intermediate binary calculation. The 65C02
+//! spends an extra cycle to produce valid N and Z flags. The Ricoh 2A03
+//! has no decimal mode at all.
+
+use crate::traits::Bus;
+
+use super::Core;
+
+impl<B: Bus> Core<B> {
+    /// Whether ADC and SBC should use decimal arithmetic.
+    pub(super) fn decimal_mode(&self) -> bool {
+        self.status.decimal() && self.variant.has_decimal_mode()
+    }
+
+    pub(super) fn adc_decimal(&mut self, byte: u8) {
+        let (binary, _, _) = self.binary_add(byte);
+        let carry = self.status.carry() as u16;
+        let acc = self.acc as u16;
+        let value = byte as u16;
+
+        let mut low = (acc & 0x0f) + (value & 0x0f) + carry;
+        if low >= 0x0a {
+            low = ((low + 0x06) & 0x0f) + 0x10;
+        }
+
+        let mut sum = (acc & 0xf0) + (value & 0xf0) + low;
+        let intermediate = sum as u8;
+        let overflow = self.check_overflow(self.acc, byte, intermediate);
+        if sum >= 0xa0 {
+            sum += 0x60;
+        }
+
+        self.acc = sum as u8;
+        self.status.set_carry(sum >= 0x100);
+        self.status.set_overflow(overflow);
+
+        if self.variant.is_cmos() {
+            self.set_nz(self.acc);
+            self.dummy_read(self.pc);
+        } else {
+            self.set_zero(binary).set_negative(intermediate);
+        }
+    }
+
+    pub(super) fn sbc_decimal(&mut self, byte: u8) {
+        // carry and overflow always come from the binary subtraction
+        let (binary, carry, overflow) = self.binary_add(!byte);
+        let borrow = 1 - self.status.carry() as i16;
+        let acc = self.acc as i16;
+        let value = byte as i16;
+
+        let low = (acc & 0x0f) - (value & 0x0f) - borrow;
+        let res = if self.variant.is_cmos() {
+            let mut res = acc - value - borrow;
+            if res < 0 {
+                res -= 0x60;
+            }
+            if low < 0 {
+                res -= 0x06;
+            }
+            res
+        } else {
+            let low = if low < 0 {
+                ((low - 0x06) & 0x0f) - 0x10
+            } else {
+                low
+            };
+            let res = (acc & 0xf0) - (value & 0xf0) + low;
+            if res < 0 {
+                res - 0x60
+            } else {
+                res
+            }
+        };
+
+        self.acc = res as u8;
+        self.status.set_carry(carry);
+        self.status.set_overflow(overflow);
+
+        if self.variant.is_cmos() {
+            self.set_nz(self.acc);
+            self.dummy_read(self.pc);
+        } else {
+            self.set_nz(binary);
+        }
+    }
+}
@@ -1,5 +1,5 @@
-#![allow(dead_code)]
-#[derive(Debug, Clone, Copy)]
+/// The processor status register P.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Flags {
     carry: bool,
     zero: bool,
@@ -8,9 +8,27 @@ pub struct Flags {
     break_command: bool,
     overflow: bool,
     negative: bool,
+    memory_8bit: bool,
+    index_8bit: bool,
+}
+
+impl Default for Flags {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the flags from a status byte, as [`Flags::from_byte`] does.
+impl From<u8> for Flags {
+    fn from(byte: u8) -> Self {
+        let mut flags = Self::new();
+        flags.from_byte(byte);
+        flags
+    }
 }
 
 impl Flags {
+    /// All flags clear, with the 65C816 registers 8 bits wide.
     pub fn new() -> Self {
         Self {
             carry: false,
@@ -20,6 +38,8 @@ impl Flags {
             break_command: false,
             overflow: false,
             negative: false,
+            memory_8bit: true,
+            index_8bit: true,
         }
     }
 
@@ -79,6 +99,24 @@ impl Flags {
         self.negative
     }
 
+    /// The 65C816 M flag. When set, the accumulator and memory are 8 bits wide.
+    pub fn set_memory_8bit(&mut self, value: bool) {
+        self.memory_8bit = value;
+    }
+
+    pub fn memory_8bit(&self) -> bool {
+        self.memory_8bit
+    }
+
+    /// The 65C816 X flag. When set, the index registers are 8 bits wide.
+    pub fn set_index_8bit(&mut self, value: bool) {
+        self.index_8bit = value;
+    }
+
+    pub fn index_8bit(&self) -> bool {
+        self.index_8bit
+    }
+
     pub fn as_byte(&self) -> u8 {
         (self.carry as u8)
             | ((self.zero as u8) << 1)
@@ -89,6 +127,36 @@ impl Flags {
             | ((self.negative as u8) << 7)
     }
 
+    /// The status byte as it is pushed to the stack. Bit 5 is always set,
+    /// and the B flag is only set when pushed by BRK or PHP.
+    pub fn stack_byte(&self, brk: bool) -> u8 {
+        let byte = (self.as_byte() & !0x10) | 0x20;
+        if brk {
+            byte | 0x10
+        } else {
+            byte
+        }
+    }
+
+    /// Loads the flags from a status byte pulled off the stack by PLP or RTI.
+    /// Bits 4 and 5 don't exist in the register and are ignored.
+    pub fn set_from_stack(&mut self, byte: u8) {
+        self.from_byte(byte);
+        self.break_command = false;
+    }
+
+    /// The status byte of a 65C816 in native mode, where bits 4 and 5
+    /// hold the X and M flags instead of B.
+    pub fn native_byte(&self) -> u8 {
+        (self.as_byte() & !0x30) | ((self.index_8bit as u8) << 4) | ((self.memory_8bit as u8) << 5)
+    }
+
+    pub fn set_native_byte(&mut self, byte: u8) {
+        self.from_byte(byte & !0x10);
+        self.index_8bit = byte & 0x10 != 0;
+        self.memory_8bit = byte & 0x20 != 0;
+    }
+
     pub fn from_byte(&mut self, byte: u8) {
         self.carry = byte & 0x01 != 0;
         self.zero = byte & 0x02 != 0;
@@ -124,4 +192,17 @@ mod test {
         assert_eq!(f.negative, true);
         assert_eq!(f.zero, true);
     }
+
+    #[test]
+    fn test_stack_byte() {
+        let mut f = Flags::new();
+        f.set_carry(true);
+
+        assert_eq!(f.stack_byte(true), 0b0011_0001);
+        assert_eq!(f.stack_byte(false), 0b0010_0001);
+
+        f.set_from_stack(0b1011_0001);
+        assert!(!f.break_cmd());
+        assert_eq!(f.as_byte(), 0b1000_0001);
+    }
 }
@@ -0,0 +1,894 @@
+//! Instruction metadata for every opcode, for disassemblers, tracers and
+//! debuggers.
+//!
+//! Each chip has its own table, indexed by opcode. The cycle counts are
+//! the base cost before any of the penalties that depend on the operands:
+//!
+//! - [`Instruction::page_cross`] marks the instructions that take one
+//!   more cycle when an indexed address or a branch target lands on
+//!   another page.
+//! - A branch that is taken takes one more cycle; the base count is for
+//!   the branch not taken.
+//! - ADC and SBC take one more cycle in decimal mode on the 65C02.
+//! - The 65C816 table is for emulation mode with 8-bit registers, the
+//!   state it resets into. 16-bit registers add a cycle per extra byte
+//!   moved, and a direct page register whose low byte isn't zero adds a
+//!   cycle to every direct page access. Immediate operands grow to two
+//!   bytes with 16-bit registers too.
+//!
+//! BRK is listed as two bytes, as the CPU skips the byte after it, and
+//! JAM as zero cycles, as it never finishes.
+
+use super::{
+    addressing::{Mode, Offset},
+    Variant,
+};
+
+/// What an opcode does on a particular chip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    /// The assembler mnemonic, in upper case.
+    pub mnemonic: &'static str,
+    pub mode: Mode,
+    /// Length in bytes, including the opcode.
+    pub length: u8,
+    /// Base cycle count.
+    pub cycles: u8,
+    /// Whether crossing a page boundary costs one more cycle.
+    pub page_cross: bool,
+    /// Whether the manufacturer documents the instruction. The NMOS
+    /// undocumented opcodes and the reserved 65C02 NOPs are not.
+    pub documented: bool,
+    /// Every variant that executes the same instruction, with the same
+    /// addressing mode, for this opcode. Cycle counts may still differ
+    /// between them.
+    pub variants: &'static [Variant],
+}
+
+impl Instruction {
+    const fn page_cross(mut self) -> Self {
+        self.page_cross = true;
+        self
+    }
+
+    const fn undocumented(mut self) -> Self {
+        self.documented = false;
+        self
+    }
+
+    const fn on(mut self, variants: &'static [Variant]) -> Self {
+        self.variants = variants;
+        self
+    }
+}
+
+const fn op(mnemonic: &'static str, mode: Mode, length: u8, cycles: u8) -> Instruction {
+    Instruction {
+        mnemonic,
+        mode,
+        length,
+        cycles,
+        page_cross: false,
+        documented: true,
+        variants: &[],
+    }
+}
+
+const NMOS: &[Variant] = &[
+    Variant::Nmos6502,
+    Variant::Ricoh2A03,
+    Variant::Mos6510,
+    Variant::Mos6507,
+];
+const CMOS: &[Variant] = &[Variant::Cmos65C02, Variant::W65C816];
+const ONLY_65C02: &[Variant] = &[Variant::Cmos65C02];
+const ONLY_65C816: &[Variant] = &[Variant::W65C816];
+const NOT_65C816: &[Variant] = &[
+    Variant::Nmos6502,
+    Variant::Ricoh2A03,
+    Variant::Mos6510,
+    Variant::Mos6507,
+    Variant::Cmos65C02,
+];
+const ALL: &[Variant] = &[
+    Variant::Nmos6502,
+    Variant::Ricoh2A03,
+    Variant::Mos6510,
+    Variant::Mos6507,
+    Variant::Cmos65C02,
+    Variant::W65C816,
+];
+
+/// The NMOS 6502 and its relatives, including the undocumented opcodes.
+#[rustfmt::skip]
+pub const NMOS_INSTRUCTIONS: [Instruction; 256] = [
+    /* 00 */ op("BRK",  Mode::Implied,                2, 7).on(ALL),
+    /* 01 */ op("ORA",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* 02 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 03 */ op("SLO",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* 04 */ op("NOP",  Mode::ZeroPage(Offset::None), 2, 3).undocumented().on(NMOS),
+    /* 05 */ op("ORA",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 06 */ op("ASL",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* 07 */ op("SLO",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* 08 */ op("PHP",  Mode::Implied,                1, 3).on(ALL),
+    /* 09 */ op("ORA",  Mode::Immediate,              2, 2).on(ALL),
+    /* 0A */ op("ASL",  Mode::Accumulator,            1, 2).on(ALL),
+    /* 0B */ op("ANC",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 0C */ op("NOP",  Mode::Absolute(Offset::None), 3, 4).undocumented().on(NMOS),
+    /* 0D */ op("ORA",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 0E */ op("ASL",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* 0F */ op("SLO",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* 10 */ op("BPL",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* 11 */ op("ORA",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* 12 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 13 */ op("SLO",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* 14 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NMOS),
+    /* 15 */ op("ORA",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 16 */ op("ASL",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* 17 */ op("SLO",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* 18 */ op("CLC",  Mode::Implied,                1, 2).on(ALL),
+    /* 19 */ op("ORA",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* 1A */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* 1B */ op("SLO",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* 1C */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* 1D */ op("ORA",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* 1E */ op("ASL",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* 1F */ op("SLO",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+    /* 20 */ op("JSR",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* 21 */ op("AND",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* 22 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 23 */ op("RLA",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* 24 */ op("BIT",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 25 */ op("AND",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 26 */ op("ROL",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* 27 */ op("RLA",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* 28 */ op("PLP",  Mode::Implied,                1, 4).on(ALL),
+    /* 29 */ op("AND",  Mode::Immediate,              2, 2).on(ALL),
+    /* 2A */ op("ROL",  Mode::Accumulator,            1, 2).on(ALL),
+    /* 2B */ op("ANC",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 2C */ op("BIT",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 2D */ op("AND",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 2E */ op("ROL",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* 2F */ op("RLA",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* 30 */ op("BMI",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* 31 */ op("AND",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* 32 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 33 */ op("RLA",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* 34 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NMOS),
+    /* 35 */ op("AND",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 36 */ op("ROL",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* 37 */ op("RLA",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* 38 */ op("SEC",  Mode::Implied,                1, 2).on(ALL),
+    /* 39 */ op("AND",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* 3A */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* 3B */ op("RLA",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* 3C */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* 3D */ op("AND",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* 3E */ op("ROL",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* 3F */ op("RLA",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+    /* 40 */ op("RTI",  Mode::Implied,                1, 6).on(ALL),
+    /* 41 */ op("EOR",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* 42 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 43 */ op("SRE",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* 44 */ op("NOP",  Mode::ZeroPage(Offset::None), 2, 3).undocumented().on(NOT_65C816),
+    /* 45 */ op("EOR",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 46 */ op("LSR",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* 47 */ op("SRE",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* 48 */ op("PHA",  Mode::Implied,                1, 3).on(ALL),
+    /* 49 */ op("EOR",  Mode::Immediate,              2, 2).on(ALL),
+    /* 4A */ op("LSR",  Mode::Accumulator,            1, 2).on(ALL),
+    /* 4B */ op("ALR",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 4C */ op("JMP",  Mode::Absolute(Offset::None), 3, 3).on(ALL),
+    /* 4D */ op("EOR",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 4E */ op("LSR",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* 4F */ op("SRE",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* 50 */ op("BVC",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* 51 */ op("EOR",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* 52 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 53 */ op("SRE",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* 54 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NOT_65C816),
+    /* 55 */ op("EOR",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 56 */ op("LSR",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* 57 */ op("SRE",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* 58 */ op("CLI",  Mode::Implied,                1, 2).on(ALL),
+    /* 59 */ op("EOR",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* 5A */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* 5B */ op("SRE",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* 5C */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* 5D */ op("EOR",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* 5E */ op("LSR",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* 5F */ op("SRE",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+    /* 60 */ op("RTS",  Mode::Implied,                1, 6).on(ALL),
+    /* 61 */ op("ADC",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* 62 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 63 */ op("RRA",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* 64 */ op("NOP",  Mode::ZeroPage(Offset::None), 2, 3).undocumented().on(NMOS),
+    /* 65 */ op("ADC",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 66 */ op("ROR",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* 67 */ op("RRA",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* 68 */ op("PLA",  Mode::Implied,                1, 4).on(ALL),
+    /* 69 */ op("ADC",  Mode::Immediate,              2, 2).on(ALL),
+    /* 6A */ op("ROR",  Mode::Accumulator,            1, 2).on(ALL),
+    /* 6B */ op("ARR",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 6C */ op("JMP",  Mode::Indirect,               3, 5).on(ALL),
+    /* 6D */ op("ADC",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 6E */ op("ROR",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* 6F */ op("RRA",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* 70 */ op("BVS",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* 71 */ op("ADC",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* 72 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 73 */ op("RRA",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* 74 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NMOS),
+    /* 75 */ op("ADC",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 76 */ op("ROR",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* 77 */ op("RRA",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* 78 */ op("SEI",  Mode::Implied,                1, 2).on(ALL),
+    /* 79 */ op("ADC",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* 7A */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* 7B */ op("RRA",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* 7C */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* 7D */ op("ADC",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* 7E */ op("ROR",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* 7F */ op("RRA",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+    /* 80 */ op("NOP",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 81 */ op("STA",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* 82 */ op("NOP",  Mode::Immediate,              2, 2).undocumented().on(NOT_65C816),
+    /* 83 */ op("SAX",  Mode::IndexedIndirect,        2, 6).undocumented().on(NMOS),
+    /* 84 */ op("STY",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 85 */ op("STA",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 86 */ op("STX",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* 87 */ op("SAX",  Mode::ZeroPage(Offset::None), 2, 3).undocumented().on(NMOS),
+    /* 88 */ op("DEY",  Mode::Implied,                1, 2).on(ALL),
+    /* 89 */ op("NOP",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 8A */ op("TXA",  Mode::Implied,                1, 2).on(ALL),
+    /* 8B */ op("XAA",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* 8C */ op("STY",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 8D */ op("STA",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 8E */ op("STX",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* 8F */ op("SAX",  Mode::Absolute(Offset::None), 3, 4).undocumented().on(NMOS),
+    /* 90 */ op("BCC",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* 91 */ op("STA",  Mode::IndirectIndexed,        2, 6).on(ALL),
+    /* 92 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* 93 */ op("SHA",  Mode::IndirectIndexed,        2, 6).undocumented().on(NMOS),
+    /* 94 */ op("STY",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 95 */ op("STA",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* 96 */ op("STX",  Mode::ZeroPage(Offset::Y),    2, 4).on(ALL),
+    /* 97 */ op("SAX",  Mode::ZeroPage(Offset::Y),    2, 4).undocumented().on(NMOS),
+    /* 98 */ op("TYA",  Mode::Implied,                1, 2).on(ALL),
+    /* 99 */ op("STA",  Mode::Absolute(Offset::Y),    3, 5).on(ALL),
+    /* 9A */ op("TXS",  Mode::Implied,                1, 2).on(ALL),
+    /* 9B */ op("TAS",  Mode::Absolute(Offset::Y),    3, 5).undocumented().on(NMOS),
+    /* 9C */ op("SHY",  Mode::Absolute(Offset::X),    3, 5).undocumented().on(NMOS),
+    /* 9D */ op("STA",  Mode::Absolute(Offset::X),    3, 5).on(ALL),
+    /* 9E */ op("SHX",  Mode::Absolute(Offset::Y),    3, 5).undocumented().on(NMOS),
+    /* 9F */ op("SHA",  Mode::Absolute(Offset::Y),    3, 5).undocumented().on(NMOS),
+    /* A0 */ op("LDY",  Mode::Immediate,              2, 2).on(ALL),
+    /* A1 */ op("LDA",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* A2 */ op("LDX",  Mode::Immediate,              2, 2).on(ALL),
+    /* A3 */ op("LAX",  Mode::IndexedIndirect,        2, 6).undocumented().on(NMOS),
+    /* A4 */ op("LDY",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* A5 */ op("LDA",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* A6 */ op("LDX",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* A7 */ op("LAX",  Mode::ZeroPage(Offset::None), 2, 3).undocumented().on(NMOS),
+    /* A8 */ op("TAY",  Mode::Implied,                1, 2).on(ALL),
+    /* A9 */ op("LDA",  Mode::Immediate,              2, 2).on(ALL),
+    /* AA */ op("TAX",  Mode::Implied,                1, 2).on(ALL),
+    /* AB */ op("LXA",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* AC */ op("LDY",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* AD */ op("LDA",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* AE */ op("LDX",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* AF */ op("LAX",  Mode::Absolute(Offset::None), 3, 4).undocumented().on(NMOS),
+    /* B0 */ op("BCS",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* B1 */ op("LDA",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* B2 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* B3 */ op("LAX",  Mode::IndirectIndexed,        2, 5).page_cross().undocumented().on(NMOS),
+    /* B4 */ op("LDY",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* B5 */ op("LDA",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* B6 */ op("LDX",  Mode::ZeroPage(Offset::Y),    2, 4).on(ALL),
+    /* B7 */ op("LAX",  Mode::ZeroPage(Offset::Y),    2, 4).undocumented().on(NMOS),
+    /* B8 */ op("CLV",  Mode::Implied,                1, 2).on(ALL),
+    /* B9 */ op("LDA",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* BA */ op("TSX",  Mode::Implied,                1, 2).on(ALL),
+    /* BB */ op("LAS",  Mode::Absolute(Offset::Y),    3, 4).page_cross().undocumented().on(NMOS),
+    /* BC */ op("LDY",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* BD */ op("LDA",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* BE */ op("LDX",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* BF */ op("LAX",  Mode::Absolute(Offset::Y),    3, 4).page_cross().undocumented().on(NMOS),
+    /* C0 */ op("CPY",  Mode::Immediate,              2, 2).on(ALL),
+    /* C1 */ op("CMP",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* C2 */ op("NOP",  Mode::Immediate,              2, 2).undocumented().on(NOT_65C816),
+    /* C3 */ op("DCP",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* C4 */ op("CPY",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* C5 */ op("CMP",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* C6 */ op("DEC",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* C7 */ op("DCP",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* C8 */ op("INY",  Mode::Implied,                1, 2).on(ALL),
+    /* C9 */ op("CMP",  Mode::Immediate,              2, 2).on(ALL),
+    /* CA */ op("DEX",  Mode::Implied,                1, 2).on(ALL),
+    /* CB */ op("SBX",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* CC */ op("CPY",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* CD */ op("CMP",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* CE */ op("DEC",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* CF */ op("DCP",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* D0 */ op("BNE",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* D1 */ op("CMP",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* D2 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* D3 */ op("DCP",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* D4 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NOT_65C816),
+    /* D5 */ op("CMP",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* D6 */ op("DEC",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* D7 */ op("DCP",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* D8 */ op("CLD",  Mode::Implied,                1, 2).on(ALL),
+    /* D9 */ op("CMP",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* DA */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* DB */ op("DCP",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* DC */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* DD */ op("CMP",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* DE */ op("DEC",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* DF */ op("DCP",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+    /* E0 */ op("CPX",  Mode::Immediate,              2, 2).on(ALL),
+    /* E1 */ op("SBC",  Mode::IndexedIndirect,        2, 6).on(ALL),
+    /* E2 */ op("NOP",  Mode::Immediate,              2, 2).undocumented().on(NOT_65C816),
+    /* E3 */ op("ISC",  Mode::IndexedIndirect,        2, 8).undocumented().on(NMOS),
+    /* E4 */ op("CPX",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* E5 */ op("SBC",  Mode::ZeroPage(Offset::None), 2, 3).on(ALL),
+    /* E6 */ op("INC",  Mode::ZeroPage(Offset::None), 2, 5).on(ALL),
+    /* E7 */ op("ISC",  Mode::ZeroPage(Offset::None), 2, 5).undocumented().on(NMOS),
+    /* E8 */ op("INX",  Mode::Implied,                1, 2).on(ALL),
+    /* E9 */ op("SBC",  Mode::Immediate,              2, 2).on(ALL),
+    /* EA */ op("NOP",  Mode::Implied,                1, 2).on(ALL),
+    /* EB */ op("SBC",  Mode::Immediate,              2, 2).undocumented().on(NMOS),
+    /* EC */ op("CPX",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* ED */ op("SBC",  Mode::Absolute(Offset::None), 3, 4).on(ALL),
+    /* EE */ op("INC",  Mode::Absolute(Offset::None), 3, 6).on(ALL),
+    /* EF */ op("ISC",  Mode::Absolute(Offset::None), 3, 6).undocumented().on(NMOS),
+    /* F0 */ op("BEQ",  Mode::Relative,               2, 2).page_cross().on(ALL),
+    /* F1 */ op("SBC",  Mode::IndirectIndexed,        2, 5).page_cross().on(ALL),
+    /* F2 */ op("JAM",  Mode::Implied,                1, 0).undocumented().on(NMOS),
+    /* F3 */ op("ISC",  Mode::IndirectIndexed,        2, 8).undocumented().on(NMOS),
+    /* F4 */ op("NOP",  Mode::ZeroPage(Offset::X),    2, 4).undocumented().on(NOT_65C816),
+    /* F5 */ op("SBC",  Mode::ZeroPage(Offset::X),    2, 4).on(ALL),
+    /* F6 */ op("INC",  Mode::ZeroPage(Offset::X),    2, 6).on(ALL),
+    /* F7 */ op("ISC",  Mode::ZeroPage(Offset::X),    2, 6).undocumented().on(NMOS),
+    /* F8 */ op("SED",  Mode::Implied,                1, 2).on(ALL),
+    /* F9 */ op("SBC",  Mode::Absolute(Offset::Y),    3, 4).page_cross().on(ALL),
+    /* FA */ op("NOP",  Mode::Implied,                1, 2).undocumented().on(NMOS),
+    /* FB */ op("ISC",  Mode::Absolute(Offset::Y),    3, 7).undocumented().on(NMOS),
+    /* FC */ op("NOP",  Mode::Absolute(Offset::X),    3, 4).page_cross().undocumented().on(NMOS),
+    /* FD */ op("SBC",  Mode::Absolute(Offset::X),    3, 4).page_cross().on(ALL),
+    /* FE */ op("INC",  Mode::Absolute(Offset::X),    3, 7).on(ALL),
+    /* FF */ op("ISC",  Mode::Absolute(Offset::X),    3, 7).undocumented().on(NMOS),
+];
+
+/// The WDC 65C02 with the Rockwell bit instructions.
+#[rustfmt::skip]
+pub const CMOS_INSTRUCTIONS: [Instruction; 256] = [
+    /* 00 */ op("BRK",  Mode::Implied,                 2, 7).on(ALL),
+    /* 01 */ op("ORA",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* 02 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(ONLY_65C02),
+    /* 03 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 04 */ op("TSB",  Mode::ZeroPage(Offset::None),  2, 5).on(CMOS),
+    /* 05 */ op("ORA",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 06 */ op("ASL",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* 07 */ op("RMB0", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 08 */ op("PHP",  Mode::Implied,                 1, 3).on(ALL),
+    /* 09 */ op("ORA",  Mode::Immediate,               2, 2).on(ALL),
+    /* 0A */ op("ASL",  Mode::Accumulator,             1, 2).on(ALL),
+    /* 0B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 0C */ op("TSB",  Mode::Absolute(Offset::None),  3, 6).on(CMOS),
+    /* 0D */ op("ORA",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 0E */ op("ASL",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* 0F */ op("BBR0", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 10 */ op("BPL",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* 11 */ op("ORA",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* 12 */ op("ORA",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* 13 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 14 */ op("TRB",  Mode::ZeroPage(Offset::None),  2, 5).on(CMOS),
+    /* 15 */ op("ORA",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 16 */ op("ASL",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* 17 */ op("RMB1", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 18 */ op("CLC",  Mode::Implied,                 1, 2).on(ALL),
+    /* 19 */ op("ORA",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* 1A */ op("INC",  Mode::Accumulator,             1, 2).on(CMOS),
+    /* 1B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 1C */ op("TRB",  Mode::Absolute(Offset::None),  3, 6).on(CMOS),
+    /* 1D */ op("ORA",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* 1E */ op("ASL",  Mode::Absolute(Offset::X),     3, 6).page_cross().on(ALL),
+    /* 1F */ op("BBR1", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 20 */ op("JSR",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* 21 */ op("AND",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* 22 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(ONLY_65C02),
+    /* 23 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 24 */ op("BIT",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 25 */ op("AND",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 26 */ op("ROL",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* 27 */ op("RMB2", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 28 */ op("PLP",  Mode::Implied,                 1, 4).on(ALL),
+    /* 29 */ op("AND",  Mode::Immediate,               2, 2).on(ALL),
+    /* 2A */ op("ROL",  Mode::Accumulator,             1, 2).on(ALL),
+    /* 2B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 2C */ op("BIT",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 2D */ op("AND",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 2E */ op("ROL",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* 2F */ op("BBR2", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 30 */ op("BMI",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* 31 */ op("AND",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* 32 */ op("AND",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* 33 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 34 */ op("BIT",  Mode::ZeroPage(Offset::X),     2, 4).on(CMOS),
+    /* 35 */ op("AND",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 36 */ op("ROL",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* 37 */ op("RMB3", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 38 */ op("SEC",  Mode::Implied,                 1, 2).on(ALL),
+    /* 39 */ op("AND",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* 3A */ op("DEC",  Mode::Accumulator,             1, 2).on(CMOS),
+    /* 3B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 3C */ op("BIT",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(CMOS),
+    /* 3D */ op("AND",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* 3E */ op("ROL",  Mode::Absolute(Offset::X),     3, 6).page_cross().on(ALL),
+    /* 3F */ op("BBR3", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 40 */ op("RTI",  Mode::Implied,                 1, 6).on(ALL),
+    /* 41 */ op("EOR",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* 42 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(ONLY_65C02),
+    /* 43 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 44 */ op("NOP",  Mode::ZeroPage(Offset::None),  2, 3).undocumented().on(NOT_65C816),
+    /* 45 */ op("EOR",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 46 */ op("LSR",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* 47 */ op("RMB4", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 48 */ op("PHA",  Mode::Implied,                 1, 3).on(ALL),
+    /* 49 */ op("EOR",  Mode::Immediate,               2, 2).on(ALL),
+    /* 4A */ op("LSR",  Mode::Accumulator,             1, 2).on(ALL),
+    /* 4B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 4C */ op("JMP",  Mode::Absolute(Offset::None),  3, 3).on(ALL),
+    /* 4D */ op("EOR",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 4E */ op("LSR",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* 4F */ op("BBR4", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 50 */ op("BVC",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* 51 */ op("EOR",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* 52 */ op("EOR",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* 53 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 54 */ op("NOP",  Mode::ZeroPage(Offset::X),     2, 4).undocumented().on(NOT_65C816),
+    /* 55 */ op("EOR",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 56 */ op("LSR",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* 57 */ op("RMB5", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 58 */ op("CLI",  Mode::Implied,                 1, 2).on(ALL),
+    /* 59 */ op("EOR",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* 5A */ op("PHY",  Mode::Implied,                 1, 3).on(CMOS),
+    /* 5B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 5C */ op("NOP",  Mode::Absolute(Offset::None),  3, 8).undocumented().on(ONLY_65C02),
+    /* 5D */ op("EOR",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* 5E */ op("LSR",  Mode::Absolute(Offset::X),     3, 6).page_cross().on(ALL),
+    /* 5F */ op("BBR5", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 60 */ op("RTS",  Mode::Implied,                 1, 6).on(ALL),
+    /* 61 */ op("ADC",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* 62 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(ONLY_65C02),
+    /* 63 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 64 */ op("STZ",  Mode::ZeroPage(Offset::None),  2, 3).on(CMOS),
+    /* 65 */ op("ADC",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 66 */ op("ROR",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* 67 */ op("RMB6", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 68 */ op("PLA",  Mode::Implied,                 1, 4).on(ALL),
+    /* 69 */ op("ADC",  Mode::Immediate,               2, 2).on(ALL),
+    /* 6A */ op("ROR",  Mode::Accumulator,             1, 2).on(ALL),
+    /* 6B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 6C */ op("JMP",  Mode::Indirect,                3, 6).on(ALL),
+    /* 6D */ op("ADC",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 6E */ op("ROR",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* 6F */ op("BBR6", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 70 */ op("BVS",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* 71 */ op("ADC",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* 72 */ op("ADC",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* 73 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 74 */ op("STZ",  Mode::ZeroPage(Offset::X),     2, 4).on(CMOS),
+    /* 75 */ op("ADC",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 76 */ op("ROR",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* 77 */ op("RMB7", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 78 */ op("SEI",  Mode::Implied,                 1, 2).on(ALL),
+    /* 79 */ op("ADC",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* 7A */ op("PLY",  Mode::Implied,                 1, 4).on(CMOS),
+    /* 7B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 7C */ op("JMP",  Mode::AbsoluteIndexedIndirect, 3, 6).on(CMOS),
+    /* 7D */ op("ADC",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* 7E */ op("ROR",  Mode::Absolute(Offset::X),     3, 6).page_cross().on(ALL),
+    /* 7F */ op("BBR7", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 80 */ op("BRA",  Mode::Relative,                2, 3).page_cross().on(CMOS),
+    /* 81 */ op("STA",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* 82 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(NOT_65C816),
+    /* 83 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 84 */ op("STY",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 85 */ op("STA",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 86 */ op("STX",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* 87 */ op("SMB0", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 88 */ op("DEY",  Mode::Implied,                 1, 2).on(ALL),
+    /* 89 */ op("BIT",  Mode::Immediate,               2, 2).on(CMOS),
+    /* 8A */ op("TXA",  Mode::Implied,                 1, 2).on(ALL),
+    /* 8B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 8C */ op("STY",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 8D */ op("STA",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 8E */ op("STX",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* 8F */ op("BBS0", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* 90 */ op("BCC",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* 91 */ op("STA",  Mode::IndirectIndexed,         2, 6).on(ALL),
+    /* 92 */ op("STA",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* 93 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 94 */ op("STY",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 95 */ op("STA",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* 96 */ op("STX",  Mode::ZeroPage(Offset::Y),     2, 4).on(ALL),
+    /* 97 */ op("SMB1", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* 98 */ op("TYA",  Mode::Implied,                 1, 2).on(ALL),
+    /* 99 */ op("STA",  Mode::Absolute(Offset::Y),     3, 5).on(ALL),
+    /* 9A */ op("TXS",  Mode::Implied,                 1, 2).on(ALL),
+    /* 9B */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* 9C */ op("STZ",  Mode::Absolute(Offset::None),  3, 4).on(CMOS),
+    /* 9D */ op("STA",  Mode::Absolute(Offset::X),     3, 5).on(ALL),
+    /* 9E */ op("STZ",  Mode::Absolute(Offset::X),     3, 5).on(CMOS),
+    /* 9F */ op("BBS1", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* A0 */ op("LDY",  Mode::Immediate,               2, 2).on(ALL),
+    /* A1 */ op("LDA",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* A2 */ op("LDX",  Mode::Immediate,               2, 2).on(ALL),
+    /* A3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* A4 */ op("LDY",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* A5 */ op("LDA",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* A6 */ op("LDX",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* A7 */ op("SMB2", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* A8 */ op("TAY",  Mode::Implied,                 1, 2).on(ALL),
+    /* A9 */ op("LDA",  Mode::Immediate,               2, 2).on(ALL),
+    /* AA */ op("TAX",  Mode::Implied,                 1, 2).on(ALL),
+    /* AB */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* AC */ op("LDY",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* AD */ op("LDA",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* AE */ op("LDX",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* AF */ op("BBS2", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* B0 */ op("BCS",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* B1 */ op("LDA",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* B2 */ op("LDA",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* B3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* B4 */ op("LDY",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* B5 */ op("LDA",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* B6 */ op("LDX",  Mode::ZeroPage(Offset::Y),     2, 4).on(ALL),
+    /* B7 */ op("SMB3", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* B8 */ op("CLV",  Mode::Implied,                 1, 2).on(ALL),
+    /* B9 */ op("LDA",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* BA */ op("TSX",  Mode::Implied,                 1, 2).on(ALL),
+    /* BB */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* BC */ op("LDY",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* BD */ op("LDA",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* BE */ op("LDX",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* BF */ op("BBS3", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* C0 */ op("CPY",  Mode::Immediate,               2, 2).on(ALL),
+    /* C1 */ op("CMP",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* C2 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(NOT_65C816),
+    /* C3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* C4 */ op("CPY",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* C5 */ op("CMP",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* C6 */ op("DEC",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* C7 */ op("SMB4", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* C8 */ op("INY",  Mode::Implied,                 1, 2).on(ALL),
+    /* C9 */ op("CMP",  Mode::Immediate,               2, 2).on(ALL),
+    /* CA */ op("DEX",  Mode::Implied,                 1, 2).on(ALL),
+    /* CB */ op("WAI",  Mode::Implied,                 1, 3).on(CMOS),
+    /* CC */ op("CPY",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* CD */ op("CMP",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* CE */ op("DEC",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* CF */ op("BBS4", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* D0 */ op("BNE",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* D1 */ op("CMP",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* D2 */ op("CMP",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* D3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* D4 */ op("NOP",  Mode::ZeroPage(Offset::X),     2, 4).undocumented().on(NOT_65C816),
+    /* D5 */ op("CMP",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* D6 */ op("DEC",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* D7 */ op("SMB5", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* D8 */ op("CLD",  Mode::Implied,                 1, 2).on(ALL),
+    /* D9 */ op("CMP",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* DA */ op("PHX",  Mode::Implied,                 1, 3).on(CMOS),
+    /* DB */ op("STP",  Mode::Implied,                 1, 3).on(CMOS),
+    /* DC */ op("NOP",  Mode::Absolute(Offset::None),  3, 4).undocumented().on(ONLY_65C02),
+    /* DD */ op("CMP",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* DE */ op("DEC",  Mode::Absolute(Offset::X),     3, 7).on(ALL),
+    /* DF */ op("BBS5", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* E0 */ op("CPX",  Mode::Immediate,               2, 2).on(ALL),
+    /* E1 */ op("SBC",  Mode::IndexedIndirect,         2, 6).on(ALL),
+    /* E2 */ op("NOP",  Mode::Immediate,               2, 2).undocumented().on(NOT_65C816),
+    /* E3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* E4 */ op("CPX",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* E5 */ op("SBC",  Mode::ZeroPage(Offset::None),  2, 3).on(ALL),
+    /* E6 */ op("INC",  Mode::ZeroPage(Offset::None),  2, 5).on(ALL),
+    /* E7 */ op("SMB6", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* E8 */ op("INX",  Mode::Implied,                 1, 2).on(ALL),
+    /* E9 */ op("SBC",  Mode::Immediate,               2, 2).on(ALL),
+    /* EA */ op("NOP",  Mode::Implied,                 1, 2).on(ALL),
+    /* EB */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* EC */ op("CPX",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* ED */ op("SBC",  Mode::Absolute(Offset::None),  3, 4).on(ALL),
+    /* EE */ op("INC",  Mode::Absolute(Offset::None),  3, 6).on(ALL),
+    /* EF */ op("BBS6", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+    /* F0 */ op("BEQ",  Mode::Relative,                2, 2).page_cross().on(ALL),
+    /* F1 */ op("SBC",  Mode::IndirectIndexed,         2, 5).page_cross().on(ALL),
+    /* F2 */ op("SBC",  Mode::ZeroPageIndirect,        2, 5).on(CMOS),
+    /* F3 */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* F4 */ op("NOP",  Mode::ZeroPage(Offset::X),     2, 4).undocumented().on(NOT_65C816),
+    /* F5 */ op("SBC",  Mode::ZeroPage(Offset::X),     2, 4).on(ALL),
+    /* F6 */ op("INC",  Mode::ZeroPage(Offset::X),     2, 6).on(ALL),
+    /* F7 */ op("SMB7", Mode::ZeroPage(Offset::None),  2, 5).on(ONLY_65C02),
+    /* F8 */ op("SED",  Mode::Implied,                 1, 2).on(ALL),
+    /* F9 */ op("SBC",  Mode::Absolute(Offset::Y),     3, 4).page_cross().on(ALL),
+    /* FA */ op("PLX",  Mode::Implied,                 1, 4).on(CMOS),
+    /* FB */ op("NOP",  Mode::Implied,                 1, 1).undocumented().on(ONLY_65C02),
+    /* FC */ op("NOP",  Mode::Absolute(Offset::None),  3, 4).undocumented().on(ONLY_65C02),
+    /* FD */ op("SBC",  Mode::Absolute(Offset::X),     3, 4).page_cross().on(ALL),
+    /* FE */ op("INC",  Mode::Absolute(Offset::X),     3, 7).on(ALL),
+    /* FF */ op("BBS7", Mode::ZeroPageRelative,        3, 5).page_cross().on(ONLY_65C02),
+];
+
+/// The WDC 65C816 in emulation mode with 8-bit registers.
+#[rustfmt::skip]
+pub const W65C816_INSTRUCTIONS: [Instruction; 256] = [
+    /* 00 */ op("BRK",  Mode::Implied,                      2, 7).on(ALL),
+    /* 01 */ op("ORA",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* 02 */ op("COP",  Mode::Immediate,                    2, 7).on(ONLY_65C816),
+    /* 03 */ op("ORA",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* 04 */ op("TSB",  Mode::ZeroPage(Offset::None),       2, 5).on(CMOS),
+    /* 05 */ op("ORA",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 06 */ op("ASL",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* 07 */ op("ORA",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* 08 */ op("PHP",  Mode::Implied,                      1, 3).on(ALL),
+    /* 09 */ op("ORA",  Mode::Immediate,                    2, 2).on(ALL),
+    /* 0A */ op("ASL",  Mode::Accumulator,                  1, 2).on(ALL),
+    /* 0B */ op("PHD",  Mode::Implied,                      1, 4).on(ONLY_65C816),
+    /* 0C */ op("TSB",  Mode::Absolute(Offset::None),       3, 6).on(CMOS),
+    /* 0D */ op("ORA",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 0E */ op("ASL",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* 0F */ op("ORA",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* 10 */ op("BPL",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* 11 */ op("ORA",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* 12 */ op("ORA",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* 13 */ op("ORA",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* 14 */ op("TRB",  Mode::ZeroPage(Offset::None),       2, 5).on(CMOS),
+    /* 15 */ op("ORA",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 16 */ op("ASL",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* 17 */ op("ORA",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* 18 */ op("CLC",  Mode::Implied,                      1, 2).on(ALL),
+    /* 19 */ op("ORA",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* 1A */ op("INC",  Mode::Accumulator,                  1, 2).on(CMOS),
+    /* 1B */ op("TCS",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* 1C */ op("TRB",  Mode::Absolute(Offset::None),       3, 6).on(CMOS),
+    /* 1D */ op("ORA",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* 1E */ op("ASL",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* 1F */ op("ORA",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* 20 */ op("JSR",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* 21 */ op("AND",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* 22 */ op("JSL",  Mode::AbsoluteLong(Offset::None),   4, 8).on(ONLY_65C816),
+    /* 23 */ op("AND",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* 24 */ op("BIT",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 25 */ op("AND",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 26 */ op("ROL",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* 27 */ op("AND",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* 28 */ op("PLP",  Mode::Implied,                      1, 4).on(ALL),
+    /* 29 */ op("AND",  Mode::Immediate,                    2, 2).on(ALL),
+    /* 2A */ op("ROL",  Mode::Accumulator,                  1, 2).on(ALL),
+    /* 2B */ op("PLD",  Mode::Implied,                      1, 5).on(ONLY_65C816),
+    /* 2C */ op("BIT",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 2D */ op("AND",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 2E */ op("ROL",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* 2F */ op("AND",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* 30 */ op("BMI",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* 31 */ op("AND",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* 32 */ op("AND",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* 33 */ op("AND",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* 34 */ op("BIT",  Mode::ZeroPage(Offset::X),          2, 4).on(CMOS),
+    /* 35 */ op("AND",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 36 */ op("ROL",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* 37 */ op("AND",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* 38 */ op("SEC",  Mode::Implied,                      1, 2).on(ALL),
+    /* 39 */ op("AND",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* 3A */ op("DEC",  Mode::Accumulator,                  1, 2).on(CMOS),
+    /* 3B */ op("TSC",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* 3C */ op("BIT",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(CMOS),
+    /* 3D */ op("AND",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* 3E */ op("ROL",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* 3F */ op("AND",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* 40 */ op("RTI",  Mode::Implied,                      1, 6).on(ALL),
+    /* 41 */ op("EOR",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* 42 */ op("WDM",  Mode::Immediate,                    2, 2).on(ONLY_65C816),
+    /* 43 */ op("EOR",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* 44 */ op("MVP",  Mode::BlockMove,                    3, 7).on(ONLY_65C816),
+    /* 45 */ op("EOR",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 46 */ op("LSR",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* 47 */ op("EOR",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* 48 */ op("PHA",  Mode::Implied,                      1, 3).on(ALL),
+    /* 49 */ op("EOR",  Mode::Immediate,                    2, 2).on(ALL),
+    /* 4A */ op("LSR",  Mode::Accumulator,                  1, 2).on(ALL),
+    /* 4B */ op("PHK",  Mode::Implied,                      1, 3).on(ONLY_65C816),
+    /* 4C */ op("JMP",  Mode::Absolute(Offset::None),       3, 3).on(ALL),
+    /* 4D */ op("EOR",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 4E */ op("LSR",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* 4F */ op("EOR",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* 50 */ op("BVC",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* 51 */ op("EOR",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* 52 */ op("EOR",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* 53 */ op("EOR",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* 54 */ op("MVN",  Mode::BlockMove,                    3, 7).on(ONLY_65C816),
+    /* 55 */ op("EOR",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 56 */ op("LSR",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* 57 */ op("EOR",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* 58 */ op("CLI",  Mode::Implied,                      1, 2).on(ALL),
+    /* 59 */ op("EOR",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* 5A */ op("PHY",  Mode::Implied,                      1, 3).on(CMOS),
+    /* 5B */ op("TCD",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* 5C */ op("JML",  Mode::AbsoluteLong(Offset::None),   4, 4).on(ONLY_65C816),
+    /* 5D */ op("EOR",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* 5E */ op("LSR",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* 5F */ op("EOR",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* 60 */ op("RTS",  Mode::Implied,                      1, 6).on(ALL),
+    /* 61 */ op("ADC",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* 62 */ op("PER",  Mode::RelativeLong,                 3, 6).on(ONLY_65C816),
+    /* 63 */ op("ADC",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* 64 */ op("STZ",  Mode::ZeroPage(Offset::None),       2, 3).on(CMOS),
+    /* 65 */ op("ADC",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 66 */ op("ROR",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* 67 */ op("ADC",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* 68 */ op("PLA",  Mode::Implied,                      1, 4).on(ALL),
+    /* 69 */ op("ADC",  Mode::Immediate,                    2, 2).on(ALL),
+    /* 6A */ op("ROR",  Mode::Accumulator,                  1, 2).on(ALL),
+    /* 6B */ op("RTL",  Mode::Implied,                      1, 6).on(ONLY_65C816),
+    /* 6C */ op("JMP",  Mode::Indirect,                     3, 5).on(ALL),
+    /* 6D */ op("ADC",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 6E */ op("ROR",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* 6F */ op("ADC",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* 70 */ op("BVS",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* 71 */ op("ADC",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* 72 */ op("ADC",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* 73 */ op("ADC",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* 74 */ op("STZ",  Mode::ZeroPage(Offset::X),          2, 4).on(CMOS),
+    /* 75 */ op("ADC",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 76 */ op("ROR",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* 77 */ op("ADC",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* 78 */ op("SEI",  Mode::Implied,                      1, 2).on(ALL),
+    /* 79 */ op("ADC",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* 7A */ op("PLY",  Mode::Implied,                      1, 4).on(CMOS),
+    /* 7B */ op("TDC",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* 7C */ op("JMP",  Mode::AbsoluteIndexedIndirect,      3, 6).on(CMOS),
+    /* 7D */ op("ADC",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* 7E */ op("ROR",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* 7F */ op("ADC",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* 80 */ op("BRA",  Mode::Relative,                     2, 3).page_cross().on(CMOS),
+    /* 81 */ op("STA",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* 82 */ op("BRL",  Mode::RelativeLong,                 3, 4).on(ONLY_65C816),
+    /* 83 */ op("STA",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* 84 */ op("STY",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 85 */ op("STA",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 86 */ op("STX",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* 87 */ op("STA",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* 88 */ op("DEY",  Mode::Implied,                      1, 2).on(ALL),
+    /* 89 */ op("BIT",  Mode::Immediate,                    2, 2).on(CMOS),
+    /* 8A */ op("TXA",  Mode::Implied,                      1, 2).on(ALL),
+    /* 8B */ op("PHB",  Mode::Implied,                      1, 3).on(ONLY_65C816),
+    /* 8C */ op("STY",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 8D */ op("STA",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 8E */ op("STX",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* 8F */ op("STA",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* 90 */ op("BCC",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* 91 */ op("STA",  Mode::IndirectIndexed,              2, 6).on(ALL),
+    /* 92 */ op("STA",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* 93 */ op("STA",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* 94 */ op("STY",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 95 */ op("STA",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* 96 */ op("STX",  Mode::ZeroPage(Offset::Y),          2, 4).on(ALL),
+    /* 97 */ op("STA",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* 98 */ op("TYA",  Mode::Implied,                      1, 2).on(ALL),
+    /* 99 */ op("STA",  Mode::Absolute(Offset::Y),          3, 5).on(ALL),
+    /* 9A */ op("TXS",  Mode::Implied,                      1, 2).on(ALL),
+    /* 9B */ op("TXY",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* 9C */ op("STZ",  Mode::Absolute(Offset::None),       3, 4).on(CMOS),
+    /* 9D */ op("STA",  Mode::Absolute(Offset::X),          3, 5).on(ALL),
+    /* 9E */ op("STZ",  Mode::Absolute(Offset::X),          3, 5).on(CMOS),
+    /* 9F */ op("STA",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* A0 */ op("LDY",  Mode::Immediate,                    2, 2).on(ALL),
+    /* A1 */ op("LDA",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* A2 */ op("LDX",  Mode::Immediate,                    2, 2).on(ALL),
+    /* A3 */ op("LDA",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* A4 */ op("LDY",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* A5 */ op("LDA",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* A6 */ op("LDX",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* A7 */ op("LDA",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* A8 */ op("TAY",  Mode::Implied,                      1, 2).on(ALL),
+    /* A9 */ op("LDA",  Mode::Immediate,                    2, 2).on(ALL),
+    /* AA */ op("TAX",  Mode::Implied,                      1, 2).on(ALL),
+    /* AB */ op("PLB",  Mode::Implied,                      1, 4).on(ONLY_65C816),
+    /* AC */ op("LDY",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* AD */ op("LDA",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* AE */ op("LDX",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* AF */ op("LDA",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* B0 */ op("BCS",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* B1 */ op("LDA",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* B2 */ op("LDA",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* B3 */ op("LDA",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* B4 */ op("LDY",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* B5 */ op("LDA",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* B6 */ op("LDX",  Mode::ZeroPage(Offset::Y),          2, 4).on(ALL),
+    /* B7 */ op("LDA",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* B8 */ op("CLV",  Mode::Implied,                      1, 2).on(ALL),
+    /* B9 */ op("LDA",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* BA */ op("TSX",  Mode::Implied,                      1, 2).on(ALL),
+    /* BB */ op("TYX",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* BC */ op("LDY",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* BD */ op("LDA",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* BE */ op("LDX",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* BF */ op("LDA",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* C0 */ op("CPY",  Mode::Immediate,                    2, 2).on(ALL),
+    /* C1 */ op("CMP",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* C2 */ op("REP",  Mode::Immediate,                    2, 3).on(ONLY_65C816),
+    /* C3 */ op("CMP",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* C4 */ op("CPY",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* C5 */ op("CMP",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* C6 */ op("DEC",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* C7 */ op("CMP",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* C8 */ op("INY",  Mode::Implied,                      1, 2).on(ALL),
+    /* C9 */ op("CMP",  Mode::Immediate,                    2, 2).on(ALL),
+    /* CA */ op("DEX",  Mode::Implied,                      1, 2).on(ALL),
+    /* CB */ op("WAI",  Mode::Implied,                      1, 3).on(CMOS),
+    /* CC */ op("CPY",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* CD */ op("CMP",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* CE */ op("DEC",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* CF */ op("CMP",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* D0 */ op("BNE",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* D1 */ op("CMP",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* D2 */ op("CMP",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* D3 */ op("CMP",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* D4 */ op("PEI",  Mode::ZeroPageIndirect,             2, 6).on(ONLY_65C816),
+    /* D5 */ op("CMP",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* D6 */ op("DEC",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* D7 */ op("CMP",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* D8 */ op("CLD",  Mode::Implied,                      1, 2).on(ALL),
+    /* D9 */ op("CMP",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* DA */ op("PHX",  Mode::Implied,                      1, 3).on(CMOS),
+    /* DB */ op("STP",  Mode::Implied,                      1, 3).on(CMOS),
+    /* DC */ op("JML",  Mode::AbsoluteIndirectLong,         3, 6).on(ONLY_65C816),
+    /* DD */ op("CMP",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* DE */ op("DEC",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* DF */ op("CMP",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+    /* E0 */ op("CPX",  Mode::Immediate,                    2, 2).on(ALL),
+    /* E1 */ op("SBC",  Mode::IndexedIndirect,              2, 6).on(ALL),
+    /* E2 */ op("SEP",  Mode::Immediate,                    2, 3).on(ONLY_65C816),
+    /* E3 */ op("SBC",  Mode::StackRelative,                2, 4).on(ONLY_65C816),
+    /* E4 */ op("CPX",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* E5 */ op("SBC",  Mode::ZeroPage(Offset::None),       2, 3).on(ALL),
+    /* E6 */ op("INC",  Mode::ZeroPage(Offset::None),       2, 5).on(ALL),
+    /* E7 */ op("SBC",  Mode::IndirectLong,                 2, 6).on(ONLY_65C816),
+    /* E8 */ op("INX",  Mode::Implied,                      1, 2).on(ALL),
+    /* E9 */ op("SBC",  Mode::Immediate,                    2, 2).on(ALL),
+    /* EA */ op("NOP",  Mode::Implied,                      1, 2).on(ALL),
+    /* EB */ op("XBA",  Mode::Implied,                      1, 3).on(ONLY_65C816),
+    /* EC */ op("CPX",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* ED */ op("SBC",  Mode::Absolute(Offset::None),       3, 4).on(ALL),
+    /* EE */ op("INC",  Mode::Absolute(Offset::None),       3, 6).on(ALL),
+    /* EF */ op("SBC",  Mode::AbsoluteLong(Offset::None),   4, 5).on(ONLY_65C816),
+    /* F0 */ op("BEQ",  Mode::Relative,                     2, 2).page_cross().on(ALL),
+    /* F1 */ op("SBC",  Mode::IndirectIndexed,              2, 5).page_cross().on(ALL),
+    /* F2 */ op("SBC",  Mode::ZeroPageIndirect,             2, 5).on(CMOS),
+    /* F3 */ op("SBC",  Mode::StackRelativeIndirectIndexed, 2, 7).on(ONLY_65C816),
+    /* F4 */ op("PEA",  Mode::Absolute(Offset::None),       3, 5).on(ONLY_65C816),
+    /* F5 */ op("SBC",  Mode::ZeroPage(Offset::X),          2, 4).on(ALL),
+    /* F6 */ op("INC",  Mode::ZeroPage(Offset::X),          2, 6).on(ALL),
+    /* F7 */ op("SBC",  Mode::IndirectLongIndexed,          2, 6).on(ONLY_65C816),
+    /* F8 */ op("SED",  Mode::Implied,                      1, 2).on(ALL),
+    /* F9 */ op("SBC",  Mode::Absolute(Offset::Y),          3, 4).page_cross().on(ALL),
+    /* FA */ op("PLX",  Mode::Implied,                      1, 4).on(CMOS),
+    /* FB */ op("XCE",  Mode::Implied,                      1, 2).on(ONLY_65C816),
+    /* FC */ op("JSR",  Mode::AbsoluteIndexedIndirect,      3, 8).on(ONLY_65C816),
+    /* FD */ op("SBC",  Mode::Absolute(Offset::X),          3, 4).page_cross().on(ALL),
+    /* FE */ op("INC",  Mode::Absolute(Offset::X),          3, 7).on(ALL),
+    /* FF */ op("SBC",  Mode::AbsoluteLong(Offset::X),      4, 5).on(ONLY_65C816),
+];
+
+impl Variant {
+    /// The instruction table for this variant, indexed by opcode.
+    pub fn instructions(self) -> &'static [Instruction; 256] {
+        match self {
+            Variant::Cmos65C02 => &CMOS_INSTRUCTIONS,
+            Variant::W65C816 => &W65C816_INSTRUCTIONS,
+            _ => &NMOS_INSTRUCTIONS,
+        }
+    }
+}
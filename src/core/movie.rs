@@ -0,0 +1,456 @@
+//! Recording and replaying everything that comes into the CPU from the
+//! outside, so a run can be reproduced down to the cycle.
+//!
+//! A [`Movie`] starts from a save state and logs the data of every read
+//! from the I/O ranges it was given or from the 6510 port pins, along
+//! with each change of the IRQ, NMI, SO and RDY lines the core sees,
+//! whether the change came from the `set_*` methods or from the bus.
+//! During playback the core answers those reads and line samples from
+//! the movie instead. The bus is still accessed as usual, so devices
+//! keep running, but their data is ignored.
+//!
+//! To catch a playback going its own way, the movie also logs a
+//! fingerprint of the registers at an instruction boundary every so many
+//! cycles. The first fingerprint, I/O read address or cycle stamp that
+//! doesn't match is reported as a [`Divergence`].
+
+use std::ops::RangeInclusive;
+
+use crate::{error::StateError, traits::Bus};
+
+use super::{
+    save::{chunks, container, Writer},
+    Core,
+};
+
+const MAGIC: &[u8; 4] = b"MOSM";
+const STATE_CHUNK: &[u8; 4] = b"STAT";
+const EVENTS_CHUNK: &[u8; 4] = b"EVNT";
+
+/// An input line of the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Line {
+    Irq,
+    Nmi,
+    So,
+    Rdy,
+}
+
+impl Line {
+    const ALL: [Line; 4] = [Line::Irq, Line::Nmi, Line::So, Line::Rdy];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// A recorded run, from [`Core::stop_recording`]. Save it with
+/// [`Movie::to_bytes`] and play it back with [`Core::start_playback`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Movie {
+    /// Save state of the core and bus when recording started.
+    state: Vec<u8>,
+    io: Vec<RangeInclusive<u32>>,
+    sync_interval: u64,
+    /// Line levels when recording started.
+    levels: [bool; 4],
+    /// Cycle, address and data of every I/O read.
+    reads: Vec<(u64, u32, u8)>,
+    /// Sample position, line and new level of every line change. Lines
+    /// are sampled both during a cycle and between cycles, so a position
+    /// is twice the cycle count, plus one between cycles.
+    lines: Vec<(u64, Line, bool)>,
+    /// Cycle and register fingerprint at instruction boundaries.
+    syncs: Vec<(u64, u64)>,
+}
+
+impl Movie {
+    /// The save state the movie starts from.
+    pub fn initial_state(&self) -> &[u8] {
+        &self.state
+    }
+
+    /// Encodes the movie in the same versioned, checksummed container as
+    /// [`Core::save_state`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.u32(self.io.len() as u32);
+        for range in &self.io {
+            w.u32(*range.start());
+            w.u32(*range.end());
+        }
+        w.u64(self.sync_interval);
+        for level in self.levels {
+            w.bool(level);
+        }
+        w.u32(self.reads.len() as u32);
+        for &(cycle, addr, byte) in &self.reads {
+            w.u64(cycle);
+            w.u32(addr);
+            w.u8(byte);
+        }
+        w.u32(self.lines.len() as u32);
+        for &(position, line, level) in &self.lines {
+            w.u64(position);
+            w.u8(line.index() as u8);
+            w.bool(level);
+        }
+        w.u32(self.syncs.len() as u32);
+        for &(cycle, fingerprint) in &self.syncs {
+            w.u64(cycle);
+            w.u64(fingerprint);
+        }
+
+        let events = w.into_bytes();
+        container(
+            MAGIC,
+            &[(STATE_CHUNK, &self.state), (EVENTS_CHUNK, &events)],
+        )
+    }
+
+    /// Decodes a movie written by [`Movie::to_bytes`].
+    pub fn from_bytes(data: &[u8]) -> Result<Self, StateError> {
+        let chunks = chunks(MAGIC, data)?;
+        let state = chunks.get(STATE_CHUNK, "STAT")?;
+        let r = &mut chunks.reader(EVENTS_CHUNK, "EVNT")?;
+
+        let io = (0..r.u32()?)
+            .map(|_| Ok(r.u32()?..=r.u32()?))
+            .collect::<Result<_, StateError>>()?;
+        let sync_interval = r.u64()?;
+        let levels = [r.bool()?, r.bool()?, r.bool()?, r.bool()?];
+        let reads = (0..r.u32()?)
+            .map(|_| Ok((r.u64()?, r.u32()?, r.u8()?)))
+            .collect::<Result<_, StateError>>()?;
+        let lines = (0..r.u32()?)
+            .map(|_| {
+                let position = r.u64()?;
+                let line = *Line::ALL
+                    .get(r.u8()? as usize)
+                    .ok_or(StateError::Invalid("unknown line"))?;
+                Ok((position, line, r.bool()?))
+            })
+            .collect::<Result<_, StateError>>()?;
+        let syncs = (0..r.u32()?)
+            .map(|_| Ok((r.u64()?, r.u64()?)))
+            .collect::<Result<_, StateError>>()?;
+
+        Ok(Self {
+            state: state.to_vec(),
+            io,
+            sync_interval,
+            levels,
+            reads,
+            lines,
+            syncs,
+        })
+    }
+
+    fn is_io(&self, addr: u32) -> bool {
+        self.io.iter().any(|range| range.contains(&addr))
+    }
+}
+
+/// Where a playback first stopped matching its movie.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divergence {
+    /// The earlier of the cycle the movie expected and the one the
+    /// playback got to.
+    pub cycle: u64,
+    pub reason: DivergenceReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DivergenceReason {
+    /// An I/O read came at another cycle or from another address than
+    /// recorded.
+    Read { expected: u32, actual: u32 },
+    /// The registers differed at an instruction boundary, or the boundary
+    /// fell on another cycle.
+    State,
+}
+
+#[derive(Debug)]
+pub(super) enum MovieMode {
+    Recording {
+        movie: Movie,
+        levels: [bool; 4],
+        next_sync: u64,
+    },
+    Playing {
+        movie: Movie,
+        levels: [bool; 4],
+        read: usize,
+        line: usize,
+        sync: usize,
+        divergence: Option<Divergence>,
+    },
+}
+
+impl<B: Bus> Core<B> {
+    /// Starts recording a [`Movie`] from the current state. Reads from
+    /// addresses in `io` are logged, and the registers are checked every
+    /// `sync_interval` cycles; 1 checks every instruction. Stops a
+    /// playback in progress.
+    pub fn start_recording(
+        &mut self,
+        io: impl IntoIterator<Item = RangeInclusive<u32>>,
+        sync_interval: u64,
+    ) {
+        self.movie = None;
+        let levels = Line::ALL.map(|line| self.live_level(line));
+        let movie = Movie {
+            state: self.save_state(),
+            io: io.into_iter().collect(),
+            sync_interval: sync_interval.max(1),
+            levels,
+            reads: Vec::new(),
+            lines: Vec::new(),
+            syncs: Vec::new(),
+        };
+        self.movie = Some(MovieMode::Recording {
+            movie,
+            levels,
+            next_sync: self.cycles,
+        });
+    }
+
+    /// Stops recording and returns the movie, or `None` if the core
+    /// wasn't recording.
+    pub fn stop_recording(&mut self) -> Option<Movie> {
+        match self.movie.take() {
+            Some(MovieMode::Recording { movie, .. }) => Some(movie),
+            other => {
+                self.movie = other;
+                None
+            }
+        }
+    }
+
+    /// Loads the state the movie starts from and plays it back. Stops a
+    /// recording in progress.
+    pub fn start_playback(&mut self, movie: Movie) -> Result<(), StateError> {
+        self.movie = None;
+        self.load_state(&movie.state)?;
+        self.movie = Some(MovieMode::Playing {
+            levels: movie.levels,
+            movie,
+            read: 0,
+            line: 0,
+            sync: 0,
+            divergence: None,
+        });
+        Ok(())
+    }
+
+    /// Stops playing back and returns where it diverged, if it did.
+    pub fn stop_playback(&mut self) -> Option<Divergence> {
+        let divergence = self.divergence();
+        if let Some(MovieMode::Playing { .. }) = self.movie {
+            self.movie = None;
+        }
+        divergence
+    }
+
+    /// The first point where the playback in progress stopped matching
+    /// its movie.
+    pub fn divergence(&self) -> Option<Divergence> {
+        match &self.movie {
+            Some(MovieMode::Playing { divergence, .. }) => *divergence,
+            _ => None,
+        }
+    }
+
+    /// Whether a playback has used up every event in its movie.
+    pub fn playback_finished(&self) -> bool {
+        match &self.movie {
+            Some(MovieMode::Playing {
+                movie,
+                read,
+                line,
+                sync,
+                ..
+            }) => {
+                *read == movie.reads.len()
+                    && *line == movie.lines.len()
+                    && *sync == movie.syncs.len()
+            }
+            _ => false,
+        }
+    }
+
+    /// Starts a recording taken off the core over from the current state,
+    /// with the same settings. A playback isn't put back.
+    pub(super) fn restart_movie(&mut self, mode: Option<MovieMode>) {
+        if let Some(MovieMode::Recording { movie, .. }) = mode {
+            self.start_recording(movie.io, movie.sync_interval);
+        }
+    }
+
+    fn live_level(&self, line: Line) -> bool {
+        match line {
+            Line::Irq => self.irq_line || self.bus.irq(),
+            Line::Nmi => self.nmi_line || self.bus.nmi(),
+            Line::So => self.so_line || self.bus.so(),
+            Line::Rdy => self.rdy_line && self.bus.rdy(),
+        }
+    }
+
+    /// The level of an input line, as recorded or played back.
+    pub(super) fn line_level(&mut self, line: Line) -> bool {
+        let live = self.live_level(line);
+        let position = self.cycles * 2 + !self.clocking as u64;
+        match &mut self.movie {
+            None => live,
+            Some(MovieMode::Recording { movie, levels, .. }) => {
+                if levels[line.index()] != live {
+                    levels[line.index()] = live;
+                    movie.lines.push((position, line, live));
+                }
+                live
+            }
+            Some(MovieMode::Playing {
+                movie,
+                levels,
+                line: next,
+                ..
+            }) => {
+                while let Some(&(at, changed, level)) = movie.lines.get(*next) {
+                    if at > position {
+                        break;
+                    }
+                    levels[changed.index()] = level;
+                    *next += 1;
+                }
+                levels[line.index()]
+            }
+        }
+    }
+
+    /// Catches up on NMI and SO edges that were recorded between
+    /// instructions. A live run samples those in `set_nmi` and `set_so`,
+    /// before the next instruction decides whether to take an interrupt.
+    pub(super) fn replay_edges(&mut self) {
+        if matches!(self.movie, Some(MovieMode::Playing { .. })) {
+            self.sample_nmi();
+            self.sample_so();
+        }
+    }
+
+    /// The data of a read from the bus, as recorded or played back.
+    pub(super) fn movie_read(&mut self, addr: u32, live: u8) -> u8 {
+        self.movie_input(addr, live, false)
+    }
+
+    /// The level of the 6510 port input pins, as recorded or played
+    /// back. They are always driven from outside, so they go into the
+    /// movie whatever I/O ranges it was given.
+    pub(super) fn movie_port_input(&mut self, live: u8) -> u8 {
+        self.movie_input(1, live, true)
+    }
+
+    /// Records or plays back a byte read at `addr`, if it is I/O.
+    fn movie_input(&mut self, addr: u32, live: u8, io: bool) -> u8 {
+        let cycle = self.cycles;
+        match &mut self.movie {
+            Some(MovieMode::Recording { movie, .. }) if io || movie.is_io(addr) => {
+                movie.reads.push((cycle, addr, live));
+                live
+            }
+            Some(MovieMode::Playing {
+                movie,
+                read,
+                divergence,
+                ..
+            }) if io || movie.is_io(addr) => {
+                let Some(&(at, expected, byte)) = movie.reads.get(*read) else {
+                    return live;
+                };
+                if (at, expected) != (cycle, addr) && divergence.is_none() {
+                    *divergence = Some(Divergence {
+                        cycle: at.min(cycle),
+                        reason: DivergenceReason::Read {
+                            expected,
+                            actual: addr,
+                        },
+                    });
+                }
+                if expected != addr {
+                    return live;
+                }
+                *read += 1;
+                byte
+            }
+            _ => live,
+        }
+    }
+
+    /// Logs or checks the registers at an instruction boundary.
+    pub(super) fn sync_movie(&mut self) {
+        if self.movie.is_none() {
+            return;
+        }
+
+        let cycle = self.cycles;
+        let fingerprint = self.fingerprint();
+        match &mut self.movie {
+            Some(MovieMode::Recording {
+                movie, next_sync, ..
+            }) if cycle >= *next_sync => {
+                movie.syncs.push((cycle, fingerprint));
+                *next_sync = cycle + movie.sync_interval;
+            }
+            Some(MovieMode::Playing {
+                movie,
+                sync,
+                divergence,
+                ..
+            }) => {
+                let Some(&(at, expected)) = movie.syncs.get(*sync) else {
+                    return;
+                };
+                if at > cycle {
+                    return;
+                }
+                *sync += 1;
+                if (at, expected) != (cycle, fingerprint) && divergence.is_none() {
+                    *divergence = Some(Divergence {
+                        cycle: at,
+                        reason: DivergenceReason::State,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// FNV-1a over the registers.
+    fn fingerprint(&self) -> u64 {
+        let state = self.state();
+        let [pc_low, pc_high] = state.pc.to_le_bytes();
+        let [dp_low, dp_high] = state.dp.to_le_bytes();
+        [
+            state.acc,
+            state.idx,
+            state.idy,
+            state.sp,
+            pc_low,
+            pc_high,
+            state.status.as_byte(),
+            state.status.native_byte(),
+            state.acc_hi,
+            state.idx_hi,
+            state.idy_hi,
+            state.sp_hi,
+            dp_low,
+            dp_high,
+            state.dbr,
+            state.pbr,
+            state.emulation as u8,
+        ]
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+    }
+}
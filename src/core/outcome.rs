@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// What a single [`Core::step`][super::Core::step] executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepOutcome {
+    /// The opcode that was executed. Interrupt sequences report $00,
+    /// the BRK they are built from, and a WAI that is still waiting
+    /// reports $CB.
+    pub opcode: u8,
+    /// Where the opcode was fetched from, including the 65C816 program
+    /// bank. For an interrupt, the address of the instruction it
+    /// preempted.
+    pub address: u32,
+    /// Clock cycles spent, including stolen ones.
+    pub cycles: u32,
+    /// Cycles the CPU spent waiting on RDY, for example while a DMA
+    /// controller had the bus.
+    pub stolen: u32,
+    /// Set when RDY held the CPU before the instruction finished. The
+    /// next step carries on with it. The other fields describe the
+    /// instruction so far, and the opcode is the byte on the bus if RDY
+    /// held its fetch.
+    pub stalled: bool,
+}
+
+/// Why the core stopped executing. Only a reset clears it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HaltReason {
+    /// A CMOS core executed STP.
+    Stopped,
+    /// An NMOS core executed one of its JAM opcodes, which lock up the
+    /// bus on real hardware.
+    Jammed,
+    /// The core fetched an opcode it doesn't know how to execute, for
+    /// example an undocumented one with undocumented opcodes disabled.
+    IllegalOpcode,
+    /// The opcode was decoded with an addressing mode its instruction
+    /// doesn't support. This is a bug in the core.
+    InvalidAddressingMode(u8),
+}
+
+impl fmt::Display for HaltReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HaltReason::Stopped => write!(f, "stopped by STP"),
+            HaltReason::Jammed => write!(f, "jammed"),
+            HaltReason::IllegalOpcode => write!(f, "illegal opcode"),
+            HaltReason::InvalidAddressingMode(opcode) => {
+                write!(f, "invalid addressing mode for opcode {opcode:#04x}")
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! What to do with opcodes the core can't execute.
+//!
+//! On the NMOS variants these are the JAM opcodes, plus every
+//! undocumented opcode while undocumented opcodes are disabled. The CMOS
+//! chips assign all 256 opcodes, so the policy never applies to them.
+
+use std::{fmt, mem};
+
+use crate::traits::Bus;
+
+use super::{flags::Flags, Core, HaltReason};
+
+/// A handler for [`OpcodePolicy::Trap`]. Returns `false` to decline the
+/// opcode, which then halts the core as [`OpcodePolicy::Halt`] would.
+pub type TrapHandler<B> = Box<dyn FnMut(&mut Trap<'_, B>) -> bool>;
+
+/// How the core handles opcodes it can't execute, set with
+/// [`Core::set_opcode_policy`].
+#[derive(Default)]
+pub enum OpcodePolicy<B: Bus> {
+    /// Stop the core and report the opcode as an error.
+    #[default]
+    Halt,
+    /// Skip the opcode as a NOP. The bus sees the same reads as if the
+    /// opcode ran, but every write is turned into a read of the same
+    /// address, so it takes the right number of bytes and cycles without
+    /// touching memory. JAM opcodes take one byte and two cycles.
+    Nop,
+    /// Lock up like a JAM on real hardware: the core keeps reading $FFFF
+    /// every cycle and ignores interrupts until it is reset. [`Core::run`]
+    /// never returns in this state.
+    Jam,
+    /// Hand the opcode to a user handler, which can emulate it through
+    /// the [`Trap`]. The handler runs once per opcode. Under
+    /// [`Core::tick`], all of its accesses happen on the tick that
+    /// fetched the opcode.
+    Trap(TrapHandler<B>),
+}
+
+impl<B: Bus> fmt::Debug for OpcodePolicy<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpcodePolicy::Halt => write!(f, "Halt"),
+            OpcodePolicy::Nop => write!(f, "Nop"),
+            OpcodePolicy::Jam => write!(f, "Jam"),
+            OpcodePolicy::Trap(_) => write!(f, "Trap(..)"),
+        }
+    }
+}
+
+/// The core as seen by a [`TrapHandler`]. The opcode has already been
+/// fetched; every access made through the trap takes one clock cycle.
+pub struct Trap<'a, B: Bus> {
+    core: &'a mut Core<B>,
+    opcode: u8,
+}
+
+impl<B: Bus> Trap<'_, B> {
+    pub fn opcode(&self) -> u8 {
+        self.opcode
+    }
+
+    /// Reads the next operand byte and advances the program counter.
+    pub fn fetch(&mut self) -> u8 {
+        self.core.fetch()
+    }
+
+    pub fn read(&mut self, addr: u16) -> u8 {
+        self.core.read_bus(addr)
+    }
+
+    pub fn write(&mut self, addr: u16, byte: u8) {
+        self.core.write_bus(addr, byte);
+    }
+
+    pub fn acc(&self) -> u8 {
+        self.core.acc
+    }
+
+    pub fn set_acc(&mut self, value: u8) {
+        self.core.acc = value;
+    }
+
+    pub fn idx(&self) -> u8 {
+        self.core.idx
+    }
+
+    pub fn set_idx(&mut self, value: u8) {
+        self.core.idx = value;
+    }
+
+    pub fn idy(&self) -> u8 {
+        self.core.idy
+    }
+
+    pub fn set_idy(&mut self, value: u8) {
+        self.core.idy = value;
+    }
+
+    pub fn sp(&self) -> u8 {
+        self.core.sp
+    }
+
+    pub fn set_sp(&mut self, value: u8) {
+        self.core.sp = value;
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.core.pc
+    }
+
+    pub fn set_pc(&mut self, value: u16) {
+        self.core.pc = value;
+    }
+
+    pub fn status(&mut self) -> &mut Flags {
+        &mut self.core.status
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Sets how opcodes that the core can't execute are handled.
+    pub fn set_opcode_policy(&mut self, policy: OpcodePolicy<B>) {
+        self.opcode_policy = policy;
+    }
+
+    /// Whether a JAM opcode has locked up the core under
+    /// [`OpcodePolicy::Jam`].
+    pub fn jammed(&self) -> bool {
+        self.jammed.is_some()
+    }
+
+    /// Applies the opcode policy. `reason` is what the core halts with
+    /// under [`OpcodePolicy::Halt`].
+    pub(super) fn unknown_opcode(&mut self, opcode: u8, reason: HaltReason) {
+        match self.opcode_policy {
+            OpcodePolicy::Halt => self.halt(reason),
+            OpcodePolicy::Nop if reason == HaltReason::Jammed => self.dummy_read(self.pc),
+            OpcodePolicy::Nop => self.skip_opcode(opcode),
+            OpcodePolicy::Jam => {
+                self.dummy_read(self.pc);
+                self.jammed = Some(opcode);
+            }
+            OpcodePolicy::Trap(_) => self.trap(opcode, reason),
+        }
+    }
+
+    /// Runs an undocumented opcode with its writes turned into reads and
+    /// throws away everything but the new program counter.
+    fn skip_opcode(&mut self, opcode: u8) {
+        let registers = self.save_registers();
+        self.dry_run = true;
+        self.decode_undocumented(opcode);
+        self.dry_run = false;
+
+        let pc = self.pc;
+        self.restore_registers(registers);
+        self.pc = pc;
+    }
+
+    fn trap(&mut self, opcode: u8, reason: HaltReason) {
+        // a tick running ahead of its real access with discarded data
+        // gets here again once the data is real
+        if !self.replay.live() {
+            return;
+        }
+        let OpcodePolicy::Trap(mut handler) = mem::take(&mut self.opcode_policy) else {
+            return;
+        };
+        // the handler can't be re-run, so the instruction can't be split
+        // over several ticks from here on
+        let replay = self.replay.suspend();
+        let handled = handler(&mut Trap { core: self, opcode });
+        self.replay.resume(replay);
+        self.opcode_policy = OpcodePolicy::Trap(handler);
+
+        if !handled {
+            self.halt(reason);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! The on-chip I/O port of the 6510.
+//!
+//! The data direction register lives at $0000 and the data register at
+//! $0001. Bits set in the direction register are outputs driven by the
+//! CPU; the rest are inputs. The 6510 only has pins for bits 0-5, so
+//! bits 6 and 7 float when set as inputs: they keep the last value that
+//! was driven onto them for a while and then fade to 0.
+
+use crate::{error::StateError, traits::Bus};
+
+use super::{
+    save::{Reader, Writer},
+    AccessKind, Core,
+};
+
+/// Bits of the port without a pin on the package.
+const FLOATING_BITS: u8 = 0xc0;
+
+/// Roughly how many cycles a floating bit holds its charge before it
+/// reads back as 0.
+const FALL_OFF_CYCLES: u32 = 350_000;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(super) struct IoPort {
+    ddr: u8,
+    data: u8,
+    /// Charge left on the floating bits by the last value driven to them.
+    latch: u8,
+    fall_off: u32,
+}
+
+impl IoPort {
+    pub(super) fn save(&self, w: &mut Writer) {
+        w.u8(self.ddr);
+        w.u8(self.data);
+        w.u8(self.latch);
+        w.u32(self.fall_off);
+    }
+
+    pub(super) fn load(r: &mut Reader<'_>) -> Result<Self, StateError> {
+        Ok(Self {
+            ddr: r.u8()?,
+            data: r.u8()?,
+            latch: r.u8()?,
+            fall_off: r.u32()?,
+        })
+    }
+
+    pub(super) fn tick(&mut self) {
+        if self.fall_off > 0 {
+            self.fall_off -= 1;
+            if self.fall_off == 0 {
+                self.latch = 0;
+            }
+        }
+    }
+
+    fn set_ddr(&mut self, ddr: u8) {
+        // floating bits that stop being driven hold their last value
+        let released = self.ddr & !ddr & FLOATING_BITS;
+        if released != 0 {
+            self.latch = (self.latch & !released) | (self.data & released);
+            self.fall_off = FALL_OFF_CYCLES;
+        }
+        self.ddr = ddr;
+        self.charge();
+    }
+
+    fn set_data(&mut self, data: u8) {
+        self.data = data;
+        self.charge();
+    }
+
+    fn charge(&mut self) {
+        let driven = self.ddr & FLOATING_BITS;
+        self.latch = (self.latch & !driven) | (self.data & driven);
+    }
+
+    /// The value seen when reading $0001, given the level of the input pins.
+    fn read(&self, input: u8) -> u8 {
+        let floating = !self.ddr & FLOATING_BITS;
+        let input = !self.ddr & !FLOATING_BITS & input;
+        (self.data & self.ddr) | input | (self.latch & floating)
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Whether an access hits the 6510 I/O port instead of memory.
+    pub(super) fn is_port(&self, addr: u32) -> bool {
+        self.variant == super::Variant::Mos6510 && addr < 2
+    }
+
+    /// The CPU still puts the address on the bus when reading the port,
+    /// but the data comes from the port registers.
+    pub(super) fn read_port(&mut self, addr: u32, kind: AccessKind) -> u8 {
+        self.bus.read_access(addr, kind, self.cycles);
+        match addr {
+            0 => self.port.ddr,
+            _ => {
+                let input = self.movie_port_input(self.bus.port_input());
+                self.port.read(input)
+            }
+        }
+    }
+
+    /// What a read of the port would return, without touching the bus.
+    pub(super) fn peek_port(&self, addr: u32) -> u8 {
+        match addr {
+            0 => self.port.ddr,
+            _ => self.port.read(self.bus.port_input()),
+        }
+    }
+
+    /// Writes also go out on the bus, so the RAM underneath the port sees
+    /// them, before the system is told about the new port state.
+    pub(super) fn write_port(&mut self, addr: u32, byte: u8, kind: AccessKind) {
+        match addr {
+            0 => self.port.set_ddr(byte),
+            _ => self.port.set_data(byte),
+        }
+
+        self.bus.write_access(addr, byte, kind, self.cycles);
+        self.bus.port_changed(self.port.data, self.port.ddr);
+    }
+
+    /// Puts the port back in its power-on state: every bit an input.
+    pub(super) fn reset_port(&mut self) {
+        self.port = Default::default();
+        if self.variant == super::Variant::Mos6510 {
+            self.bus.port_changed(0, 0);
+        }
+    }
+}
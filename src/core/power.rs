@@ -0,0 +1,113 @@
+//! Power-on state.
+//!
+//! Real chips come up with whatever the registers and RAM cells happen to
+//! settle on. Some software, and plenty of copy protection, ends up
+//! depending on it, so the contents can be picked to match a machine.
+
+use crate::traits::Bus;
+
+use super::Core;
+
+/// Initial contents of the registers and RAM for [`Core::power_on`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub enum PowerOn {
+    /// Everything cleared to zero.
+    #[default]
+    Zero,
+    /// Pseudo-random bytes, the same on every run with the same seed.
+    /// The registers and RAM draw from separate streams.
+    Random(u64),
+    /// The pattern repeated over the whole of RAM, for example
+    /// `vec![0x00, 0xff]`. The registers take their values from the
+    /// pattern starting halfway through it, so they don't simply repeat
+    /// the first cells of RAM. An empty pattern acts like
+    /// [`PowerOn::Zero`].
+    Pattern(Vec<u8>),
+}
+
+impl PowerOn {
+    /// An endless stream of bytes following the chosen contents, for RAM.
+    fn contents(&self) -> Contents<'_> {
+        match self {
+            PowerOn::Zero => Contents::Zero,
+            PowerOn::Random(seed) => Contents::Random(*seed),
+            PowerOn::Pattern(pattern) if pattern.is_empty() => Contents::Zero,
+            PowerOn::Pattern(pattern) => Contents::Pattern(pattern, 0),
+        }
+    }
+
+    /// The stream the registers take their values from.
+    fn registers(&self) -> Contents<'_> {
+        match self.contents() {
+            Contents::Random(seed) => Contents::Random(!seed),
+            Contents::Pattern(pattern, _) => Contents::Pattern(pattern, pattern.len() / 2),
+            contents => contents,
+        }
+    }
+}
+
+enum Contents<'a> {
+    Zero,
+    Random(u64),
+    Pattern(&'a [u8], usize),
+}
+
+impl Iterator for Contents<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = match self {
+            Contents::Zero => 0,
+            Contents::Random(state) => {
+                // splitmix64
+                *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = *state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                (z ^ (z >> 31)) as u8
+            }
+            Contents::Pattern(pattern, index) => {
+                let byte = pattern[*index];
+                *index = (*index + 1) % pattern.len();
+                byte
+            }
+        };
+        Some(byte)
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Powers the system up: loads A, X, Y, SP and P with the chosen
+    /// contents, lets the bus fill its RAM through
+    /// [`Bus::power_on`][crate::traits::Bus::power_on], and then runs the
+    /// reset sequence. With [`PowerOn::Zero`], SP ends up at $FD.
+    ///
+    /// Power-on starts a new timeline. The rewind history is dropped and
+    /// taken again from the powered-on state, a recording in progress
+    /// starts over from it with the same settings, and a playback is
+    /// stopped, since a movie can't replay a power cycle.
+    pub fn power_on(&mut self, state: PowerOn) {
+        let rewind = self.rewind.take();
+        let movie = self.movie.take();
+
+        let mut registers = state.registers();
+        self.acc = registers.next().unwrap_or_default();
+        self.idx = registers.next().unwrap_or_default();
+        self.idy = registers.next().unwrap_or_default();
+        self.sp = registers.next().unwrap_or_default();
+        self.status
+            .set_from_stack(registers.next().unwrap_or_default());
+        self.acc_hi = registers.next().unwrap_or_default();
+        self.waiting = false;
+        self.nmi_level = false;
+        self.cycles = 0;
+        self.stolen = 0;
+
+        self.bus.power_on(&mut state.contents());
+        self.reset();
+
+        self.rewind = rewind;
+        self.restart_rewind();
+        self.restart_movie(movie);
+    }
+}
@@ -0,0 +1,175 @@
+//! Rewinding to an earlier point of the emulation.
+//!
+//! A snapshot holds the CPU part of a save state and nothing of the bus.
+//! Memory is kept as an undo log instead: the first time the CPU writes
+//! to a page after a snapshot, the page is read with [`Bus::peek`] and
+//! added to that snapshot, so a typical interval costs a few pages
+//! rather than a copy of all of RAM. Going back writes the pages of
+//! every newer snapshot back, newest first, and dropping the oldest
+//! snapshot never breaks the chain.
+
+use std::collections::{HashSet, VecDeque};
+
+use crate::{error::StateError, traits::Bus};
+
+use super::Core;
+
+/// The snapshots taken so far.
+#[derive(Debug)]
+pub(super) struct Rewind {
+    interval: u64,
+    capacity: usize,
+    /// Cycle count at which the next snapshot is due.
+    next: u64,
+    /// Oldest first.
+    snapshots: VecDeque<Snapshot>,
+    /// Pages already in the undo log of the newest snapshot.
+    saved: HashSet<u32>,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    cycle: u64,
+    /// The `CORE` chunk of a save state.
+    cpu: Vec<u8>,
+    /// What the pages the CPU wrote afterwards held when it was taken.
+    memory: Vec<(u32, u8)>,
+}
+
+impl<B: Bus> Core<B> {
+    /// Starts taking a snapshot every `interval` cycles, keeping up to
+    /// `capacity` of them, so the core can [`rewind`][Core::rewind] about
+    /// `interval * capacity` cycles. The first snapshot is taken right
+    /// away. Any earlier history is dropped.
+    pub fn enable_rewind(&mut self, interval: u64, capacity: usize) {
+        self.rewind = Some(Rewind {
+            interval: interval.max(1),
+            capacity: capacity.max(1),
+            next: self.cycles,
+            snapshots: VecDeque::new(),
+            saved: HashSet::new(),
+        });
+        self.checkpoint();
+    }
+
+    /// Stops taking snapshots and frees the history.
+    pub fn disable_rewind(&mut self) {
+        self.rewind = None;
+    }
+
+    /// Goes back `cycles` cycles: restores the newest snapshot from before
+    /// that point and runs forward from it to the exact instruction. If
+    /// the history doesn't reach that far, stops at the oldest snapshot.
+    /// Returns how many cycles were actually undone, which can be a few
+    /// less than asked for as the core only stops between instructions.
+    ///
+    /// Only memory the CPU wrote and [`Bus::peek`] can see is put back,
+    /// through [`Bus::write_long`]. Devices keep their state, and
+    /// running forward replays what the bus returns now, so they can make
+    /// it go differently the second time. The tracers and a movie being
+    /// recorded or played are detached while running forward. The
+    /// history after the new position is dropped.
+    pub fn rewind(&mut self, cycles: u64) -> Result<u64, StateError> {
+        let Some(mut rewind) = self.rewind.take() else {
+            return Ok(0);
+        };
+
+        let now = self.cycles;
+        let target = now.saturating_sub(cycles);
+        let mut result = Ok(());
+        while let Some(mut snapshot) = rewind.snapshots.pop_back() {
+            for &(addr, byte) in &snapshot.memory {
+                self.bus.write_long(addr, byte);
+            }
+            if snapshot.cycle > target && !rewind.snapshots.is_empty() {
+                continue;
+            }
+
+            snapshot.memory.clear();
+            rewind.saved.clear();
+            rewind.next = snapshot.cycle + rewind.interval;
+            result = self.load_cpu(&snapshot.cpu);
+            rewind.snapshots.push_back(snapshot);
+            break;
+        }
+        self.rewind = Some(rewind);
+        result?;
+
+        if self.cycles < target {
+            let tracer = self.tracer.take();
+            let bus_trace = self.bus_trace.take();
+            let movie = self.movie.take();
+            self.run_for_cycles(target - self.cycles);
+            self.tracer = tracer;
+            self.bus_trace = bus_trace;
+            self.movie = movie;
+        }
+        Ok(now.saturating_sub(self.cycles))
+    }
+
+    /// Drops the history and takes the first snapshot again, keeping the
+    /// interval and capacity.
+    pub(super) fn restart_rewind(&mut self) {
+        let Some(rewind) = &mut self.rewind else {
+            return;
+        };
+        rewind.next = self.cycles;
+        rewind.snapshots.clear();
+        self.checkpoint();
+    }
+
+    /// Takes a snapshot if one is due. Called between instructions.
+    pub(super) fn checkpoint(&mut self) {
+        let Some(rewind) = &self.rewind else {
+            return;
+        };
+        if self.cycles < rewind.next {
+            return;
+        }
+
+        let cpu = self.save_cpu();
+        let Some(rewind) = &mut self.rewind else {
+            return;
+        };
+        // the cycle counter went back, so it's a new timeline
+        if rewind
+            .snapshots
+            .back()
+            .is_some_and(|s| s.cycle > self.cycles)
+        {
+            rewind.snapshots.clear();
+        }
+        rewind.snapshots.push_back(Snapshot {
+            cycle: self.cycles,
+            cpu,
+            memory: Vec::new(),
+        });
+        if rewind.snapshots.len() > rewind.capacity {
+            rewind.snapshots.pop_front();
+        }
+        rewind.saved.clear();
+        rewind.next = self.cycles + rewind.interval;
+    }
+
+    /// Adds the page holding `addr` to the undo log of the newest
+    /// snapshot, unless it is there already. Called before every write
+    /// the CPU puts on the bus.
+    pub(super) fn save_page(&mut self, addr: u32) {
+        let Some(rewind) = &mut self.rewind else {
+            return;
+        };
+        let page = addr >> 8;
+        let Some(snapshot) = rewind.snapshots.back_mut() else {
+            return;
+        };
+        if !rewind.saved.insert(page) {
+            return;
+        }
+
+        let start = page << 8;
+        let bus = &self.bus;
+        snapshot
+            .memory
+            .extend((start..start + 0x100).filter_map(|addr| Some((addr, bus.peek(addr)?))));
+    }
+}
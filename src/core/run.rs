@@ -0,0 +1,106 @@
+//! Running the core for a budget of cycles or instructions, or until
+//! something happens.
+
+use crate::traits::Bus;
+
+use super::{Core, HaltReason};
+
+/// What a run method did, returned by [`Core::run_for_cycles`] and the
+/// other budgeted runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunOutcome {
+    /// Clock cycles spent, including stolen ones.
+    pub cycles: u64,
+    /// Instructions executed, counting interrupt sequences and every
+    /// cycle of sleep after WAI as one each.
+    pub instructions: u64,
+    pub reason: StopReason,
+}
+
+/// Why a run method returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// The cycle budget ran out.
+    Cycles,
+    /// The instruction budget ran out.
+    Instructions,
+    /// The program counter reached the requested address.
+    Pc,
+    /// The predicate returned `true`.
+    Predicate,
+    /// The core halted, or already was halted when the run started.
+    Halted(HaltReason),
+    /// RDY held the CPU in the middle of an instruction. The next run or
+    /// step carries on with it, once the line is released.
+    Stalled,
+}
+
+impl<B: Bus> Core<B> {
+    /// Clock cycles elapsed since the core was created or powered on,
+    /// including stolen ones. It only goes up while the core runs, but
+    /// [`Core::power_on`] resets it to zero, and loading a save state or
+    /// rewinding puts back the count from that point in time.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Runs whole instructions until at least `cycles` cycles have passed.
+    /// The last instruction usually overshoots the budget; the outcome
+    /// says by how much, so a frame loop can carry it over.
+    pub fn run_for_cycles(&mut self, cycles: u64) -> RunOutcome {
+        let end = self.cycles.saturating_add(cycles);
+        self.run_while(|core, _| (core.cycles >= end).then_some(StopReason::Cycles))
+    }
+
+    /// Runs exactly `instructions` instructions, unless the core halts.
+    pub fn run_for_instructions(&mut self, instructions: u64) -> RunOutcome {
+        self.run_while(|_, executed| (executed >= instructions).then_some(StopReason::Instructions))
+    }
+
+    /// Runs until the next instruction to execute is at `addr`, which
+    /// includes the program bank on the 65C816. At least one instruction
+    /// is executed, so a run can start from a breakpoint.
+    pub fn run_until_pc(&mut self, addr: u32) -> RunOutcome {
+        self.run_until_inner(|core| core.program_addr() == addr, StopReason::Pc)
+    }
+
+    /// Runs until `done` returns `true`. It is checked after every
+    /// instruction, starting with the first one.
+    pub fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> RunOutcome {
+        self.run_until_inner(|core| done(core), StopReason::Predicate)
+    }
+
+    fn run_until_inner(
+        &mut self,
+        mut done: impl FnMut(&Self) -> bool,
+        reason: StopReason,
+    ) -> RunOutcome {
+        self.run_while(|core, executed| (executed > 0 && done(core)).then_some(reason))
+    }
+
+    /// Steps until `stop` returns a reason, given the number of
+    /// instructions executed so far, or until the core halts or stalls.
+    fn run_while(&mut self, mut stop: impl FnMut(&Self, u64) -> Option<StopReason>) -> RunOutcome {
+        let start = self.cycles;
+        let mut instructions = 0;
+        let reason = loop {
+            if let Some(reason) = self.halt {
+                break StopReason::Halted(reason);
+            }
+            if let Some(reason) = stop(self, instructions) {
+                break reason;
+            }
+            match self.step() {
+                Ok(outcome) if outcome.stalled => break StopReason::Stalled,
+                // a halt is reported through `self.halt` on the next pass
+                _ => instructions += 1,
+            }
+        };
+
+        RunOutcome {
+            cycles: self.cycles - start,
+            instructions,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Save states.
+//!
+//! A save state starts with the magic bytes `MOSS` and a little-endian
+//! format version, followed by chunks. Each chunk is a four byte tag, the
+//! length of its data as a little-endian `u32`, the data, and the CRC-32
+//! of the data. The `CORE` chunk holds the CPU and the `BUS ` chunk
+//! whatever [`Bus::save_state`] wrote. Chunks with unknown tags are
+//! skipped, so newer versions can add their own without breaking older
+//! readers. The chunks are decoded with the layout of the version in the
+//! header, so states from older versions keep loading.
+//!
+//! Version 2 stores the opcode of an invalid addressing mode halt
+//! instead of the name of the instruction, and adds the unstable magic
+//! value after the magic constant.
+
+use crate::{error::StateError, traits::Bus};
+
+use super::{
+    flags::Flags,
+    port::IoPort,
+    tick::{Registers, Replay},
+    Core, HaltReason, Variant,
+};
+
+const MAGIC: &[u8; 4] = b"MOSS";
+
+/// The format version written by this crate. States with a higher
+/// version are refused.
+pub const STATE_VERSION: u16 = 2;
+
+const CORE_CHUNK: &[u8; 4] = b"CORE";
+const BUS_CHUNK: &[u8; 4] = b"BUS ";
+
+/// Little-endian encoder for the fields of a chunk.
+#[derive(Default)]
+pub(super) struct Writer {
+    data: Vec<u8>,
+}
+
+impl Writer {
+    pub(super) fn u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub(super) fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    pub(super) fn u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(super) fn u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub(super) fn u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// A byte string, prefixed with its length.
+    pub(super) fn bytes(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub(super) fn flags(&mut self, flags: Flags) {
+        self.u8(flags.as_byte());
+        self.u8(flags.native_byte());
+    }
+
+    pub(super) fn halt(&mut self, halt: Option<HaltReason>) {
+        match halt {
+            None => self.u8(0),
+            Some(HaltReason::Stopped) => self.u8(1),
+            Some(HaltReason::Jammed) => self.u8(2),
+            Some(HaltReason::IllegalOpcode) => self.u8(3),
+            Some(HaltReason::InvalidAddressingMode(opcode)) => {
+                self.u8(4);
+                self.u8(opcode);
+            }
+        }
+    }
+
+    pub(super) fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Decoder matching [`Writer`], for data written by the given format
+/// version.
+pub(super) struct Reader<'a> {
+    data: &'a [u8],
+    version: u16,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(data: &'a [u8], version: u16) -> Self {
+        Self { data, version }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], StateError> {
+        if self.data.len() < len {
+            return Err(StateError::Truncated);
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], StateError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    pub(super) fn u8(&mut self) -> Result<u8, StateError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub(super) fn bool(&mut self) -> Result<bool, StateError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(StateError::Invalid("boolean out of range")),
+        }
+    }
+
+    pub(super) fn u16(&mut self) -> Result<u16, StateError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    pub(super) fn u32(&mut self) -> Result<u32, StateError> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    pub(super) fn u64(&mut self) -> Result<u64, StateError> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    pub(super) fn bytes(&mut self) -> Result<&'a [u8], StateError> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    pub(super) fn flags(&mut self) -> Result<Flags, StateError> {
+        let byte = self.u8()?;
+        let mut flags = Flags::new();
+        flags.set_native_byte(self.u8()?);
+        flags.set_break(byte & 0x10 != 0);
+        Ok(flags)
+    }
+
+    pub(super) fn halt(&mut self) -> Result<Option<HaltReason>, StateError> {
+        Ok(match self.u8()? {
+            0 => None,
+            1 => Some(HaltReason::Stopped),
+            2 => Some(HaltReason::Jammed),
+            3 => Some(HaltReason::IllegalOpcode),
+            // version 1 only kept the name of the instruction, which
+            // doesn't tell which opcode it was
+            4 if self.version < 2 => {
+                return Err(StateError::Invalid(
+                    "invalid addressing mode halt from version 1",
+                ))
+            }
+            4 => Some(HaltReason::InvalidAddressingMode(self.u8()?)),
+            _ => return Err(StateError::Invalid("unknown halt reason")),
+        })
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+/// The tag and data of a chunk.
+pub(super) type Chunk<'a> = ([u8; 4], &'a [u8]);
+
+/// The chunks of a container, and the format version they were written
+/// with.
+pub(super) struct Chunks<'a> {
+    pub(super) version: u16,
+    pub(super) chunks: Vec<Chunk<'a>>,
+}
+
+impl<'a> Chunks<'a> {
+    /// The data of the chunk tagged `tag`, which is `name` in errors.
+    pub(super) fn get(&self, tag: &[u8; 4], name: &'static str) -> Result<&'a [u8], StateError> {
+        self.chunks
+            .iter()
+            .find(|(found, _)| found == tag)
+            .map(|&(_, data)| data)
+            .ok_or(StateError::MissingChunk(name))
+    }
+
+    /// A reader for the chunk tagged `tag`, decoding it by the version
+    /// of the container.
+    pub(super) fn reader(
+        &self,
+        tag: &[u8; 4],
+        name: &'static str,
+    ) -> Result<Reader<'a>, StateError> {
+        Ok(Reader::new(self.get(tag, name)?, self.version))
+    }
+}
+
+/// Wraps chunks in the container format described at the top.
+pub(super) fn container(magic: &[u8; 4], chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+    let mut w = Writer::default();
+    w.data.extend_from_slice(magic);
+    w.u16(STATE_VERSION);
+    for (tag, data) in chunks {
+        w.data.extend_from_slice(*tag);
+        w.bytes(data);
+        w.u32(crc32(data));
+    }
+    w.data
+}
+
+/// Checks the header and checksums of a container and splits it into
+/// its chunks.
+pub(super) fn chunks<'a>(magic: &[u8; 4], data: &'a [u8]) -> Result<Chunks<'a>, StateError> {
+    let mut reader = Reader::new(data, STATE_VERSION);
+    if reader.take(4).ok() != Some(&magic[..]) {
+        return Err(StateError::BadMagic);
+    }
+    let version = reader.u16()?;
+    if version > STATE_VERSION {
+        return Err(StateError::UnsupportedVersion(version));
+    }
+
+    let mut chunks = Vec::new();
+    while !reader.is_empty() {
+        let tag: [u8; 4] = reader.array()?;
+        let data = reader.bytes()?;
+        if reader.u32()? != crc32(data) {
+            return Err(StateError::Checksum(
+                String::from_utf8_lossy(&tag).into_owned(),
+            ));
+        }
+        chunks.push((tag, data));
+    }
+    Ok(Chunks { version, chunks })
+}
+
+/// CRC-32 as used by zlib and PNG.
+pub(super) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+impl Variant {
+    fn to_byte(self) -> u8 {
+        match self {
+            Variant::Nmos6502 => 0,
+            Variant::Cmos65C02 => 1,
+            Variant::W65C816 => 2,
+            Variant::Ricoh2A03 => 3,
+            Variant::Mos6510 => 4,
+            Variant::Mos6507 => 5,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, StateError> {
+        Ok(match byte {
+            0 => Variant::Nmos6502,
+            1 => Variant::Cmos65C02,
+            2 => Variant::W65C816,
+            3 => Variant::Ricoh2A03,
+            4 => Variant::Mos6510,
+            5 => Variant::Mos6507,
+            _ => return Err(StateError::Invalid("unknown variant")),
+        })
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Captures the whole state of the CPU, including an instruction
+    /// started with [`Core::tick`], along with the state the bus adds
+    /// through [`Bus::save_state`]. The opcode policy is configuration
+    /// and is not saved.
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut core = Writer::default();
+        self.save_core(&mut core);
+        let mut bus = Vec::new();
+        self.bus.save_state(&mut bus);
+
+        container(MAGIC, &[(CORE_CHUNK, &core.data), (BUS_CHUNK, &bus)])
+    }
+
+    /// Restores a state from [`Core::save_state`]. The state has to come
+    /// from the same variant. Nothing is changed if it turns out to be
+    /// invalid, unless the bus fails halfway through its own part.
+    pub fn load_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        let chunks = chunks(MAGIC, state)?;
+        let mut core = chunks.reader(CORE_CHUNK, "CORE")?;
+        let bus = chunks.get(BUS_CHUNK, "BUS ")?;
+
+        // decode everything before touching the core, so that a bad
+        // chunk leaves it as it was
+        let loaded = self.load_core(&mut core)?;
+        self.bus.load_state(bus)?;
+        loaded.apply(self);
+        // memory changed without the CPU writing it
+        self.restart_rewind();
+        Ok(())
+    }
+
+    /// The `CORE` chunk alone, for snapshots that leave the bus out.
+    pub(super) fn save_cpu(&self) -> Vec<u8> {
+        let mut w = Writer::default();
+        self.save_core(&mut w);
+        w.data
+    }
+
+    /// Restores what [`Core::save_cpu`] returned.
+    pub(super) fn load_cpu(&mut self, data: &[u8]) -> Result<(), StateError> {
+        let loaded = self.load_core(&mut Reader::new(data, STATE_VERSION))?;
+        loaded.apply(self);
+        Ok(())
+    }
+
+    fn save_core(&self, w: &mut Writer) {
+        w.u8(self.variant.to_byte());
+        self.save_registers().save(w);
+        w.bool(self.undocumented);
+        w.u8(self.magic);
+        w.bool(self.unstable_magic.is_some());
+        w.u8(self.unstable_magic.unwrap_or(0));
+        self.port.save(w);
+        w.bool(self.irq_line);
+        w.bool(self.nmi_line);
+        w.bool(self.rdy_line);
+        w.bool(self.so_line);
+        w.bool(self.so_level);
+        w.bool(self.nmi_level);
+        w.bool(self.nmi_pending);
+        w.u64(self.cycles);
+        w.u64(self.stolen);
+        self.replay.save(w);
+    }
+
+    fn load_core(&self, r: &mut Reader<'_>) -> Result<Loaded, StateError> {
+        let variant = Variant::from_byte(r.u8()?)?;
+        if variant != self.variant {
+            return Err(StateError::WrongVariant {
+                expected: self.variant,
+                found: variant,
+            });
+        }
+
+        Ok(Loaded {
+            registers: Registers::load(r)?,
+            undocumented: r.bool()?,
+            magic: r.u8()?,
+            unstable_magic: match r.version {
+                1 => None,
+                _ => {
+                    let set = r.bool()?;
+                    let magic = r.u8()?;
+                    set.then_some(magic)
+                }
+            },
+            port: IoPort::load(r)?,
+            irq_line: r.bool()?,
+            nmi_line: r.bool()?,
+            rdy_line: r.bool()?,
+            so_line: r.bool()?,
+            so_level: r.bool()?,
+            nmi_level: r.bool()?,
+            nmi_pending: r.bool()?,
+            cycles: r.u64()?,
+            stolen: r.u64()?,
+            replay: Replay::load(r)?,
+        })
+    }
+}
+
+/// A decoded `CORE` chunk waiting to be applied.
+struct Loaded {
+    registers: Registers,
+    undocumented: bool,
+    magic: u8,
+    unstable_magic: Option<u8>,
+    port: IoPort,
+    irq_line: bool,
+    nmi_line: bool,
+    rdy_line: bool,
+    so_line: bool,
+    so_level: bool,
+    nmi_level: bool,
+    nmi_pending: bool,
+    cycles: u64,
+    stolen: u64,
+    replay: Replay,
+}
+
+impl Loaded {
+    fn apply<B: Bus>(self, core: &mut Core<B>) {
+        core.restore_registers(self.registers);
+        core.undocumented = self.undocumented;
+        core.magic = self.magic;
+        core.unstable_magic = self.unstable_magic;
+        core.port = self.port;
+        core.irq_line = self.irq_line;
+        core.nmi_line = self.nmi_line;
+        core.rdy_line = self.rdy_line;
+        core.so_line = self.so_line;
+        core.so_level = self.so_level;
+        core.nmi_level = self.nmi_level;
+        core.nmi_pending = self.nmi_pending;
+        core.cycles = self.cycles;
+        core.stolen = self.stolen;
+        core.replay = self.replay;
+        core.dry_run = false;
+    }
+}
@@ -0,0 +1,177 @@
+//! Snapshots of the CPU registers.
+
+use std::fmt;
+
+use crate::traits::Bus;
+
+use super::{tick::Replay, Core, Flags, Variant};
+
+/// Every register of the CPU, as read by [`Core::state`] and written by
+/// [`Core::set_state`]. The fields after `status` only matter on the
+/// 65C816.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CpuState {
+    pub acc: u8,
+    pub idx: u8,
+    pub idy: u8,
+    pub sp: u8,
+    pub pc: u16,
+    pub status: Flags,
+    /// The hidden B accumulator, or the high byte of the 16-bit one.
+    pub acc_hi: u8,
+    pub idx_hi: u8,
+    pub idy_hi: u8,
+    pub sp_hi: u8,
+    /// The direct page register.
+    pub dp: u16,
+    /// The data bank register.
+    pub dbr: u8,
+    /// The program bank register.
+    pub pbr: u8,
+    /// Whether the 65C816 is in 6502 emulation mode. Always true for the
+    /// other variants.
+    pub emulation: bool,
+}
+
+impl Default for CpuState {
+    fn default() -> Self {
+        Self {
+            acc: 0,
+            idx: 0,
+            idy: 0,
+            sp: 0xff,
+            pc: 0,
+            status: Flags::new(),
+            acc_hi: 0,
+            idx_hi: 0,
+            idy_hi: 0,
+            sp_hi: 0x01,
+            dp: 0,
+            dbr: 0,
+            pbr: 0,
+            emulation: true,
+        }
+    }
+}
+
+impl CpuState {
+    /// The status byte as shown by debuggers: bit 5 is always set in
+    /// emulation mode, and holds M in native mode.
+    pub fn status_byte(&self) -> u8 {
+        if self.emulation {
+            self.status.as_byte() | 0x20
+        } else {
+            self.status.native_byte()
+        }
+    }
+}
+
+/// Formats the registers in the `A:xx X:xx Y:xx P:xx SP:xx PC:xxxx
+/// NV-BDIZC` form most 6502 debuggers and trace logs use. Each letter of
+/// `NV-BDIZC` is upper case when its flag is set and lower case when it
+/// is clear, so a P of $24 shows as:
+///
+/// ```text
+/// A:00 X:00 Y:00 P:24 SP:FD PC:C000 nv-bdIzc
+/// ```
+///
+/// A 65C816 in native mode shows the 16-bit registers, the program bank,
+/// the direct page and data bank registers, and M and X in place of the
+/// unused bit and B.
+impl fmt::Display for CpuState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let p = self.status_byte();
+        let names = if self.emulation {
+            write!(
+                f,
+                "A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} PC:{:04X}",
+                self.acc, self.idx, self.idy, p, self.sp, self.pc
+            )?;
+            "NV-BDIZC"
+        } else {
+            write!(
+                f,
+                "A:{:02X}{:02X} X:{:02X}{:02X} Y:{:02X}{:02X} P:{:02X} SP:{:02X}{:02X} \
+                 PC:{:02X}{:04X} D:{:04X} DB:{:02X}",
+                self.acc_hi,
+                self.acc,
+                self.idx_hi,
+                self.idx,
+                self.idy_hi,
+                self.idy,
+                p,
+                self.sp_hi,
+                self.sp,
+                self.pbr,
+                self.pc,
+                self.dp,
+                self.dbr
+            )?;
+            "NVMXDIZC"
+        };
+
+        write!(f, " ")?;
+        for (bit, name) in names.chars().enumerate() {
+            if p & (0x80 >> bit) != 0 {
+                write!(f, "{name}")?;
+            } else {
+                write!(f, "{}", name.to_ascii_lowercase())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// A copy of every register.
+    pub fn state(&self) -> CpuState {
+        CpuState {
+            acc: self.acc,
+            idx: self.idx,
+            idy: self.idy,
+            sp: self.sp,
+            pc: self.pc,
+            status: self.status,
+            acc_hi: self.acc_hi,
+            idx_hi: self.idx_hi,
+            idy_hi: self.idy_hi,
+            sp_hi: self.sp_hi,
+            dp: self.dp,
+            dbr: self.dbr,
+            pbr: self.pbr,
+            emulation: self.emulation,
+        }
+    }
+
+    /// Loads every register. Values the chip can't hold are fixed up as
+    /// the chip would: only the 65C816 leaves emulation mode, and
+    /// emulation mode or 8-bit index registers clear the high bytes they
+    /// hide. An instruction started with [`Core::tick`] is abandoned.
+    pub fn set_state(&mut self, state: CpuState) {
+        self.replay = Replay::default();
+        self.acc = state.acc;
+        self.idx = state.idx;
+        self.idy = state.idy;
+        self.sp = state.sp;
+        self.pc = state.pc;
+        self.status = state.status;
+        self.acc_hi = state.acc_hi;
+        self.idx_hi = state.idx_hi;
+        self.idy_hi = state.idy_hi;
+        self.sp_hi = state.sp_hi;
+        self.dp = state.dp;
+        self.dbr = state.dbr;
+        self.pbr = state.pbr;
+        self.emulation = state.emulation || self.variant != Variant::W65C816;
+
+        if self.emulation {
+            self.status.set_memory_8bit(true);
+            self.status.set_index_8bit(true);
+            self.sp_hi = 0x01;
+        }
+        if self.status.index_8bit() {
+            self.idx_hi = 0;
+            self.idy_hi = 0;
+        }
+    }
+}
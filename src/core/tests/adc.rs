@@ -8,7 +8,7 @@ fn adc_immediate() {
     let program = vec![0x69, 0x08];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 2));
@@ -21,7 +21,7 @@ fn adc_zeropage() {
     bus.write(0x0020, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 3));
@@ -35,7 +35,7 @@ fn adc_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 4));
@@ -48,7 +48,7 @@ fn adc_absolute() {
     bus.write(0x1337, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 4));
@@ -62,7 +62,7 @@ fn adc_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 4));
@@ -76,7 +76,7 @@ fn adc_absolute_x_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 5));
@@ -90,7 +90,7 @@ fn adc_absolute_y() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 4));
@@ -104,7 +104,7 @@ fn adc_absolute_y_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 5));
@@ -120,7 +120,7 @@ fn adc_indexed_indirect() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 6));
@@ -136,7 +136,7 @@ fn adc_indirect_indexed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 5));
@@ -152,7 +152,7 @@ fn adc_indirect_indexed_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x08;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x10);
     assert!(verify_clocks(&core, 6));
@@ -165,17 +165,17 @@ fn adc_status_flags() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x1;
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b1000_0000);
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0000_0011);
 
     core.acc = 0x50;
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b1100_0000);
 
     core.acc = 0x80;
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0100_0011);
 }
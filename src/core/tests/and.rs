@@ -8,7 +8,7 @@ fn and_immediate() {
     let program = vec![0x29, 0b0000_1000];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 2));
@@ -21,7 +21,7 @@ fn and_zeropage() {
     bus.write(0x0020, 0b0000_1000);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 3));
@@ -35,7 +35,7 @@ fn and_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 4));
@@ -48,7 +48,7 @@ fn and_absolute() {
     bus.write(0x1337, 0b0000_1000);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 4));
@@ -62,7 +62,7 @@ fn and_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0001_1000;
     core.idx = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 4));
@@ -76,7 +76,7 @@ fn and_aboslute_x_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 5));
@@ -90,7 +90,7 @@ fn and_absolute_y() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0001_1000;
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 4));
@@ -104,7 +104,7 @@ fn and_absolute_y_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 5));
@@ -120,7 +120,7 @@ fn and_indexed_indirect() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 6));
@@ -136,7 +136,7 @@ fn and_indirect_indexed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 5));
@@ -152,7 +152,7 @@ fn and_indirect_indexed_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0b0001_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0000_1000);
     assert!(verify_clocks(&core, 6));
@@ -8,7 +8,7 @@ fn lsr_acc() {
     let program = vec![0x0A];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0100_0100;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b1000_1000);
     assert!(verify_clocks(&core, 2));
@@ -20,8 +20,8 @@ fn lsr_zeropage() {
     let program = vec![0x06, 0x20];
     bus.write(0x0020, 0b0100_0100);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0b1000_1000);
     assert!(verify_clocks(&core, 5));
@@ -34,8 +34,8 @@ fn lsr_zeropage_x() {
     bus.write(0x0025, 0b0100_0100);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
-    let byte = core.get_bus().read(0x0025);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0025);
 
     assert_eq!(byte, 0b1000_1000);
     assert!(verify_clocks(&core, 6));
@@ -47,8 +47,8 @@ fn lsr_absolute() {
     let program = vec![0x0E, 0x37, 0x13];
     bus.write(0x1337, 0b0100_0100);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1000_1000);
     assert!(verify_clocks(&core, 6));
@@ -61,8 +61,8 @@ fn lsr_absolute_x() {
     bus.write(0x1337, 0b0100_0100);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1000_1000);
     assert!(verify_clocks(&core, 7));
@@ -9,7 +9,7 @@ fn bit_zeropage() {
     bus.write(0x0069, 0b1001_1000);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0000_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.zero(), false);
     assert_eq!(core.status.overflow(), false);
@@ -24,7 +24,7 @@ fn bit_absolute() {
     bus.write(0x2020, 0b0100_0000);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0000_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.zero(), true);
     assert_eq!(core.status.overflow(), true);
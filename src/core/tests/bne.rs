@@ -6,7 +6,7 @@ fn bne_false() {
     let program = vec![0xD0, 0x05];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_zero(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.pc, 0x02);
     assert!(verify_clocks(&core, 2));
@@ -18,7 +18,7 @@ fn bne_true() {
     let program = vec![0xD0, 0x05];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_zero(false);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.pc, 0x07);
     assert!(verify_clocks(&core, 3));
@@ -33,7 +33,7 @@ fn bne_page_crossed() {
     bus.write(0xFFFD, 0x80);
     let mut core = Core::new(bus, vec![]).unwrap();
     core.status.set_zero(false);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.pc, 0x8101);
     assert!(verify_clocks(&core, 4));
@@ -43,12 +43,12 @@ fn bne_page_crossed() {
 fn bne_negative_offset() {
     let mut bus = MockBus::new();
     bus.write(0x8020, 0xD0);
-    bus.write(0x8021, 0x81);
+    bus.write(0x8021, 0xFF);
     bus.write(0xFFFC, 0x20);
     bus.write(0xFFFD, 0x80);
     let mut core = Core::new(bus, vec![]).unwrap();
     core.status.set_zero(false);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.pc, 0x8021);
     assert!(verify_clocks(&core, 3));
@@ -58,12 +58,12 @@ fn bne_negative_offset() {
 fn bne_negative_offset_page_crossed() {
     let mut bus = MockBus::new();
     bus.write(0x8000, 0xD0);
-    bus.write(0x8001, 0x83);
+    bus.write(0x8001, 0xFD);
     bus.write(0xFFFC, 0x00);
     bus.write(0xFFFD, 0x80);
     let mut core = Core::new(bus, vec![]).unwrap();
     core.status.set_zero(false);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.pc, 0x7FFF);
     assert!(verify_clocks(&core, 4));
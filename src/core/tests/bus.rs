@@ -0,0 +1,111 @@
+use crate::{
+    core::{AccessKind, Core, DynCore},
+    traits::Bus,
+};
+
+use super::*;
+
+#[test]
+fn owns_its_bus() {
+    let bus = MockBus::new();
+    let program = vec![0xAD, 0x00, 0x20, 0x8D, 0x01, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.bus_mut().mem[0x2000] = 0x42;
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.bus().mem[0x2001], 0x42);
+    assert_eq!(core.into_bus().mem[0xc10c], 8);
+}
+
+#[test]
+fn dynamic_bus() {
+    let bus: Box<dyn Bus> = Box::new(MockBus::new());
+    let mut core: DynCore = Core::new(bus, vec![0xA9, 0x69]).unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert_eq!(core.bus_mut().read(0xc10c), 2);
+}
+
+/// Answers vector pulls from its own table, like a system decoding the
+/// 65C02 VPB pin, and logs every access it sees.
+#[derive(Debug)]
+struct VectorBus {
+    mem: MockBus,
+    accesses: Vec<(u32, AccessKind, u64)>,
+}
+
+impl Bus for VectorBus {
+    fn read(&mut self, addr: u16) -> u8 {
+        self.mem.read(addr)
+    }
+
+    fn write(&mut self, addr: u16, byte: u8) {
+        self.mem.write(addr, byte)
+    }
+
+    fn on_clock(&mut self) {}
+
+    fn load_rom(&mut self, prog: Vec<u8>) -> Result<(), crate::error::BusError> {
+        self.mem.load_rom(prog)
+    }
+
+    fn dump_rom(&self) -> Vec<u8> {
+        self.mem.dump_rom()
+    }
+
+    fn read_access(&mut self, addr: u32, kind: AccessKind, cycle: u64) -> u8 {
+        self.accesses.push((addr, kind, cycle));
+        match (kind, addr) {
+            (AccessKind::Vector, 0xFFFC) => 0x00,
+            (AccessKind::Vector, 0xFFFD) => 0x40,
+            _ => self.read_long(addr),
+        }
+    }
+
+    fn write_access(&mut self, addr: u32, byte: u8, kind: AccessKind, cycle: u64) {
+        self.accesses.push((addr, kind, cycle));
+        self.write_long(addr, byte)
+    }
+}
+
+#[test]
+fn access_kinds_reach_the_bus() {
+    let mut mem = MockBus::new();
+    mem.write(0xFFFC, 0x00);
+    mem.write(0xFFFD, 0x30);
+    // LDA $FFFD, PHA
+    mem.write(0x4000, 0xAD);
+    mem.write(0x4001, 0xFD);
+    mem.write(0x4002, 0xFF);
+    mem.write(0x4003, 0x48);
+    let bus = VectorBus {
+        mem,
+        accesses: Vec::new(),
+    };
+    let mut core = Core::new(bus, vec![]).unwrap();
+    core.reset();
+
+    assert_eq!(core.pc, 0x4000);
+
+    let start = core.cycles();
+    core.bus_mut().accesses.clear();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    // a data read of the vector address sees memory
+    assert_eq!(core.acc, 0x30);
+    assert_eq!(
+        core.bus().accesses,
+        [
+            (0x4000, AccessKind::Opcode, start),
+            (0x4001, AccessKind::Operand, start + 1),
+            (0x4002, AccessKind::Operand, start + 2),
+            (0xFFFD, AccessKind::Data, start + 3),
+            (0x4003, AccessKind::Opcode, start + 4),
+            (0x4004, AccessKind::Dummy, start + 5),
+            (0x01FC, AccessKind::Stack, start + 6),
+        ]
+    );
+}
@@ -0,0 +1,225 @@
+use crate::core::Core;
+
+use super::*;
+
+use Access::{Read, Write};
+
+/// Runs one instruction at $0200 and returns its bus activity.
+fn run(program: Vec<u8>, setup: impl FnOnce(&mut Core<HookBus>)) -> Vec<Access> {
+    execute(program, setup, |core| {
+        let _ = core.step();
+    })
+}
+
+/// Like `run`, but one cycle at a time.
+fn tick(program: Vec<u8>, setup: impl FnOnce(&mut Core<HookBus>)) -> Vec<Access> {
+    execute(program, setup, |core| while let Ok(None) = core.tick() {})
+}
+
+fn execute(
+    program: Vec<u8>,
+    setup: impl FnOnce(&mut Core<HookBus>),
+    advance: impl FnOnce(&mut Core<HookBus>),
+) -> Vec<Access> {
+    let mut core = Core::new(HookBus::new(0x0200), program).unwrap();
+    setup(&mut core);
+    core.bus_mut().log.clear();
+    core.bus_mut().clocks = 0;
+    advance(&mut core);
+
+    let bus = core.bus();
+    assert_eq!(bus.log.len(), bus.clocks as usize, "{:x?}", bus.log);
+    bus.log.clone()
+}
+
+#[test]
+fn implied_reads_next_byte() {
+    let log = run(vec![0x18], |_| {});
+
+    assert_eq!(log, vec![Read(0x0200), Read(0x0201)]);
+}
+
+#[test]
+fn absolute_x_page_crossed() {
+    let log = run(vec![0xBD, 0xF0, 0x12], |core| core.idx = 0x20);
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x0202),
+            Read(0x1210),
+            Read(0x1310),
+        ]
+    );
+}
+
+#[test]
+fn absolute_x_store_always_reads_first() {
+    let log = run(vec![0x9D, 0x00, 0x12], |core| {
+        core.idx = 0x10;
+        core.acc = 0x69;
+    });
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x0202),
+            Read(0x1210),
+            Write(0x1210, 0x69),
+        ]
+    );
+}
+
+#[test]
+fn indirect_y_page_crossed() {
+    let log = run(vec![0xB1, 0x20], |core| {
+        core.bus_mut().write(0x0020, 0xff);
+        core.bus_mut().write(0x0021, 0x12);
+        core.idy = 0x01;
+    });
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x0020),
+            Read(0x0021),
+            Read(0x1200),
+            Read(0x1300),
+        ]
+    );
+}
+
+#[test]
+fn zeropage_x_reads_base() {
+    let log = run(vec![0xB5, 0xF0], |core| core.idx = 0x20);
+
+    assert_eq!(
+        log,
+        vec![Read(0x0200), Read(0x0201), Read(0x00F0), Read(0x0010)]
+    );
+}
+
+#[test]
+fn read_modify_write_writes_twice() {
+    let log = run(vec![0xE6, 0x20], |core| core.bus_mut().write(0x0020, 0x41));
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x0020),
+            Write(0x0020, 0x41),
+            Write(0x0020, 0x42),
+        ]
+    );
+}
+
+#[test]
+fn pla_reads_stack() {
+    let log = run(vec![0x68], |core| core.sp = 0xfe);
+
+    assert_eq!(
+        log,
+        vec![Read(0x0200), Read(0x0201), Read(0x01FE), Read(0x01FF)]
+    );
+}
+
+#[test]
+fn jsr() {
+    let log = run(vec![0x20, 0x37, 0x13], |_| {});
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x01FF),
+            Write(0x01FF, 0x02),
+            Write(0x01FE, 0x02),
+            Read(0x0202),
+        ]
+    );
+}
+
+#[test]
+fn rts() {
+    let log = run(vec![0x60], |core| {
+        core.sp = 0xfd;
+        core.bus_mut().write(0x01fe, 0x36);
+        core.bus_mut().write(0x01ff, 0x13);
+    });
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0201),
+            Read(0x01FD),
+            Read(0x01FE),
+            Read(0x01FF),
+            Read(0x1336),
+        ]
+    );
+}
+
+#[test]
+fn branch_page_crossed() {
+    let log = run(vec![0xD0, 0xFC], |_| {});
+
+    assert_eq!(
+        log,
+        vec![Read(0x0200), Read(0x0201), Read(0x0202), Read(0x02FE)]
+    );
+}
+
+#[test]
+fn every_cycle_is_a_bus_access() {
+    for opcode in 0..=0xff_u8 {
+        run(vec![opcode, 0x10, 0x10], |core| {
+            core.idx = 0xf8;
+            core.idy = 0xf8;
+        });
+    }
+}
+
+#[test]
+fn ticking_matches_step() {
+    for opcode in 0..=0xff_u8 {
+        let setup = |core: &mut Core<HookBus>| {
+            core.idx = 0xf8;
+            core.idy = 0xf8;
+        };
+        let program = vec![opcode, 0x10, 0x10];
+
+        assert_eq!(
+            tick(program.clone(), setup),
+            run(program, setup),
+            "{opcode:02x}"
+        );
+    }
+}
+
+#[test]
+fn reset() {
+    let log = execute(vec![0xEA], |_| {}, Core::reset);
+
+    assert_eq!(
+        log,
+        vec![
+            Read(0x0200),
+            Read(0x0200),
+            Read(0x01FF),
+            Read(0x01FE),
+            Read(0x01FD),
+            Read(0xFFFC),
+            Read(0xFFFD),
+        ]
+    );
+}
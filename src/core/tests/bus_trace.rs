@@ -0,0 +1,164 @@
+use crate::core::{AccessKind, BusCycle, BusTrace, Core};
+
+use super::*;
+
+use AccessKind::*;
+
+/// LDA #$42, STA $0200, INC $10, PHA
+const PROGRAM: [u8; 8] = [0xA9, 0x42, 0x8D, 0x00, 0x02, 0xE6, 0x10, 0x48];
+
+fn accesses(trace: &BusTrace) -> Vec<(u32, u8, bool, AccessKind)> {
+    trace
+        .cycles()
+        .iter()
+        .map(|cycle| (cycle.address, cycle.data, cycle.write, cycle.kind))
+        .collect()
+}
+
+#[test]
+fn tags_every_cycle() {
+    let mut core = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    core.start_bus_trace();
+    for _ in 0..4 {
+        core.step().unwrap();
+    }
+    let trace = core.stop_bus_trace().unwrap();
+
+    assert_eq!(
+        accesses(&trace),
+        [
+            (0x0000, 0xA9, false, Opcode),
+            (0x0001, 0x42, false, Operand),
+            (0x0002, 0x8D, false, Opcode),
+            (0x0003, 0x00, false, Operand),
+            (0x0004, 0x02, false, Operand),
+            (0x0200, 0x42, true, Data),
+            (0x0005, 0xE6, false, Opcode),
+            (0x0006, 0x10, false, Operand),
+            (0x0010, 0x00, false, Data),
+            (0x0010, 0x00, true, Dummy),
+            (0x0010, 0x01, true, Data),
+            (0x0007, 0x48, false, Opcode),
+            (0x0008, 0x00, false, Dummy),
+            (0x01FF, 0x42, true, Stack),
+        ]
+    );
+    assert_eq!(trace.cycles()[0].cycle, 0);
+    assert_eq!(trace.cycles()[13].cycle, 13);
+}
+
+#[test]
+fn reset_reads_vector() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFC, 0x00);
+    bus.write(0xFFFD, 0x30);
+    let mut core = Core::new(bus, PROGRAM.to_vec()).unwrap();
+    core.start_bus_trace();
+    core.reset();
+
+    let kinds: Vec<_> = core
+        .bus_trace()
+        .unwrap()
+        .cycles()
+        .iter()
+        .map(|c| c.kind)
+        .collect();
+    assert_eq!(kinds, [Dummy, Dummy, Dummy, Dummy, Dummy, Vector, Vector]);
+    assert_eq!(core.bus_trace().unwrap().cycles()[6].address, 0xFFFD);
+}
+
+#[test]
+fn interrupt_sequence() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFA, 0x00);
+    bus.write(0xFFFB, 0x30);
+    let mut core = Core::new(bus, vec![0xEA]).unwrap();
+    core.start_bus_trace();
+    core.set_nmi(true);
+    core.step().unwrap();
+
+    let trace = core.stop_bus_trace().unwrap();
+    let kinds: Vec<_> = trace.cycles().iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, [Opcode, Dummy, Stack, Stack, Stack, Vector, Vector]);
+}
+
+#[test]
+fn ticks_log_each_cycle_once() {
+    let mut stepped = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    stepped.start_bus_trace();
+    for _ in 0..4 {
+        stepped.step().unwrap();
+    }
+
+    let mut ticked = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    ticked.start_bus_trace();
+    while ticked.cycles() < stepped.cycles() {
+        ticked.tick().unwrap();
+    }
+
+    assert_eq!(ticked.stop_bus_trace(), stepped.stop_bus_trace());
+}
+
+#[test]
+fn csv() {
+    let mut core = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    core.start_bus_trace();
+    core.step().unwrap();
+    core.step().unwrap();
+    let mut csv = Vec::new();
+    core.stop_bus_trace().unwrap().write_csv(&mut csv).unwrap();
+
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "cycle,address,data,rw,kind\n\
+         0,0000,A9,R,sync\n\
+         1,0001,42,R,operand\n\
+         2,0002,8D,R,sync\n\
+         3,0003,00,R,operand\n\
+         4,0004,02,R,operand\n\
+         5,0200,42,W,data\n"
+    );
+}
+
+#[test]
+fn binary_round_trip() {
+    let mut core = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    let state = core.save_state();
+    core.start_bus_trace();
+    core.step().unwrap();
+    core.step().unwrap();
+    // going back in time breaks the run of consecutive cycles
+    core.load_state(&state).unwrap();
+    core.step().unwrap();
+    let trace = core.stop_bus_trace().unwrap();
+    let bytes = trace.to_bytes();
+
+    assert_eq!(BusTrace::from_bytes(&bytes).unwrap(), trace);
+    assert_eq!(
+        trace.cycles()[6],
+        BusCycle {
+            cycle: 0,
+            address: 0x0000,
+            data: 0xA9,
+            write: false,
+            kind: Opcode
+        }
+    );
+}
+
+#[test]
+fn binary_is_compact() {
+    let mut core = Core::new(MockBus::new(), PROGRAM.to_vec()).unwrap();
+    core.start_bus_trace();
+    for _ in 0..4 {
+        core.step().unwrap();
+    }
+    let trace = core.stop_bus_trace().unwrap();
+    let empty = BusTrace::default().to_bytes();
+
+    // five bytes a cycle, plus the starting cycle
+    assert_eq!(
+        trace.to_bytes().len() - empty.len(),
+        trace.cycles().len() * 5 + 8
+    );
+}
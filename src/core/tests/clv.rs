@@ -9,7 +9,7 @@ fn clv() {
     let program = vec![0xB8];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_overflow(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.overflow(), false);
     assert!(verify_clocks(&core, 2));
@@ -0,0 +1,310 @@
+use crate::core::{Core, HaltReason, Variant};
+
+use super::*;
+
+fn cmos(bus: MockBus, program: Vec<u8>) -> Core<MockBus> {
+    Core::with_variant(bus, program, Variant::Cmos65C02).unwrap()
+}
+
+#[test]
+fn bra() {
+    let bus = MockBus::new();
+    let program = vec![0x80, 0x05];
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x07);
+    assert!(verify_clocks(&core, 3));
+}
+
+#[test]
+fn stz_absolute_x() {
+    let mut bus = MockBus::new();
+    let program = vec![0x9E, 0x33, 0x13];
+    bus.write(0x1337, 0xff);
+    let mut core = cmos(bus, program);
+    core.idx = 0x04;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x00);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn stz_zeropage() {
+    let mut bus = MockBus::new();
+    let program = vec![0x64, 0x20];
+    bus.write(0x0020, 0xff);
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0x00);
+    assert!(verify_clocks(&core, 3));
+}
+
+#[test]
+fn phx_ply() {
+    let bus = MockBus::new();
+    let program = vec![0xDA, 0x7A];
+    let mut core = cmos(bus, program);
+    core.idx = 0x69;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x01ff), 0x69);
+    assert!(verify_clocks(&core, 3));
+
+    core.step().unwrap();
+
+    assert_eq!(core.idy, 0x69);
+    assert_eq!(core.sp, 0xff);
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn tsb_zeropage() {
+    let mut bus = MockBus::new();
+    let program = vec![0x04, 0x20];
+    bus.write(0x0020, 0b1010_0000);
+    let mut core = cmos(bus, program);
+    core.acc = 0b0000_0101;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0b1010_0101);
+    assert!(core.status.zero());
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn trb_absolute() {
+    let mut bus = MockBus::new();
+    let program = vec![0x1C, 0x37, 0x13];
+    bus.write(0x1337, 0b1010_0101);
+    let mut core = cmos(bus, program);
+    core.acc = 0b0000_0101;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0b1010_0000);
+    assert!(!core.status.zero());
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn inc_dec_accumulator() {
+    let bus = MockBus::new();
+    let program = vec![0x1A, 0x3A, 0x3A];
+    let mut core = cmos(bus, program);
+    core.acc = 0xff;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x00);
+    assert!(core.status.zero());
+
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0xfe);
+    assert!(core.status.negative());
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn lda_zeropage_indirect() {
+    let mut bus = MockBus::new();
+    let program = vec![0xB2, 0x20];
+    bus.write(0x0020, 0x37);
+    bus.write(0x0021, 0x13);
+    bus.write(0x1337, 0x69);
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn sta_zeropage_indirect() {
+    let mut bus = MockBus::new();
+    let program = vec![0x92, 0x20];
+    bus.write(0x0020, 0x37);
+    bus.write(0x0021, 0x13);
+    let mut core = cmos(bus, program);
+    core.acc = 0x69;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x69);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn bit_immediate_only_affects_zero() {
+    let bus = MockBus::new();
+    let program = vec![0x89, 0xc0];
+    let mut core = cmos(bus, program);
+    core.acc = 0x01;
+    core.step().unwrap();
+
+    assert!(core.status.zero());
+    assert!(!core.status.negative());
+    assert!(!core.status.overflow());
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn bit_absolute_x() {
+    let mut bus = MockBus::new();
+    let program = vec![0x3C, 0x33, 0x13];
+    bus.write(0x1337, 0xc0);
+    let mut core = cmos(bus, program);
+    core.idx = 0x04;
+    core.acc = 0xff;
+    core.step().unwrap();
+
+    assert!(!core.status.zero());
+    assert!(core.status.negative());
+    assert!(core.status.overflow());
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn jmp_absolute_indexed_indirect() {
+    let mut bus = MockBus::new();
+    let program = vec![0x7C, 0x1e, 0x20];
+    bus.write(0x2020, 0x37);
+    bus.write(0x2021, 0x13);
+    let mut core = cmos(bus, program);
+    core.idx = 0x02;
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn jmp_indirect_page_boundary() {
+    let mut bus = MockBus::new();
+    let program = vec![0x6C, 0xff, 0x20];
+    bus.write(0x20ff, 0x37);
+    bus.write(0x2100, 0x13);
+    bus.write(0x2000, 0x42);
+    let mut core = cmos(bus.clone(), program.clone());
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    assert!(verify_clocks(&core, 6));
+
+    let mut core = Core::new(bus, program).unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x4237);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn rmb_smb() {
+    let mut bus = MockBus::new();
+    let program = vec![0x37, 0x20, 0xC7, 0x20];
+    bus.write(0x0020, 0b0000_1000);
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0b0000_0000);
+    assert!(verify_clocks(&core, 5));
+
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0b0001_0000);
+}
+
+#[test]
+fn bbr_bbs() {
+    let mut bus = MockBus::new();
+    let program = vec![0x7F, 0x20, 0x05, 0xFF, 0x20, 0x05];
+    bus.write(0x0020, 0b1000_0000);
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    // bit 7 is set, so BBR7 falls through
+    assert_eq!(core.pc, 0x03);
+    assert!(verify_clocks(&core, 5));
+
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x0b);
+    assert!(verify_clocks(&core, 11));
+}
+
+#[test]
+fn wai_sleeps_until_interrupt() {
+    let mut bus = MockBus::new();
+    let program = vec![0xCB, 0xE8];
+    bus.write(0xFFFE, 0x00);
+    bus.write(0xFFFF, 0x20);
+    let mut core = cmos(bus, program);
+    core.status.set_interrupt(true);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x01);
+
+    // a masked IRQ resumes execution without entering the handler
+    core.set_irq(true);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x02);
+    assert_eq!(core.idx, 0x01);
+}
+
+#[test]
+fn stp_halts() {
+    let bus = MockBus::new();
+    let program = vec![0xDB];
+    let mut core = cmos(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.halt_reason(), Some(HaltReason::Stopped));
+    assert!(verify_clocks(&core, 3));
+}
+
+#[test]
+fn unassigned_opcodes_are_nops() {
+    let bus = MockBus::new();
+    let program = vec![0x03, 0x02, 0xff, 0x5C, 0x37, 0x13, 0xDC, 0x37, 0x13];
+    let mut core = cmos(bus, program);
+    let expected = [(0x01, 1), (0x03, 2), (0x06, 8), (0x09, 4)];
+
+    for (pc, clocks) in expected {
+        core.bus_mut().write(0xc10c, 0);
+        core.step().unwrap();
+        assert_eq!(core.pc, pc);
+        assert_eq!(core.halt_reason(), None);
+        assert!(verify_clocks(&core, clocks));
+    }
+}
+
+#[test]
+fn asl_absolute_x_no_page_cross() {
+    let mut bus = MockBus::new();
+    let program = vec![0x1E, 0x33, 0x13];
+    bus.write(0x1337, 0x01);
+    let mut core = cmos(bus, program);
+    core.idx = 0x04;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x02);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn brk_clears_decimal() {
+    let mut bus = MockBus::new();
+    let program = vec![0x00];
+    bus.write(0xFFFE, 0x69);
+    bus.write(0xFFFF, 0x69);
+    let mut core = cmos(bus, program);
+    core.status.set_decimal(true);
+    core.step().unwrap();
+
+    assert!(!core.status.decimal());
+    assert_eq!(core.bus_mut().read(0x01fd) & 0x08, 0x08);
+}
@@ -8,7 +8,7 @@ fn cmp_immediate() {
     let program = vec![0xC9, 0x05];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -23,7 +23,7 @@ fn cmp_zeropage() {
     bus.write(0x0069, 0x05);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(core.status.zero());
@@ -39,7 +39,7 @@ fn cmp_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -54,7 +54,7 @@ fn cmp_absolute() {
     bus.write(0x1337, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.carry());
     assert!(!core.status.zero());
@@ -70,7 +70,7 @@ fn cmp_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -86,7 +86,7 @@ fn cmp_absolute_x_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -102,7 +102,7 @@ fn cmp_absolute_y() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -118,7 +118,7 @@ fn cmp_absolute_y_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -136,7 +136,7 @@ fn cmp_indexed_indirect() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -154,7 +154,7 @@ fn cmp_indirect_indexed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -172,7 +172,7 @@ fn cmp_indirect_indexed_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
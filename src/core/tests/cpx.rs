@@ -8,7 +8,7 @@ fn cpx_immediate() {
     let program = vec![0xE0, 0x05];
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x0A;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(!core.status.zero());
@@ -23,7 +23,7 @@ fn cpx_zeropage() {
     bus.write(0x0069, 0x05);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.carry());
     assert!(core.status.zero());
@@ -38,7 +38,7 @@ fn cpx_absolute() {
     bus.write(0x1337, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.carry());
     assert!(!core.status.zero());
@@ -0,0 +1,150 @@
+use crate::core::{Core, Variant};
+
+use super::*;
+
+/// Runs a single immediate-mode ADC or SBC with the D flag set and
+/// returns the core for inspection.
+fn decimal_op(variant: Variant, opcode: u8, acc: u8, byte: u8, carry: bool) -> Core<MockBus> {
+    let bus = MockBus::new();
+    let program = vec![opcode, byte];
+    let mut core = Core::with_variant(bus, program, variant).unwrap();
+    core.status.set_decimal(true);
+    core.status.set_carry(carry);
+    core.acc = acc;
+    core.step().unwrap();
+    core
+}
+
+/// Packs the result as (acc, N, V, Z, C) for compact assertions.
+fn result(core: &Core<MockBus>) -> (u8, bool, bool, bool, bool) {
+    (
+        core.acc,
+        core.status.negative(),
+        core.status.overflow(),
+        core.status.zero(),
+        core.status.carry(),
+    )
+}
+
+#[test]
+fn adc_decimal_nmos() {
+    let cases = [
+        (0x00, 0x00, false, (0x00, false, false, true, false)),
+        (0x12, 0x34, false, (0x46, false, false, false, false)),
+        (0x58, 0x46, true, (0x05, true, true, false, true)),
+        (0x79, 0x00, true, (0x80, true, true, false, false)),
+        (0x24, 0x56, false, (0x80, true, true, false, false)),
+        (0x93, 0x82, false, (0x75, false, true, false, true)),
+        (0x89, 0x76, false, (0x65, false, false, false, true)),
+        (0x89, 0x76, true, (0x66, false, false, true, true)),
+        (0x80, 0xf0, false, (0xd0, false, true, false, true)),
+        (0x80, 0xfa, false, (0xe0, true, false, false, true)),
+        (0x2f, 0x4f, false, (0x74, false, false, false, false)),
+        (0x6f, 0x00, true, (0x76, false, false, false, false)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Nmos6502, 0x69, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} + {byte:02x} + {carry}");
+        assert!(verify_clocks(&core, 2));
+    }
+}
+
+#[test]
+fn sbc_decimal_nmos() {
+    let cases = [
+        (0x46, 0x12, true, (0x34, false, false, false, true)),
+        (0x40, 0x13, true, (0x27, false, false, false, true)),
+        (0x32, 0x02, false, (0x29, false, false, false, true)),
+        (0x12, 0x21, true, (0x91, true, false, false, false)),
+        (0x00, 0x00, false, (0x99, true, false, false, false)),
+        (0x00, 0x00, true, (0x00, false, false, true, true)),
+        (0x00, 0x01, true, (0x99, true, false, false, false)),
+        (0x0a, 0x00, true, (0x0a, false, false, false, true)),
+        (0x0b, 0x00, false, (0x0a, false, false, false, true)),
+        (0x9a, 0x00, true, (0x9a, true, false, false, true)),
+        (0x9b, 0x00, false, (0x9a, true, false, false, true)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Nmos6502, 0xE9, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} - {byte:02x} - !{carry}");
+        assert!(verify_clocks(&core, 2));
+    }
+}
+
+#[test]
+fn adc_decimal_cmos() {
+    let cases = [
+        (0x58, 0x46, true, (0x05, false, true, false, true)),
+        (0x89, 0x76, true, (0x66, false, false, false, true)),
+        (0x79, 0x00, true, (0x80, true, true, false, false)),
+        (0x99, 0x01, false, (0x00, false, false, true, true)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Cmos65C02, 0x69, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} + {byte:02x} + {carry}");
+        assert!(verify_clocks(&core, 3));
+    }
+}
+
+#[test]
+fn sbc_decimal_cmos() {
+    let cases = [
+        (0x46, 0x12, true, (0x34, false, false, false, true)),
+        (0x12, 0x21, true, (0x91, true, false, false, false)),
+        (0x00, 0x00, false, (0x99, true, false, false, false)),
+        (0x00, 0x00, true, (0x00, false, false, true, true)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Cmos65C02, 0xE9, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} - {byte:02x} - !{carry}");
+        assert!(verify_clocks(&core, 3));
+    }
+}
+
+#[test]
+fn adc_ricoh_ignores_decimal_flag() {
+    let cases = [
+        (0x09, 0x01, false, (0x0a, false, false, false, false)),
+        (0x58, 0x46, true, (0x9f, true, true, false, false)),
+        (0x99, 0x01, false, (0x9a, true, false, false, false)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Ricoh2A03, 0x69, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} + {byte:02x} + {carry}");
+        assert!(core.status.decimal());
+        assert!(verify_clocks(&core, 2));
+    }
+}
+
+#[test]
+fn sbc_ricoh_ignores_decimal_flag() {
+    let cases = [
+        (0x10, 0x01, true, (0x0f, false, false, false, true)),
+        (0x00, 0x01, true, (0xff, true, false, false, false)),
+    ];
+
+    for (acc, byte, carry, expected) in cases {
+        let core = decimal_op(Variant::Ricoh2A03, 0xE9, acc, byte, carry);
+        assert_eq!(result(&core), expected, "{acc:02x} - {byte:02x} - !{carry}");
+        assert!(verify_clocks(&core, 2));
+    }
+}
+
+#[test]
+fn sed_cld_ricoh() {
+    let bus = MockBus::new();
+    let program = vec![0xF8, 0xD8];
+    let mut core = Core::with_variant(bus, program, Variant::Ricoh2A03).unwrap();
+    core.step().unwrap();
+
+    assert!(core.status.decimal());
+
+    core.step().unwrap();
+
+    assert!(!core.status.decimal());
+}
@@ -8,7 +8,7 @@ fn eor_immediate() {
     let program = vec![0x49, 0b0000_1000];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0000_1000;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.zero());
     assert!(!core.status.negative());
@@ -22,7 +22,7 @@ fn eor_zeropage() {
     let program = vec![0x45, 0x69];
     bus.write(0x0069, 0b1000_0000);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert!(core.status.negative());
     assert!(!core.status.zero());
@@ -37,7 +37,7 @@ fn eor_zeropage_x() {
     bus.write(0x0022, 0b0000_0001);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.negative());
     assert!(!core.status.zero());
@@ -51,7 +51,7 @@ fn eor_absolute() {
     let program = vec![0x4D, 0x00, 0x20];
     bus.write(0x2000, 1);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.negative());
     assert!(!core.status.zero());
@@ -66,7 +66,7 @@ fn eor_absolute_x() {
     bus.write(0x2020, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x20;
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.negative());
     assert!(!core.status.zero());
@@ -81,7 +81,7 @@ fn eor_absolute_x_page_crossed() {
     bus.write(0x2100, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(!core.status.negative());
     assert!(!core.status.zero());
@@ -96,7 +96,7 @@ fn eor_absolute_y() {
     bus.write(0x2001, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 4));
@@ -109,7 +109,7 @@ fn eor_absolute_y_page_crossed() {
     bus.write(0x2100, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 5));
@@ -124,7 +124,7 @@ fn eor_indexed_indirect() {
     bus.write(0x1337, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 2;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 6));
@@ -139,7 +139,7 @@ fn eor_indirect_indexed() {
     bus.write(0x1337, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 5));
@@ -154,7 +154,7 @@ fn eor_indirect_indexed_page_crossed() {
     bus.write(0x1400, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 6));
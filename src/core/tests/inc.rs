@@ -8,8 +8,8 @@ fn inc_zeropage() {
     let program = vec![0xe6, 0x20];
     bus.write(0x0020, 0x68);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -22,8 +22,8 @@ fn inc_zeropage_x() {
     bus.write(0x0022, 0x68);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
-    core.step();
-    let byte = core.get_bus().read(0x0022);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0022);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 6));
@@ -35,8 +35,8 @@ fn inc_absolute() {
     let program = vec![0xee, 0x37, 0x13];
     bus.write(0x1337, 0x68);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 6));
@@ -49,8 +49,8 @@ fn inc_absolute_x() {
     bus.write(0x1337, 0x68);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 7));
@@ -62,7 +62,7 @@ fn inx() {
     let program = vec![0xE8];
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x68;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idx, 0x69);
     assert!(verify_clocks(&core, 2));
@@ -74,7 +74,7 @@ fn iny() {
     let program = vec![0xC8];
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x68;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 2));
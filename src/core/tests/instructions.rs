@@ -0,0 +1,83 @@
+use crate::core::{
+    Core, Mode, Variant, CMOS_INSTRUCTIONS, NMOS_INSTRUCTIONS, W65C816_INSTRUCTIONS,
+};
+
+use super::*;
+
+const FLOW: &[&str] = &[
+    "BRK", "COP", "JMP", "JML", "JSR", "JSL", "RTS", "RTI", "RTL", "BRA", "BRL", "JAM",
+];
+
+/// Runs `opcode` with operands pointing at $2010, or through a pointer to
+/// $20F0, and both index registers set to `index`. Returns the cycles
+/// taken and the new program counter.
+fn run(variant: Variant, opcode: u8, index: u8) -> (u32, u16) {
+    let mut bus = MockBus::new();
+    bus.write(0x10, 0xF0);
+    bus.write(0x11, 0x20);
+    let program = vec![opcode, 0x10, 0x20, 0x00];
+    let mut core = Core::with_variant(bus, program, variant).unwrap();
+    core.idx = index;
+    core.idy = index;
+    let outcome = core.step().unwrap();
+    (outcome.cycles, core.pc)
+}
+
+fn matches_core(variant: Variant) {
+    for (opcode, instruction) in variant.instructions().iter().enumerate() {
+        let opcode = opcode as u8;
+        assert!(instruction.variants.contains(&variant));
+        let branch = matches!(instruction.mode, Mode::Relative | Mode::ZeroPageRelative)
+            && !FLOW.contains(&instruction.mnemonic);
+        if branch || instruction.mnemonic == "JAM" {
+            continue;
+        }
+
+        let (cycles, pc) = run(variant, opcode, 0x00);
+        assert_eq!(cycles, instruction.cycles as u32, "{opcode:#04x}");
+        if !FLOW.contains(&instruction.mnemonic) {
+            assert_eq!(pc, instruction.length as u16, "{opcode:#04x}");
+        }
+
+        let (cycles, _) = run(variant, opcode, 0xFF);
+        let penalty = instruction.page_cross && !FLOW.contains(&instruction.mnemonic);
+        assert_eq!(
+            cycles,
+            instruction.cycles as u32 + penalty as u32,
+            "{opcode:#04x} crossing a page"
+        );
+    }
+}
+
+#[test]
+fn nmos_matches_core() {
+    matches_core(Variant::Nmos6502);
+}
+
+#[test]
+fn cmos_matches_core() {
+    matches_core(Variant::Cmos65C02);
+}
+
+#[test]
+fn w65c816_matches_core() {
+    matches_core(Variant::W65C816);
+}
+
+#[test]
+fn shared_variants() {
+    let lda = NMOS_INSTRUCTIONS[0xA9];
+    assert_eq!(lda.mnemonic, "LDA");
+    assert_eq!(lda.mode, Mode::Immediate);
+    assert_eq!(lda.variants.len(), 6);
+
+    let slo = Variant::Mos6510.instructions()[0x07];
+    assert_eq!(slo.mnemonic, "SLO");
+    assert!(!slo.documented);
+    assert!(!slo.variants.contains(&Variant::Cmos65C02));
+
+    let rmb = CMOS_INSTRUCTIONS[0x07];
+    assert_eq!(rmb.mnemonic, "RMB0");
+    assert_eq!(rmb.variants, &[Variant::Cmos65C02]);
+    assert_eq!(W65C816_INSTRUCTIONS[0x07].mode, Mode::IndirectLong);
+}
@@ -0,0 +1,117 @@
+use crate::core::Core;
+
+use super::*;
+
+#[test]
+fn irq() {
+    let bus = interrupt_bus();
+    let program = vec![0xEA, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.step().unwrap();
+    core.set_irq(true);
+    core.step().unwrap();
+    core.bus_mut().write(0xc10c, 0);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x2000);
+    assert_eq!(core.sp, 0xfc);
+    assert!(core.status.interrupt());
+    assert_eq!(core.bus_mut().read(0x01FF), 0x00);
+    assert_eq!(core.bus_mut().read(0x01FE), 0x02);
+    assert_eq!(core.bus_mut().read(0x01FD), 0b0010_0000);
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn irq_masked() {
+    let bus = interrupt_bus();
+    let program = vec![0xEA, 0xEA, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_interrupt(true);
+    core.set_irq(true);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x0003);
+}
+
+#[test]
+fn irq_is_level_triggered() {
+    let mut bus = interrupt_bus();
+    bus.write(0x2000, 0x40);
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_irq(true);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    // the line is still held, so the handler is entered again after RTI
+    assert_eq!(core.pc, 0x2000);
+    assert_eq!(core.sp, 0xfc);
+}
+
+#[test]
+fn cli_delays_irq_by_one_instruction() {
+    let bus = interrupt_bus();
+    let program = vec![0x58, 0xE8, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_interrupt(true);
+    core.set_irq(true);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0x01);
+    assert_eq!(core.pc, 0x2000);
+}
+
+#[test]
+fn nmi() {
+    let bus = interrupt_bus();
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_interrupt(true);
+    core.set_nmi(true);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x3000);
+    assert_eq!(core.bus_mut().read(0x01FD), 0b0010_0100);
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn nmi_is_edge_triggered() {
+    let mut bus = interrupt_bus();
+    bus.write(0x3000, 0xEA);
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_nmi(true);
+    core.step().unwrap();
+    core.step().unwrap();
+
+    // the line is still held, but no new edge has occurred
+    assert_eq!(core.pc, 0x3001);
+
+    core.set_nmi(false);
+    core.set_nmi(true);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x3000);
+}
+
+#[test]
+fn nmi_hijacks_brk() {
+    let bus = interrupt_bus();
+    let program = vec![0x00];
+    let mut core = Core::new(bus, program).unwrap();
+    core.fetch();
+    core.nmi_pending = true;
+    core.brk();
+
+    assert_eq!(core.pc, 0x3000);
+    assert!(!core.nmi_pending);
+    assert_eq!(core.bus_mut().read(0x01FD), 0b0011_0000);
+}
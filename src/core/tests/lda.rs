@@ -5,7 +5,7 @@ fn lda_immediate() {
     let bus = MockBus::new();
     let program = vec![0xa9, 0x05];
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x05);
     assert!(verify_clocks(&core, 2));
@@ -17,7 +17,7 @@ fn lda_zeropage() {
     let program = vec![0xa5, 0x20];
     bus.write(0x0020, 0x69);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 3));
@@ -30,7 +30,7 @@ fn lda_zeropage_x() {
     bus.write(0x0025, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -42,7 +42,7 @@ fn lda_absolute() {
     let program = vec![0xad, 0x37, 0x13];
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -55,7 +55,7 @@ fn lda_absolute_x() {
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -68,7 +68,7 @@ fn lda_absolute_x_page_crossed() {
     bus.write(0x2100, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -81,7 +81,7 @@ fn lda_absolute_y() {
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -94,7 +94,7 @@ fn lda_absolute_y_page_crossed() {
     bus.write(0x2100, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -109,7 +109,7 @@ fn lda_indexed_indirect() {
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 6));
@@ -124,7 +124,7 @@ fn lda_indirect_indexed() {
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -139,7 +139,7 @@ fn lda_indirect_indexed_page_crossed() {
     bus.write(0x2100, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 6));
@@ -151,9 +151,9 @@ fn lda_status_flags() {
     let program = vec![0xa9, 0xff, 0xa9, 0x00];
     let mut core = Core::new(bus, program).unwrap();
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b1000_0000);
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0000_0010);
 }
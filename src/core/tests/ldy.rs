@@ -7,7 +7,7 @@ fn ldy_immediate() {
     let bus = MockBus::new();
     let program = vec![0xa0, 0x69];
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 2));
@@ -19,7 +19,7 @@ fn ldy_zeropage() {
     let program = vec![0xa4, 0x20];
     bus.write(0x0020, 0x69);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 3));
@@ -32,7 +32,7 @@ fn ldy_zeropage_x() {
     bus.write(0x0025, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -44,7 +44,7 @@ fn ldy_absolute() {
     let program = vec![0xac, 0x37, 0x13];
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -57,7 +57,7 @@ fn ldy_absolute_x() {
     bus.write(0x1337, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -70,7 +70,7 @@ fn ldy_absolute_x_page_crossed() {
     bus.write(0x2100, 0x69);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -82,9 +82,9 @@ fn ldy_status_flags() {
     let program = vec![0xa0, 0xff, 0xa0, 0x00];
     let mut core = Core::new(bus, program).unwrap();
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b1000_0000);
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0000_0010);
 }
@@ -8,7 +8,7 @@ fn lsr_acc() {
     let program = vec![0x4a];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b1000_1000;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b0100_0100);
     assert!(verify_clocks(&core, 2));
@@ -20,8 +20,8 @@ fn lsr_zeropage() {
     let program = vec![0x46, 0x20];
     bus.write(0x0020, 0b1000_1000);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0b0100_0100);
     assert!(verify_clocks(&core, 5));
@@ -34,8 +34,8 @@ fn lsr_zeropage_x() {
     bus.write(0x0025, 0b1000_1000);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
-    core.step();
-    let byte = core.get_bus().read(0x0025);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0025);
 
     assert_eq!(byte, 0b0100_0100);
     assert!(verify_clocks(&core, 6));
@@ -47,8 +47,8 @@ fn lsr_absolute() {
     let program = vec![0x4e, 0x37, 0x13];
     bus.write(0x1337, 0b1000_1000);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b0100_0100);
     assert!(verify_clocks(&core, 6));
@@ -61,8 +61,8 @@ fn lsr_absolute_x() {
     bus.write(0x1337, 0b1000_1000);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b0100_0100);
     assert!(verify_clocks(&core, 7));
@@ -75,9 +75,9 @@ fn lsr_status_flags() {
     let mut core = Core::new(bus, program).unwrap();
 
     core.acc = 0b0000_0010;
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0000_0000);
 
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.status.as_byte(), 0b0000_0011);
 }
@@ -13,38 +13,62 @@ mod bmi;
 mod bne;
 mod bpl;
 mod brk;
+mod bus;
+mod bus_cycles;
+mod bus_trace;
 mod bvc;
 mod bvs;
 mod clv;
+mod cmos;
 mod cmp;
 mod cpx;
 mod cpy;
 mod dec;
+mod decimal;
 mod eor;
 mod inc;
+mod instructions;
+mod interrupts;
 mod jmp;
 mod jsr;
 mod lda;
 mod ldx;
 mod ldy;
 mod lsr;
+mod mos6507;
+mod movie;
 mod ora;
+mod policy;
+mod port;
 mod push_pull;
+mod rdy;
+mod reset;
+mod rewind;
 mod rol;
 mod ror;
+mod rti;
+mod run;
+mod save;
 mod sbc;
 mod sec;
 mod sed;
 mod sei;
+mod so;
 mod sta;
+mod state;
+mod step;
 mod stx;
 mod sty;
 mod tax;
 mod tay;
+mod tick;
+mod trace;
 mod tsx;
 mod txa;
 mod txs;
 mod tya;
+mod undocumented;
+mod w65c816;
 
 #[derive(Debug, Clone)]
 struct MockBus {
@@ -81,10 +105,156 @@ impl Bus for MockBus {
     fn dump_rom(&self) -> Vec<u8> {
         self.mem.to_vec()
     }
+
+    fn peek(&self, addr: u32) -> Option<u8> {
+        Some(self.mem[addr as u16 as usize])
+    }
+
+    fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend_from_slice(&self.mem);
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), crate::error::StateError> {
+        self.mem.copy_from_slice(state);
+        Ok(())
+    }
 }
 
-pub fn verify_clocks(core: &Core, expected: i32) -> bool {
-    let clocks = core.get_bus().read(0xc10c);
+fn verify_clocks(core: &Core<MockBus>, expected: i32) -> bool {
+    let clocks = core.bus().mem[0xc10c];
     dbg!(clocks);
-    return (clocks as i32) == expected;
+    (clocks as i32) == expected
+}
+
+/// A core running a counter loop that keeps storing X at $2000.
+fn counter_core() -> Core<MockBus> {
+    // INX, STX $2000, JMP $0000
+    let program = vec![0xE8, 0x8E, 0x00, 0x20, 0x4C, 0x00];
+    Core::new(MockBus::new(), program).unwrap()
+}
+
+/// A [`MockBus`] with the NMI vector pointing at $3000 and the IRQ vector
+/// at $2000.
+fn interrupt_bus() -> MockBus {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFA, 0x00);
+    bus.write(0xFFFB, 0x30);
+    bus.write(0xFFFE, 0x00);
+    bus.write(0xFFFF, 0x20);
+    bus
+}
+
+/// A bus access, as logged by [`HookBus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read(u16),
+    Write(u16, u8),
+}
+
+/// RAM for the tests that need to watch or drive more than memory. It
+/// logs every access and counts clocks separately, so a cycle without a
+/// bus access shows up as a mismatch. RDY is held low while `dma` counts
+/// down one per clock, and SO rises once `so_at` clocks have passed.
+/// Reading $D000 returns `input`, which also drives the 6510 port pins,
+/// and reading $D001 acknowledges the IRQ.
+#[derive(Debug)]
+struct HookBus {
+    mem: Vec<u8>,
+    /// Where the program is loaded and the reset vector points.
+    origin: u16,
+    log: Vec<Access>,
+    clocks: u32,
+    dma: u32,
+    so_at: Option<u32>,
+    irq: bool,
+    input: u8,
+    /// Data and direction of every change of the 6510 port.
+    port_changes: Vec<(u8, u8)>,
+    /// Full addresses of the 6507, one for each access in `log`.
+    unmasked: Vec<u16>,
+}
+
+impl HookBus {
+    fn new(origin: u16) -> Self {
+        Self {
+            mem: vec![0; 0x10000],
+            origin,
+            log: Vec::new(),
+            clocks: 0,
+            dma: 0,
+            so_at: None,
+            irq: false,
+            input: 0,
+            port_changes: Vec::new(),
+            unmasked: Vec::new(),
+        }
+    }
+}
+
+impl Bus for HookBus {
+    fn read(&mut self, addr: u16) -> u8 {
+        self.log.push(Access::Read(addr));
+        match addr {
+            0xD000 => self.input,
+            0xD001 => {
+                self.irq = false;
+                0x00
+            }
+            _ => self.mem[addr as usize],
+        }
+    }
+
+    fn write(&mut self, addr: u16, byte: u8) {
+        self.log.push(Access::Write(addr, byte));
+        self.mem[addr as usize] = byte;
+    }
+
+    fn on_clock(&mut self) {
+        self.clocks += 1;
+        self.dma = self.dma.saturating_sub(1);
+    }
+
+    fn load_rom(&mut self, prog: Vec<u8>) -> Result<(), crate::error::BusError> {
+        let origin = self.origin as usize;
+        self.mem[origin..origin + prog.len()].copy_from_slice(&prog);
+        self.mem[0xfffc..0xfffe].copy_from_slice(&self.origin.to_le_bytes());
+        Ok(())
+    }
+
+    fn dump_rom(&self) -> Vec<u8> {
+        self.mem.clone()
+    }
+
+    fn irq(&self) -> bool {
+        self.irq
+    }
+
+    fn rdy(&self) -> bool {
+        self.dma == 0
+    }
+
+    fn so(&self) -> bool {
+        self.so_at.is_some_and(|at| self.clocks >= at)
+    }
+
+    fn port_input(&self) -> u8 {
+        self.input
+    }
+
+    fn port_changed(&mut self, data: u8, ddr: u8) {
+        self.port_changes.push((data, ddr));
+    }
+
+    fn unmasked_address(&mut self, addr: u16) {
+        self.unmasked.push(addr);
+    }
+
+    fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend_from_slice(&self.mem);
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), crate::error::StateError> {
+        self.mem.copy_from_slice(state);
+        Ok(())
+    }
 }
@@ -0,0 +1,78 @@
+use crate::core::{Core, Variant};
+
+use super::*;
+
+fn mos6507(mut bus: MockBus, program: Vec<u8>) -> Core<MockBus> {
+    // $E000 mirrors $0000, where the program is loaded
+    bus.write(0x1ffc, 0x00);
+    bus.write(0x1ffd, 0xe0);
+    Core::with_variant(bus, program, Variant::Mos6507).unwrap()
+}
+
+#[test]
+fn reset_vector_mirrors() {
+    let bus = MockBus::new();
+    let program = vec![0xA9, 0x42];
+    let mut core = mos6507(bus, program);
+
+    assert_eq!(core.pc, 0xe000);
+
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x42);
+    assert_eq!(core.pc, 0xe002);
+}
+
+#[test]
+fn addresses_are_masked() {
+    let bus = MockBus::new();
+    let program = vec![0x8D, 0xFF, 0xFF];
+    let mut core = mos6507(bus, program);
+    core.acc = 0x69;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1fff), 0x69);
+    assert_eq!(core.bus_mut().read(0xffff), 0x00);
+}
+
+#[test]
+fn brk_vector_mirrors() {
+    let mut bus = MockBus::new();
+    bus.write(0x1ffe, 0x37);
+    bus.write(0x1fff, 0xf3);
+    let program = vec![0x00, 0x00];
+    let mut core = mos6507(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0xf337);
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn no_interrupt_lines() {
+    let bus = MockBus::new();
+    let program = vec![0x58, 0xEA, 0xEA];
+    let mut core = mos6507(bus, program);
+    core.set_irq(true);
+    core.set_nmi(true);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0xe003);
+}
+
+#[test]
+fn bus_sees_unmasked_address() {
+    let bus = HookBus::new(0x0000);
+    let program = vec![0xAD, 0xF8, 0xFF, 0xAD, 0xF8, 0x1F];
+    let mut core = Core::with_variant(bus, program, Variant::Mos6507).unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    let bus = core.bus();
+    let access = |i: usize| (bus.unmasked[i], bus.log[i]);
+    assert_eq!(access(0), (0xfffc, Access::Read(0x1ffc)));
+    assert_eq!(access(5), (0xfff8, Access::Read(0x1ff8)));
+    assert_eq!(access(9), (0x1ff8, Access::Read(0x1ff8)));
+}
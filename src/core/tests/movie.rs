@@ -0,0 +1,190 @@
+use crate::{
+    core::{Core, CpuState, Divergence, DivergenceReason, Movie, PowerOn, Variant},
+    error::StateError,
+};
+
+use super::*;
+
+/// A [`HookBus`] with an IRQ handler that acknowledges the IRQ. The
+/// test drives the input and the IRQ from outside, as a frontend would.
+fn io_bus() -> HookBus {
+    let mut bus = HookBus::new(0x0000);
+    // INC $11, LDA $D001, RTI
+    bus.mem[0x0300..0x0306].copy_from_slice(&[0xE6, 0x11, 0xAD, 0x01, 0xD0, 0x40]);
+    bus.mem[0xFFFE] = 0x00;
+    bus.mem[0xFFFF] = 0x03;
+    bus
+}
+
+fn core() -> Core<HookBus> {
+    // CLI, LDA $D000, ADC $10, STA $10, JMP $0001
+    let program = vec![
+        0x58, 0xAD, 0x00, 0xD0, 0x65, 0x10, 0x85, 0x10, 0x4C, 0x01, 0x00,
+    ];
+    Core::new(io_bus(), program).unwrap()
+}
+
+/// Records a run fed with changing input and a couple of interrupts.
+fn record() -> (Movie, Core<HookBus>) {
+    let mut core = core();
+    core.start_recording([0xD000..=0xD0FF], 1);
+    for i in 0..60u32 {
+        core.bus_mut().input = (i * 7) as u8;
+        if i % 25 == 10 {
+            core.bus_mut().irq = true;
+        }
+        if i == 30 {
+            core.set_nmi(true);
+        }
+        core.step().unwrap();
+    }
+
+    (core.stop_recording().unwrap(), core)
+}
+
+#[test]
+fn replays_exactly() {
+    let (movie, recorded) = record();
+    let movie = Movie::from_bytes(&movie.to_bytes()).unwrap();
+    let mut core = core();
+    core.start_playback(movie).unwrap();
+
+    core.run_until(|core| core.cycles() >= recorded.cycles());
+
+    assert_eq!(core.cycles(), recorded.cycles());
+    assert_eq!(core.state(), recorded.state());
+    assert_eq!(core.bus().mem, recorded.bus().mem);
+    assert_ne!(core.bus().mem[0x11], 0x00);
+    assert!(core.playback_finished());
+    assert_eq!(core.stop_playback(), None);
+}
+
+#[test]
+fn replays_port_input() {
+    // $0000 and $0001 are the port, so the program starts after them:
+    // LDA $01, ADC $10, STA $10, JMP $0002
+    let program = vec![
+        0x00, 0x00, 0xA5, 0x01, 0x65, 0x10, 0x85, 0x10, 0x4C, 0x02, 0x00,
+    ];
+    let core = || {
+        let mut core = Core::with_variant(io_bus(), program.clone(), Variant::Mos6510).unwrap();
+        core.set_state(CpuState {
+            pc: 0x0002,
+            ..core.state()
+        });
+        core
+    };
+    let mut recorded = core();
+    // the port is recorded even though it is outside every I/O range
+    recorded.start_recording([0xD000..=0xD0FF], 1);
+    for i in 0..40u32 {
+        recorded.bus_mut().input = (i * 5) as u8;
+        recorded.step().unwrap();
+    }
+    let movie = recorded.stop_recording().unwrap();
+    let movie = Movie::from_bytes(&movie.to_bytes()).unwrap();
+
+    let mut core = core();
+    core.start_playback(movie).unwrap();
+    core.run_until(|core| core.cycles() >= recorded.cycles());
+
+    assert_eq!(core.state(), recorded.state());
+    assert_eq!(core.bus().mem, recorded.bus().mem);
+    assert_ne!(core.bus().mem[0x10], 0x00);
+    assert_eq!(core.divergence(), None);
+}
+
+#[test]
+fn power_on_restarts_recording() {
+    let mut recorded = core();
+    recorded.start_recording([0xD000..=0xD0FF], 1);
+    recorded.run_for_instructions(10);
+    recorded.power_on(PowerOn::Zero);
+    let state = recorded.save_state();
+    for i in 0..20u32 {
+        recorded.bus_mut().input = (i * 3) as u8;
+        recorded.step().unwrap();
+    }
+    let movie = recorded.stop_recording().unwrap();
+
+    assert_eq!(movie.initial_state(), state);
+
+    let mut core = core();
+    core.start_playback(movie).unwrap();
+    core.run_until(|core| core.cycles() >= recorded.cycles());
+
+    assert_eq!(core.state(), recorded.state());
+    assert!(core.playback_finished());
+
+    core.power_on(PowerOn::Zero);
+
+    assert!(!core.playback_finished());
+}
+
+#[test]
+fn live_run_differs() {
+    let (_, recorded) = record();
+    let mut core = core();
+
+    core.run_until(|core| core.cycles() >= recorded.cycles());
+
+    assert_ne!(core.bus().mem, recorded.bus().mem);
+}
+
+#[test]
+fn state_divergence() {
+    let (movie, _) = record();
+    let mut core = core();
+    core.start_playback(movie).unwrap();
+    core.run_for_instructions(20);
+    core.set_state(CpuState {
+        idx: 0x42,
+        ..core.state()
+    });
+    core.step().unwrap();
+    let cycle = core.cycles();
+    core.run_for_instructions(20);
+
+    assert_eq!(
+        core.divergence(),
+        Some(Divergence {
+            cycle,
+            reason: DivergenceReason::State
+        })
+    );
+}
+
+#[test]
+fn read_divergence() {
+    let (movie, _) = record();
+    let mut core = core();
+    core.start_playback(movie).unwrap();
+    core.run_for_instructions(5);
+    // LDA $D000 becomes LDA $D002
+    core.bus_mut().mem[0x0002] = 0x02;
+    core.run_for_instructions(10);
+
+    assert!(matches!(
+        core.divergence(),
+        Some(Divergence {
+            reason: DivergenceReason::Read {
+                expected: 0xD000,
+                actual: 0xD002
+            },
+            ..
+        })
+    ));
+}
+
+#[test]
+fn corrupted_movie() {
+    let (movie, _) = record();
+    let mut bytes = movie.to_bytes();
+    let last = bytes.len() - 5;
+    bytes[last] ^= 0x01;
+
+    assert!(matches!(
+        Movie::from_bytes(&bytes),
+        Err(StateError::Checksum(_))
+    ));
+}
@@ -8,7 +8,7 @@ fn ora_immediate() {
     let program = vec![0x09, 0b0000_1000];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0000_1000;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 0b0000_1000);
     assert!(verify_clocks(&core, 2));
@@ -20,7 +20,7 @@ fn ora_zeropage() {
     let program = vec![0x05, 0x69];
     bus.write(0x0069, 0b1000_0000);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 0x80);
     assert!(verify_clocks(&core, 3));
@@ -33,7 +33,7 @@ fn ora_zeropage_x() {
     bus.write(0x0022, 0b0000_0001);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 4));
@@ -45,7 +45,7 @@ fn ora_absolute() {
     let program = vec![0x0D, 0x00, 0x20];
     bus.write(0x2000, 1);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 4));
@@ -58,7 +58,7 @@ fn ora_absolute_x() {
     bus.write(0x2020, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x20;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 4));
@@ -71,7 +71,7 @@ fn ora_absolute_x_page_crossed() {
     bus.write(0x2100, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 5));
@@ -84,7 +84,7 @@ fn ora_absolute_y() {
     bus.write(0x2001, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 4));
@@ -97,7 +97,7 @@ fn ora_absolute_y_page_crossed() {
     bus.write(0x2100, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 1;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 5));
@@ -112,7 +112,7 @@ fn ora_indexed_indirect() {
     bus.write(0x1337, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 2;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 6));
@@ -127,7 +127,7 @@ fn ora_indirect_indexed() {
     bus.write(0x1337, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 5));
@@ -142,7 +142,7 @@ fn ora_indirect_indexed_page_crossed() {
     bus.write(0x1400, 1);
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
-    core.step();
+    core.step().unwrap();
 
     assert!(core.acc == 1);
     assert!(verify_clocks(&core, 6));
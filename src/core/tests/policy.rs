@@ -0,0 +1,124 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::{
+    core::{Core, HaltReason, OpcodePolicy},
+    error::CoreError,
+};
+
+use super::*;
+
+#[test]
+fn nop_skips_undocumented() {
+    let mut bus = MockBus::new();
+    bus.write(0x0020, 0x41);
+    let program = vec![0x0F, 0x20, 0x00, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_undocumented_opcodes(false);
+    core.set_opcode_policy(OpcodePolicy::Nop);
+    let outcome = core.step().unwrap();
+
+    assert_eq!(outcome.cycles, 6);
+    assert_eq!(core.pc, 0x0003);
+    assert_eq!(core.acc, 0x00);
+    assert!(!core.status.carry());
+    assert_eq!(core.bus_mut().read(0x0020), 0x41);
+
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0x01);
+}
+
+#[test]
+fn nop_skips_jam() {
+    let bus = MockBus::new();
+    let program = vec![0x02, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_opcode_policy(OpcodePolicy::Nop);
+
+    assert_eq!(core.step().unwrap().cycles, 2);
+    assert_eq!(core.pc, 0x0001);
+    assert_eq!(core.halt_reason(), None);
+}
+
+#[test]
+fn jam_locks_bus() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFA, 0x00);
+    bus.write(0xFFFB, 0x30);
+    let program = vec![0x02, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_opcode_policy(OpcodePolicy::Jam);
+    core.step().unwrap();
+
+    assert!(core.jammed());
+
+    core.set_nmi(true);
+    core.bus_mut().write(0xc10c, 0);
+    let outcome = core.step().unwrap();
+
+    assert_eq!(outcome.cycles, 1);
+    assert_eq!(outcome.opcode, 0x02);
+    assert_eq!(core.pc, 0x0001);
+    assert!(verify_clocks(&core, 1));
+
+    core.reset();
+
+    assert!(!core.jammed());
+}
+
+#[test]
+fn trap_emulates_opcode() {
+    let bus = MockBus::new();
+    let program = vec![0x02, 0x69, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_opcode_policy(OpcodePolicy::Trap(Box::new(|trap| {
+        if trap.opcode() != 0x02 {
+            return false;
+        }
+        let byte = trap.fetch();
+        trap.set_acc(byte);
+        trap.status().set_zero(byte == 0);
+        true
+    })));
+
+    assert_eq!(core.step().unwrap().cycles, 2);
+    assert_eq!(core.acc, 0x69);
+    assert_eq!(core.pc, 0x0002);
+}
+
+#[test]
+fn trap_declines_opcode() {
+    let bus = MockBus::new();
+    let program = vec![0x12];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_opcode_policy(OpcodePolicy::Trap(Box::new(|_| false)));
+
+    assert!(matches!(core.step(), Err(CoreError::Jam { .. })));
+    assert_eq!(core.halt_reason(), Some(HaltReason::Jammed));
+}
+
+#[test]
+fn trap_runs_once_under_tick() {
+    let bus = MockBus::new();
+    let program = vec![0x02, 0x69, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    let calls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&calls);
+    core.set_opcode_policy(OpcodePolicy::Trap(Box::new(move |trap| {
+        counter.set(counter.get() + 1);
+        let byte = trap.fetch();
+        trap.set_acc(byte);
+        true
+    })));
+    let outcome = core.tick().unwrap().unwrap();
+
+    assert_eq!(outcome.cycles, 2);
+    assert_eq!(calls.get(), 1);
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 2));
+
+    while core.tick().unwrap().is_none() {}
+
+    assert_eq!(calls.get(), 1);
+    assert_eq!(core.idx, 0x01);
+}
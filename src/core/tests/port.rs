@@ -0,0 +1,97 @@
+use crate::core::{Core, Variant};
+
+use super::*;
+
+/// A 6510 with the program clear of the port registers and `input` on
+/// the pins.
+fn mos6510(program: Vec<u8>, input: u8) -> Core<HookBus> {
+    let mut bus = HookBus::new(0x0200);
+    bus.input = input;
+    Core::with_variant(bus, program, Variant::Mos6510).unwrap()
+}
+
+#[test]
+fn reset_makes_every_bit_an_input() {
+    let core = mos6510(vec![], 0xff);
+
+    assert_eq!(core.pc, 0x0200);
+    assert_eq!(core.bus().port_changes, vec![(0x00, 0x00)]);
+}
+
+#[test]
+fn registers_read_back() {
+    // LDA #$2F; STA $00; LDA #$37; STA $01; LDA $00; LDX $01
+    let program = vec![
+        0xA9, 0x2F, 0x85, 0x00, 0xA9, 0x37, 0x85, 0x01, 0xA5, 0x00, 0xA6, 0x01,
+    ];
+    let mut core = mos6510(program, 0xff);
+    for _ in 0..6 {
+        core.step().unwrap();
+    }
+
+    assert_eq!(core.acc, 0x2f);
+    assert_eq!(core.idx, 0x37);
+}
+
+#[test]
+fn input_bits_come_from_the_bus() {
+    // LDA #$0F; STA $00; LDA #$00; STA $01; LDA $01
+    let program = vec![0xA9, 0x0F, 0x85, 0x00, 0xA9, 0x00, 0x85, 0x01, 0xA5, 0x01];
+    let mut core = mos6510(program, 0xef);
+    for _ in 0..5 {
+        core.step().unwrap();
+    }
+
+    // bit 4 is held low externally and bits 6-7 have never been driven
+    assert_eq!(core.acc, 0x20);
+}
+
+#[test]
+fn writes_notify_the_bus() {
+    // LDA #$07; STA $00; LDA #$35; STA $01
+    let program = vec![0xA9, 0x07, 0x85, 0x00, 0xA9, 0x35, 0x85, 0x01];
+    let mut core = mos6510(program, 0xff);
+    for _ in 0..4 {
+        core.step().unwrap();
+    }
+
+    assert_eq!(
+        core.bus().port_changes,
+        vec![(0x00, 0x00), (0x00, 0x07), (0x35, 0x07)]
+    );
+    // the write still reaches the RAM underneath
+    assert_eq!(core.bus_mut().read(0x0001), 0x35);
+}
+
+#[test]
+fn floating_bits_fade() {
+    // LDA #$FF; STA $00; LDA #$C0; STA $01; LDA #$00; STA $00; LDA $01
+    let program = vec![
+        0xA9, 0xFF, 0x85, 0x00, 0xA9, 0xC0, 0x85, 0x01, 0xA9, 0x00, 0x85, 0x00, 0xA5, 0x01, 0xA5,
+        0x01,
+    ];
+    let mut core = mos6510(program, 0x00);
+    for _ in 0..7 {
+        core.step().unwrap();
+    }
+
+    assert_eq!(core.acc, 0xc0);
+
+    for _ in 0..350_000 {
+        core.clock_bus();
+    }
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x00);
+}
+
+#[test]
+fn other_variants_have_no_port() {
+    let bus = MockBus::new();
+    let program = vec![0x85, 0x01];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x42;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0001), 0x42);
+}
@@ -8,8 +8,8 @@ fn pha() {
     let program = vec![0x48];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
-    core.step();
-    let byte = core.get_bus().read(0x01fe);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x01ff);
 
     assert_eq!(byte, 0x69);
     assert_eq!(core.sp, 0xfe);
@@ -22,10 +22,11 @@ fn pla() {
     let program = vec![0x68];
     bus.write(0x01ff, 0x69);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.sp = 0xfe;
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
-    assert_eq!(core.sp, 0x00);
+    assert_eq!(core.sp, 0xff);
     assert!(verify_clocks(&core, 4));
 }
 
@@ -36,10 +37,10 @@ fn php() {
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
     core.status.set_negative(true);
-    core.step();
-    let byte = core.get_bus().read(0x01fe);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x01ff);
 
-    assert_eq!(byte, 0x81);
+    assert_eq!(byte, 0xB1);
     assert!(verify_clocks(&core, 3));
 }
 
@@ -49,7 +50,8 @@ fn plp() {
     let program = vec![0x28];
     bus.write(0x01ff, 0x81);
     let mut core = Core::new(bus, program).unwrap();
-    core.step();
+    core.sp = 0xfe;
+    core.step().unwrap();
 
     assert_eq!(core.status.as_byte(), 0x81);
     assert!(verify_clocks(&core, 4));
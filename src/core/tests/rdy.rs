@@ -0,0 +1,107 @@
+use crate::core::{Core, StopReason, Variant};
+
+use super::*;
+
+#[test]
+fn bus_steals_cycles() {
+    let mut bus = HookBus::new(0x0000);
+    bus.mem[0x2000] = 0x42;
+    let program = vec![0xAD, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.bus_mut().log.clear();
+    core.bus_mut().dma = 3;
+
+    // each step hands back control after one stolen cycle
+    for stolen in 1..4 {
+        let outcome = core.step().unwrap();
+        assert!(outcome.stalled);
+        assert_eq!(outcome.stolen, stolen);
+    }
+    let outcome = core.step().unwrap();
+
+    assert!(!outcome.stalled);
+    assert_eq!(outcome.cycles, 7);
+    assert_eq!(outcome.stolen, 3);
+    assert_eq!(core.acc, 0x42);
+    assert_eq!(
+        core.bus().log,
+        [0x0000, 0x0000, 0x0000, 0x0000, 0x0001, 0x0002, 0x2000].map(Access::Read)
+    );
+}
+
+#[test]
+fn nmos_writes_ignore_rdy() {
+    let bus = MockBus::new();
+    let program = vec![0x8D, 0x00, 0x20, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x69;
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.set_rdy(false);
+
+    assert!(core.tick().unwrap().is_some());
+    assert_eq!(core.bus_mut().read(0x2000), 0x69);
+
+    // the opcode fetch of INX waits
+    for _ in 0..5 {
+        assert!(core.tick().unwrap().is_none());
+    }
+    core.set_rdy(true);
+    core.tick().unwrap();
+    let outcome = core.tick().unwrap().unwrap();
+
+    assert_eq!(core.idx, 0x01);
+    assert_eq!(outcome.cycles, 7);
+    assert_eq!(outcome.stolen, 5);
+}
+
+#[test]
+fn cmos_writes_wait() {
+    let bus = MockBus::new();
+    let program = vec![0x8D, 0x00, 0x20];
+    let mut core = Core::with_variant(bus, program, Variant::Cmos65C02).unwrap();
+    core.acc = 0x69;
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.set_rdy(false);
+
+    assert!(core.tick().unwrap().is_none());
+    assert_eq!(core.bus_mut().read(0x2000), 0x00);
+
+    core.set_rdy(true);
+
+    assert!(core.tick().unwrap().is_some());
+    assert_eq!(core.bus_mut().read(0x2000), 0x69);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn run_returns_while_held() {
+    let mut bus = MockBus::new();
+    bus.write(0x2000, 0x42);
+    let program = vec![0xAD, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.set_rdy(false);
+
+    let outcome = core.run_for_cycles(10);
+
+    assert_eq!(outcome.reason, StopReason::Stalled);
+    assert_eq!(outcome.cycles, 1);
+    assert_eq!(outcome.instructions, 0);
+    assert!(core.run().is_ok());
+    assert!(core.step().unwrap().stalled);
+    assert_eq!(core.acc, 0x00);
+
+    core.set_rdy(true);
+    let outcome = core.step().unwrap();
+
+    assert!(!outcome.stalled);
+    assert_eq!(outcome.stolen, 3);
+    assert_eq!(outcome.cycles, 7);
+    assert_eq!(core.acc, 0x42);
+}
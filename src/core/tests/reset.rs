@@ -0,0 +1,117 @@
+use crate::{
+    core::{Core, PowerOn, Variant},
+    default::DefaultBus,
+};
+
+use super::*;
+
+#[test]
+fn reset_sequence() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFC, 0x37);
+    bus.write(0xFFFD, 0x13);
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x69;
+    core.idx = 0x42;
+    core.status.set_decimal(true);
+    core.bus_mut().write(0xc10c, 0);
+    core.reset();
+
+    assert_eq!(core.pc, 0x1337);
+    assert_eq!(core.sp, 0xfc);
+    assert_eq!(core.acc, 0x69);
+    assert_eq!(core.idx, 0x42);
+    assert!(core.status.interrupt());
+    assert!(core.status.decimal());
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn reset_leaves_memory_alone() {
+    let bus = MockBus::new();
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.bus_mut().write(0x01ff, 0x69);
+    core.reset();
+
+    assert_eq!(core.bus_mut().read(0x01ff), 0x69);
+}
+
+#[test]
+fn cmos_reset_clears_decimal() {
+    let bus = MockBus::new();
+    let program = vec![0xEA];
+    let mut core = Core::with_variant(bus, program, Variant::Cmos65C02).unwrap();
+    core.status.set_decimal(true);
+    core.reset();
+
+    assert!(!core.status.decimal());
+}
+
+#[test]
+fn w65c816_reset_enters_emulation() {
+    let bus = MockBus::new();
+    // CLC, XCE, REP #$30
+    let program = vec![0x18, 0xFB, 0xC2, 0x30];
+    let mut core = Core::with_variant(bus, program, Variant::W65C816).unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+    core.reset();
+
+    assert!(core.emulation_mode());
+    assert!(core.status.memory_8bit());
+    assert!(core.status.index_8bit());
+    assert_eq!(core.sp_hi, 0x01);
+}
+
+#[test]
+fn power_on_zero() {
+    let bus = DefaultBus::default();
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.bus_mut().write(0x0200, 0x69);
+    core.power_on(PowerOn::Zero);
+
+    assert_eq!(core.sp, 0xfd);
+    assert_eq!(core.acc, 0x00);
+    assert_eq!(core.pc, 0x0000);
+    assert!(core.status.interrupt());
+    assert_eq!(core.bus_mut().read(0x0200), 0x00);
+}
+
+#[test]
+fn power_on_pattern() {
+    let bus = DefaultBus::default();
+    let program = vec![0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.power_on(PowerOn::Pattern(vec![0x00, 0x00, 0xff, 0xff]));
+
+    // the registers start halfway through the pattern
+    assert_eq!(core.acc, 0xff);
+    assert_eq!(core.idy, 0x00);
+    assert_eq!(core.sp, 0xfd);
+    assert_eq!(core.bus_mut().read(0x0000), 0x00);
+    assert_eq!(core.bus_mut().read(0x0002), 0xff);
+    assert_eq!(core.bus_mut().read(0x7ffd), 0x00);
+    assert_eq!(core.bus_mut().read(0x7fff), 0xff);
+}
+
+#[test]
+fn power_on_random_is_seeded() {
+    let dump = |seed| {
+        let bus = DefaultBus::default();
+        let mut core = Core::new(bus, vec![0xEA]).unwrap();
+        core.power_on(PowerOn::Random(seed));
+        let ram: Vec<u8> = (0..0x100).map(|addr| core.bus_mut().read(addr)).collect();
+        (core.acc, core.idx, core.idy, core.sp, ram)
+    };
+
+    assert_eq!(dump(6502), dump(6502));
+    assert_ne!(dump(6502), dump(6510));
+
+    // the registers don't repeat the first cells of RAM
+    let (acc, idx, idy, _, ram) = dump(6502);
+    assert_ne!([acc, idx, idy], ram[..3]);
+}
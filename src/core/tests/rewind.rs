@@ -0,0 +1,105 @@
+use crate::core::{Core, PowerOn};
+
+use super::*;
+
+/// A fresh core run up to `cycles`, for comparison.
+fn reference(cycles: u64) -> Core<MockBus> {
+    let mut core = counter_core();
+    core.run_until(|core| core.cycles() >= cycles);
+    core
+}
+
+#[test]
+fn goes_back() {
+    let mut core = counter_core();
+    core.enable_rewind(20, 16);
+    core.run_for_cycles(200);
+    let now = core.cycles();
+
+    let undone = core.rewind(45).unwrap();
+
+    assert!((43..=45).contains(&undone));
+    assert_eq!(core.cycles(), now - undone);
+    let expected = reference(now - 45);
+    assert_eq!(core.state(), expected.state());
+    assert_eq!(core.bus().mem[0x2000], expected.bus().mem[0x2000]);
+}
+
+#[test]
+fn runs_on_after_rewinding() {
+    let mut core = counter_core();
+    core.enable_rewind(10, 8);
+    core.run_for_cycles(100);
+    core.rewind(30).unwrap();
+    core.run_for_cycles(50);
+    core.rewind(25).unwrap();
+
+    let expected = reference(core.cycles());
+    assert_eq!(core.state(), expected.state());
+    assert_eq!(core.bus().mem[0x2000], expected.bus().mem[0x2000]);
+}
+
+#[test]
+fn bounded_history() {
+    let mut core = counter_core();
+    core.enable_rewind(10, 4);
+    core.run_for_cycles(200);
+    let now = core.cycles();
+
+    let undone = core.rewind(200).unwrap();
+
+    assert!((30..50).contains(&undone));
+    assert_eq!(core.cycles(), now - undone);
+    assert_eq!(core.state(), reference(core.cycles()).state());
+}
+
+#[test]
+fn disabled() {
+    let mut core = counter_core();
+    core.run_for_cycles(100);
+    let state = core.state();
+
+    assert_eq!(core.rewind(50).unwrap(), 0);
+    assert_eq!(core.state(), state);
+
+    core.enable_rewind(10, 4);
+    core.disable_rewind();
+
+    assert_eq!(core.rewind(50).unwrap(), 0);
+}
+
+#[test]
+fn power_on_restarts_history() {
+    let mut core = counter_core();
+    core.enable_rewind(10, 8);
+    core.run_for_cycles(100);
+    core.power_on(PowerOn::Zero);
+    let start = (core.cycles(), core.state());
+    core.run_for_cycles(40);
+
+    let undone = core.rewind(1000).unwrap();
+
+    assert!((40..50).contains(&undone));
+    assert_eq!((core.cycles(), core.state()), start);
+}
+
+#[test]
+fn replay_is_not_traced() {
+    let mut core = counter_core();
+    core.enable_rewind(20, 8);
+    core.start_bus_trace();
+    core.start_recording([0x2000..=0x2000], 1);
+    core.run_for_cycles(100);
+    let traced = core.bus_trace().unwrap().cycles().len();
+    let mut reference = counter_core();
+    reference.start_recording([0x2000..=0x2000], 1);
+    reference.run_for_cycles(100);
+
+    core.rewind(30).unwrap();
+
+    assert_eq!(core.bus_trace().unwrap().cycles().len(), traced);
+    assert_eq!(
+        core.stop_recording().unwrap().to_bytes(),
+        reference.stop_recording().unwrap().to_bytes()
+    );
+}
@@ -9,14 +9,14 @@ fn rol_acc() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0110_0110;
     core.status.set_carry(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b1100_1101);
     assert!(verify_clocks(&core, 2));
 
     core.reset();
     core.acc = 0b0110_0110;
-    core.step();
+    core.step().unwrap();
     assert_eq!(core.acc, 0b1100_1100);
 }
 
@@ -27,8 +27,8 @@ fn rol_zeropage() {
     bus.write(0x0020, 0b0110_0110);
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0b1100_1101);
     assert!(verify_clocks(&core, 5));
@@ -42,8 +42,8 @@ fn rol_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x0025);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0025);
 
     assert_eq!(byte, 0b1100_1101);
     assert!(verify_clocks(&core, 6));
@@ -56,8 +56,8 @@ fn rol_absolute() {
     bus.write(0x1337, 0b0110_0110);
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1100_1101);
     assert!(verify_clocks(&core, 6));
@@ -71,8 +71,8 @@ fn rol_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1100_1101);
     assert!(verify_clocks(&core, 7));
@@ -9,7 +9,7 @@ fn ror_acc() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0b0110_0110;
     core.status.set_carry(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0b1011_0011, "got {:0>8b}", core.acc);
     assert!(verify_clocks(&core, 2));
@@ -22,8 +22,8 @@ fn ror_zeropage() {
     bus.write(0x0020, 0b0110_0110);
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0b1011_0011);
     assert!(verify_clocks(&core, 5));
@@ -37,8 +37,8 @@ fn ror_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x0025);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0025);
 
     assert_eq!(byte, 0b1011_0011);
     assert!(verify_clocks(&core, 6));
@@ -51,8 +51,8 @@ fn ror_absolute() {
     bus.write(0x1337, 0b0110_0110);
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1011_0011);
     assert!(verify_clocks(&core, 6));
@@ -66,8 +66,8 @@ fn ror_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0b1011_0011);
     assert!(verify_clocks(&core, 7));
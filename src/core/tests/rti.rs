@@ -0,0 +1,35 @@
+use crate::core::Core;
+
+use super::*;
+
+#[test]
+fn rti() {
+    let bus = MockBus::new();
+    let program = vec![0x40];
+    let mut core = Core::new(bus, program).unwrap();
+    core.sp -= 3;
+    core.bus_mut().write(0x01FD, 0b1111_0011);
+    core.bus_mut().write(0x01FE, 0x37);
+    core.bus_mut().write(0x01FF, 0x13);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    assert_eq!(core.sp, 0xff);
+    assert_eq!(core.status.as_byte(), 0b1100_0011);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn brk_rti_round_trip() {
+    let mut bus = MockBus::new();
+    let program = vec![0x00, 0xEA, 0xE8];
+    bus.write(0x2000, 0x40);
+    bus.write(0xFFFE, 0x00);
+    bus.write(0xFFFF, 0x20);
+    let mut core = Core::new(bus, program).unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x0002);
+    assert!(!core.status.interrupt());
+}
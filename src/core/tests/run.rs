@@ -0,0 +1,108 @@
+use crate::core::{Core, HaltReason, RunOutcome, StopReason};
+
+use super::*;
+
+// INX, JMP $0000
+const LOOP: [u8; 4] = [0xE8, 0x4C, 0x00, 0x00];
+
+#[test]
+fn for_cycles() {
+    let bus = MockBus::new();
+    let program = vec![0xEA; 8];
+    let mut core = Core::new(bus, program).unwrap();
+
+    assert_eq!(
+        core.run_for_cycles(5),
+        RunOutcome {
+            cycles: 6,
+            instructions: 3,
+            reason: StopReason::Cycles
+        }
+    );
+    assert_eq!(core.cycles(), 6);
+    assert!(verify_clocks(&core, 6));
+
+    assert_eq!(core.run_for_cycles(0).cycles, 0);
+}
+
+#[test]
+fn for_cycles_until_halt() {
+    let bus = MockBus::new();
+    // NOP, JAM
+    let program = vec![0xEA, 0x02];
+    let mut core = Core::new(bus, program).unwrap();
+
+    let outcome = core.run_for_cycles(u64::MAX);
+
+    assert_eq!(outcome.reason, StopReason::Halted(HaltReason::Jammed));
+    assert_eq!(outcome.instructions, 2);
+}
+
+#[test]
+fn for_instructions() {
+    let bus = MockBus::new();
+    let program = LOOP.to_vec();
+    let mut core = Core::new(bus, program).unwrap();
+
+    assert_eq!(
+        core.run_for_instructions(4),
+        RunOutcome {
+            cycles: 10,
+            instructions: 4,
+            reason: StopReason::Instructions
+        }
+    );
+    assert_eq!(core.idx, 0x02);
+}
+
+#[test]
+fn until_pc() {
+    let bus = MockBus::new();
+    let program = LOOP.to_vec();
+    let mut core = Core::new(bus, program).unwrap();
+
+    let outcome = core.run_until_pc(0x0000);
+
+    assert_eq!(outcome.reason, StopReason::Pc);
+    assert_eq!(outcome.instructions, 2);
+    assert_eq!(core.idx, 0x01);
+
+    core.run_until_pc(0x0000);
+
+    assert_eq!(core.idx, 0x02);
+    assert_eq!(core.cycles(), 10);
+}
+
+#[test]
+fn until_predicate() {
+    let bus = MockBus::new();
+    let program = LOOP.to_vec();
+    let mut core = Core::new(bus, program).unwrap();
+
+    let outcome = core.run_until(|core| core.idx == 3);
+
+    assert_eq!(outcome.reason, StopReason::Predicate);
+    assert_eq!(outcome.cycles, 12);
+    assert_eq!(core.pc, 0x0001);
+}
+
+#[test]
+fn stops_on_halt() {
+    let bus = MockBus::new();
+    let program = vec![0xEA, 0x02];
+    let mut core = Core::new(bus, program).unwrap();
+
+    let outcome = core.run_for_cycles(100);
+
+    assert_eq!(outcome.reason, StopReason::Halted(HaltReason::Jammed));
+    assert_eq!(outcome.instructions, 2);
+
+    assert_eq!(
+        core.run_until(|_| true),
+        RunOutcome {
+            cycles: 0,
+            instructions: 0,
+            reason: StopReason::Halted(HaltReason::Jammed)
+        }
+    );
+}
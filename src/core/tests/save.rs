@@ -0,0 +1,223 @@
+use crate::{
+    core::{
+        save::{chunks, container, crc32, Reader, Writer},
+        Core, HaltReason, Variant, STATE_VERSION,
+    },
+    default::DefaultBus,
+    error::StateError,
+    traits::Bus,
+};
+
+use super::*;
+
+fn core() -> Core<MockBus> {
+    let mut core = counter_core();
+    core.run_for_instructions(10);
+    core
+}
+
+#[test]
+fn round_trip() {
+    let mut core = core();
+    let state = core.save_state();
+    let before = core.state();
+    core.run_for_instructions(7);
+    let after = (core.state(), core.cycles(), core.bus().mem[0x2000]);
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.state(), before);
+    assert_eq!(core.cycles(), 29);
+    assert_eq!(core.bus().mem[0x2000], 0x03);
+
+    core.run_for_instructions(7);
+
+    assert_eq!((core.state(), core.cycles(), core.bus().mem[0x2000]), after);
+}
+
+#[test]
+fn mid_instruction() {
+    let mut core = core();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    let state = core.save_state();
+    core.step().unwrap();
+    let after = core.state();
+
+    core.load_state(&state).unwrap();
+    core.tick().unwrap();
+    let outcome = core.tick().unwrap().unwrap();
+
+    assert_eq!(outcome.opcode, 0x8E);
+    assert_eq!(outcome.cycles, 4);
+    assert_eq!(core.state(), after);
+}
+
+#[test]
+fn halted() {
+    let bus = MockBus::new();
+    let mut core = Core::new(bus, vec![0x02]).unwrap();
+    assert!(core.step().is_err());
+    let state = core.save_state();
+    core.reset();
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.halt_reason(), Some(HaltReason::Jammed));
+}
+
+#[test]
+fn invalid_mode_halt() {
+    let mut w = Writer::default();
+    w.halt(Some(HaltReason::InvalidAddressingMode(0xAD)));
+    let bytes = w.into_bytes();
+
+    assert_eq!(
+        Reader::new(&bytes, STATE_VERSION).halt().unwrap(),
+        Some(HaltReason::InvalidAddressingMode(0xAD))
+    );
+    // version 1 wrote the name of the instruction instead
+    assert!(matches!(
+        Reader::new(&[4, 3, 0, 0, 0, b'L', b'D', b'A'], 1).halt(),
+        Err(StateError::Invalid(_))
+    ));
+}
+
+#[test]
+fn corrupted() {
+    let mut core = core();
+    let state = core.save_state();
+    let before = core.state();
+    let mut flipped = state.clone();
+    flipped[20] ^= 0x01;
+
+    assert!(matches!(
+        core.load_state(&flipped),
+        Err(StateError::Checksum(tag)) if tag == "CORE"
+    ));
+    assert!(matches!(
+        core.load_state(&state[..state.len() - 1]),
+        Err(StateError::Truncated)
+    ));
+    assert!(matches!(
+        core.load_state(b"NOPE"),
+        Err(StateError::BadMagic)
+    ));
+    assert_eq!(core.state(), before);
+}
+
+#[test]
+fn unstable_magic() {
+    let mut core = core();
+    core.set_unstable_magic(Some(0xff));
+    let state = core.save_state();
+    core.set_unstable_magic(None);
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.save_state(), state);
+}
+
+#[test]
+fn newer_version() {
+    let mut core = core();
+    let mut state = core.save_state();
+    state[4..6].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+
+    assert!(matches!(
+        core.load_state(&state),
+        Err(StateError::UnsupportedVersion(_))
+    ));
+}
+
+/// Rewrites a state in the version 1 layout, which lacks the unstable
+/// magic value.
+fn version_1(core: &mut Core<MockBus>) -> Vec<u8> {
+    let state = core.save_state();
+    core.set_unstable_magic(Some(0xa5));
+    let marked = core.save_state();
+    core.set_unstable_magic(None);
+
+    let data = chunks(b"MOSS", &state).unwrap();
+    let marked = chunks(b"MOSS", &marked).unwrap();
+    let mut cpu = data.get(b"CORE", "CORE").unwrap().to_vec();
+    let other = marked.get(b"CORE", "CORE").unwrap();
+    let at = (0..cpu.len()).find(|&i| cpu[i] != other[i]).unwrap();
+    cpu.drain(at..at + 2);
+
+    let mut old = container(
+        b"MOSS",
+        &[
+            (b"CORE", &cpu),
+            (b"BUS ", data.get(b"BUS ", "BUS ").unwrap()),
+        ],
+    );
+    old[4..6].copy_from_slice(&1u16.to_le_bytes());
+    old
+}
+
+#[test]
+fn older_version() {
+    let mut core = core();
+    let state = version_1(&mut core);
+    let before = core.state();
+    core.step().unwrap();
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.state(), before);
+}
+
+#[test]
+fn wrong_variant() {
+    let state = core().save_state();
+    let bus = MockBus::new();
+    let mut core = Core::with_variant(bus, vec![], Variant::Cmos65C02).unwrap();
+
+    assert!(matches!(
+        core.load_state(&state),
+        Err(StateError::WrongVariant {
+            expected: Variant::Cmos65C02,
+            found: Variant::Nmos6502
+        })
+    ));
+}
+
+#[test]
+fn skips_unknown_chunks() {
+    let mut core = core();
+    let mut state = core.save_state();
+    let data = [1, 2, 3];
+    state.extend_from_slice(b"NEW!");
+    state.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    state.extend_from_slice(&data);
+    state.extend_from_slice(&crc32(&data).to_le_bytes());
+    let before = core.state();
+    core.step().unwrap();
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.state(), before);
+}
+
+#[test]
+fn default_bus() {
+    let mut bus = DefaultBus::default();
+    bus.write(0x1234, 0x56);
+    let mut core = Core::new(bus, vec![]).unwrap();
+    let state = core.save_state();
+    core.bus_mut().write(0x1234, 0x00);
+
+    core.load_state(&state).unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1234), 0x56);
+    assert!(matches!(
+        core.bus_mut().load_state(&[0; 4]),
+        Err(StateError::Invalid(_))
+    ));
+}
+
+#[test]
+fn checksum() {
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+}
@@ -8,7 +8,8 @@ fn sbc_immediate() {
     let program = vec![0xE9, 0x08];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 2));
@@ -21,7 +22,8 @@ fn sbc_zeropage() {
     bus.write(0x0020, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 3));
@@ -35,7 +37,8 @@ fn sbc_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x05;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 4));
@@ -48,7 +51,8 @@ fn sbc_absolute() {
     bus.write(0x1337, 0x08);
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 4));
@@ -62,7 +66,8 @@ fn sbc_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x04;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 4));
@@ -76,7 +81,8 @@ fn sbc_absolute_x_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x01;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 5));
@@ -90,7 +96,8 @@ fn sbc_absolute_y() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 4));
@@ -104,7 +111,8 @@ fn sbc_absolute_y_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 5));
@@ -120,7 +128,8 @@ fn sbc_indexed_indirect() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 6));
@@ -136,7 +145,8 @@ fn sbc_indirect_indexed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x04;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 5));
@@ -152,7 +162,8 @@ fn sbc_indirect_indexed_page_crossed() {
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x01;
     core.acc = 0x10;
-    core.step();
+    core.status.set_carry(true);
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x08);
     assert!(verify_clocks(&core, 6));
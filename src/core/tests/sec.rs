@@ -8,7 +8,7 @@ fn sec() {
     let program = vec![0x38];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(false); // known initial state
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.carry(), true);
     assert!(verify_clocks(&core, 2));
@@ -20,7 +20,7 @@ fn clc() {
     let program = vec![0x18];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_carry(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.carry(), false);
     assert!(verify_clocks(&core, 2));
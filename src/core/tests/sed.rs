@@ -8,7 +8,7 @@ fn sed() {
     let program = vec![0xF8];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_decimal(false); // known initial state
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.decimal(), true);
     assert!(verify_clocks(&core, 2));
@@ -20,7 +20,7 @@ fn cld() {
     let program = vec![0xD8];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_decimal(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.decimal(), false);
     assert!(verify_clocks(&core, 2));
@@ -8,7 +8,7 @@ fn sei() {
     let program = vec![0x78];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_interrupt(false); // known initial state
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.interrupt(), true);
     assert!(verify_clocks(&core, 2));
@@ -20,7 +20,7 @@ fn cli() {
     let program = vec![0x58];
     let mut core = Core::new(bus, program).unwrap();
     core.status.set_interrupt(true);
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.status.interrupt(), false);
     assert!(verify_clocks(&core, 2));
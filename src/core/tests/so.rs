@@ -0,0 +1,76 @@
+use crate::core::{Core, Variant};
+
+use super::*;
+
+#[test]
+fn falling_edge_sets_overflow() {
+    let bus = MockBus::new();
+    let program = vec![0xB8, 0xB8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_so(true);
+
+    assert!(core.status.overflow());
+
+    core.step().unwrap();
+
+    // still held, so there is no new edge
+    assert!(!core.status.overflow());
+
+    core.set_so(false);
+    core.set_so(true);
+
+    assert!(core.status.overflow());
+}
+
+#[test]
+fn no_pin_on_6510() {
+    let bus = MockBus::new();
+    let program = vec![0xEA];
+    let mut core = Core::with_variant(bus, program, Variant::Mos6510).unwrap();
+    core.set_so(true);
+
+    assert!(!core.status.overflow());
+}
+
+/// SO rises on the fifth clock, like a disk controller signalling that a
+/// byte is ready.
+fn byte_ready_core() -> Core<HookBus> {
+    let mut bus = HookBus::new(0x0000);
+    bus.so_at = Some(5);
+    // BVC *
+    let program = vec![0x50, 0xFE];
+    Core::new(bus, program).unwrap()
+}
+
+#[test]
+fn bvc_loop_exits() {
+    let mut core = byte_ready_core();
+    while core.pc != 0x0002 {
+        core.step().unwrap();
+    }
+
+    assert!(core.status.overflow());
+    assert_eq!(core.bus().clocks, 8);
+}
+
+#[test]
+fn bvc_loop_exits_when_ticked() {
+    let mut core = byte_ready_core();
+    // PC moves past the operand halfway through each BVC
+    while core.tick().unwrap().is_none() || core.pc != 0x0002 {}
+
+    assert!(core.status.overflow());
+    assert_eq!(core.bus().clocks, 8);
+}
+
+#[test]
+fn edge_between_ticks() {
+    let bus = MockBus::new();
+    let program = vec![0xAD, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.tick().unwrap();
+    core.set_so(true);
+    while core.tick().unwrap().is_none() {}
+
+    assert!(core.status.overflow());
+}
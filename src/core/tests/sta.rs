@@ -8,8 +8,8 @@ fn sta_zeropage() {
     let program = vec![0x85, 0x20];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
-    core.step();
-    let byte = core.get_bus().read(0x0020);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0020);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 3));
@@ -22,8 +22,8 @@ fn sta_zeropage_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0x69;
-    core.step();
-    let byte = core.get_bus().read(0x0022);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x0022);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -35,8 +35,8 @@ fn sta_absolute() {
     let program = vec![0x8D, 0x37, 0x13];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 4));
@@ -49,8 +49,8 @@ fn sta_absolute_x() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
     core.idx = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -63,8 +63,8 @@ fn sta_absolute_y() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
     core.idy = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 5));
@@ -79,8 +79,8 @@ fn sta_indexed_indirect() {
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x02;
     core.acc = 0x69;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 6));
@@ -95,8 +95,8 @@ fn sta_indirect_indexed() {
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
     core.idy = 0x04;
-    core.step();
-    let byte = core.get_bus().read(0x1337);
+    core.step().unwrap();
+    let byte = core.bus_mut().read(0x1337);
 
     assert_eq!(byte, 0x69);
     assert!(verify_clocks(&core, 6));
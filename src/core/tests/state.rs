@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+
+use crate::core::{Core, CpuState, Flags, Variant};
+
+use super::*;
+
+fn state() -> CpuState {
+    CpuState {
+        acc: 0x12,
+        idx: 0x34,
+        idy: 0x56,
+        sp: 0xFD,
+        pc: 0xC000,
+        status: Flags::from(0x24),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn round_trip() {
+    let bus = MockBus::new();
+    let mut core = Core::new(bus, vec![]).unwrap();
+    core.set_state(state());
+
+    assert_eq!(core.state(), state());
+    assert_eq!(core.acc, 0x12);
+    assert_eq!(core.pc, 0xC000);
+    assert!(core.status.interrupt());
+}
+
+#[test]
+fn display() {
+    assert_eq!(
+        state().to_string(),
+        "A:12 X:34 Y:56 P:24 SP:FD PC:C000 nv-bdIzc"
+    );
+}
+
+#[test]
+fn display_native() {
+    let mut status = Flags::new();
+    status.set_memory_8bit(false);
+    status.set_negative(true);
+    let state = CpuState {
+        acc_hi: 0x9A,
+        pbr: 0x01,
+        dp: 0x2000,
+        dbr: 0x7E,
+        emulation: false,
+        status,
+        ..state()
+    };
+
+    assert_eq!(
+        state.to_string(),
+        "A:9A12 X:0034 Y:0056 P:90 SP:01FD PC:01C000 D:2000 DB:7E NvmXdizc"
+    );
+}
+
+#[test]
+fn fixed_up() {
+    let bus = MockBus::new();
+    let mut core = Core::new(bus, vec![]).unwrap();
+    let mut status = Flags::new();
+    status.set_index_8bit(false);
+    core.set_state(CpuState {
+        idx_hi: 0x12,
+        sp_hi: 0x02,
+        emulation: false,
+        status,
+        ..state()
+    });
+
+    assert!(core.emulation_mode());
+    assert_eq!(core.idx_hi, 0x00);
+    assert_eq!(core.sp_hi, 0x01);
+    assert!(core.status.index_8bit());
+}
+
+#[test]
+fn native_kept_on_65c816() {
+    let bus = MockBus::new();
+    let mut core = Core::with_variant(bus, vec![], Variant::W65C816).unwrap();
+    let mut status = Flags::new();
+    status.set_index_8bit(false);
+    let state = CpuState {
+        idx_hi: 0x12,
+        sp_hi: 0x02,
+        emulation: false,
+        status,
+        ..state()
+    };
+    core.set_state(state);
+
+    assert_eq!(core.state(), state);
+}
+
+#[test]
+fn abandons_ticked_instruction() {
+    let bus = MockBus::new();
+    let program = vec![0xAD, 0x00, 0x20, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.tick().unwrap();
+    core.set_state(CpuState {
+        pc: 0x0003,
+        ..Default::default()
+    });
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0x01);
+    assert_eq!(core.pc, 0x0004);
+}
+
+#[test]
+fn hashable() {
+    let mut states = HashSet::new();
+    states.insert(state());
+    states.insert(state());
+    states.insert(CpuState::default());
+
+    assert_eq!(states.len(), 2);
+}
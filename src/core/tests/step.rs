@@ -0,0 +1,101 @@
+use crate::{
+    core::{Core, HaltReason, StepOutcome, Variant},
+    error::CoreError,
+};
+
+use super::*;
+
+#[test]
+fn outcome() {
+    let bus = MockBus::new();
+    let program = vec![0xEA, 0xBD, 0xFF, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x01;
+    core.step().unwrap();
+
+    assert_eq!(
+        core.step().unwrap(),
+        StepOutcome {
+            opcode: 0xBD,
+            address: 0x0001,
+            cycles: 5,
+            stolen: 0,
+            stalled: false
+        }
+    );
+}
+
+#[test]
+fn interrupt_outcome() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFE, 0x00);
+    bus.write(0xFFFF, 0x20);
+    let program = vec![0xEA, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_irq(true);
+    core.step().unwrap();
+
+    assert_eq!(
+        core.step().unwrap(),
+        StepOutcome {
+            opcode: 0x00,
+            address: 0x0001,
+            cycles: 7,
+            stolen: 0,
+            stalled: false
+        }
+    );
+}
+
+#[test]
+fn run_stops_at_stp() {
+    let bus = MockBus::new();
+    let program = vec![0xE8, 0xE8, 0xDB, 0xE8];
+    let mut core = Core::with_variant(bus, program, Variant::Cmos65C02).unwrap();
+
+    assert!(core.run().is_ok());
+    assert_eq!(core.idx, 0x02);
+    assert_eq!(core.halt_reason(), Some(HaltReason::Stopped));
+}
+
+#[test]
+fn run_reports_jam() {
+    let bus = MockBus::new();
+    let program = vec![0xE8, 0x12];
+    let mut core = Core::new(bus, program).unwrap();
+
+    assert!(matches!(
+        core.run(),
+        Err(CoreError::Jam {
+            opcode: 0x12,
+            address: 0x0001
+        })
+    ));
+    assert_eq!(core.halt_reason(), Some(HaltReason::Jammed));
+}
+
+#[test]
+fn halted_core_does_not_step() {
+    let bus = MockBus::new();
+    let program = vec![0x02, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    let _ = core.step();
+    core.bus_mut().write(0xc10c, 0);
+
+    assert!(matches!(
+        core.step(),
+        Err(CoreError::Halted(HaltReason::Jammed))
+    ));
+    assert!(verify_clocks(&core, 0));
+}
+
+#[test]
+fn reset_clears_halt() {
+    let bus = MockBus::new();
+    let program = vec![0x02];
+    let mut core = Core::new(bus, program).unwrap();
+    let _ = core.step();
+    core.reset();
+
+    assert_eq!(core.halt_reason(), None);
+}
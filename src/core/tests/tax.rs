@@ -8,7 +8,7 @@ fn tax() {
     let program = vec![0xaa];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idx, 0x69);
     assert!(verify_clocks(&core, 2));
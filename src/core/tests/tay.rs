@@ -8,7 +8,7 @@ fn tay() {
     let program = vec![0xA8];
     let mut core = Core::new(bus, program).unwrap();
     core.acc = 0x69;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idy, 0x69);
     assert!(verify_clocks(&core, 2));
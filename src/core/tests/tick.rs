@@ -0,0 +1,148 @@
+use crate::{
+    core::{Core, HaltReason, StepOutcome},
+    error::CoreError,
+};
+
+use super::*;
+
+#[test]
+fn one_access_per_tick() {
+    let mut bus = MockBus::new();
+    bus.write(0x2000, 0x42);
+    let program = vec![0xAD, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+
+    for cycle in 1..4 {
+        assert_eq!(core.tick().unwrap(), None);
+        assert!(verify_clocks(&core, cycle));
+        assert_eq!(core.acc, 0x00);
+    }
+    let outcome = core.tick().unwrap();
+
+    assert_eq!(
+        outcome,
+        Some(StepOutcome {
+            opcode: 0xAD,
+            address: 0x0000,
+            cycles: 4,
+            stolen: 0,
+            stalled: false
+        })
+    );
+
+    assert_eq!(core.acc, 0x42);
+    assert_eq!(core.pc, 0x0003);
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn progress_between_ticks() {
+    let mut bus = MockBus::new();
+    bus.write(0x2000, 0x42);
+    let program = vec![0xAD, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+
+    for pc in 1..4 {
+        core.tick().unwrap();
+        assert_eq!(core.pc, pc);
+    }
+}
+
+#[test]
+fn store_lands_on_its_cycle() {
+    let bus = MockBus::new();
+    let program = vec![0x8D, 0x00, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x69;
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x2000), 0x00);
+
+    core.tick().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x2000), 0x69);
+}
+
+#[test]
+fn step_finishes_started_instruction() {
+    let mut bus = MockBus::new();
+    bus.write(0x2000, 0x42);
+    let program = vec![0xAD, 0x00, 0x20, 0xE8];
+    let mut core = Core::new(bus, program).unwrap();
+    core.tick().unwrap();
+    core.tick().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x42);
+    assert!(verify_clocks(&core, 4));
+
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0x01);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn nmi_during_instruction() {
+    let bus = interrupt_bus();
+    let program = vec![0xEA, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    core.tick().unwrap();
+    core.set_nmi(true);
+
+    assert!(core.tick().unwrap().is_some());
+    assert_eq!(core.pc, 0x0001);
+
+    for _ in 0..6 {
+        assert!(core.tick().unwrap().is_none());
+    }
+    assert!(core.tick().unwrap().is_some());
+
+    assert_eq!(core.pc, 0x3000);
+}
+
+#[test]
+fn nmi_hijacks_brk() {
+    let bus = interrupt_bus();
+    let program = vec![0x00, 0x00];
+    let mut core = Core::new(bus, program).unwrap();
+    for _ in 0..3 {
+        core.tick().unwrap();
+    }
+    core.set_nmi(true);
+    while core.tick().unwrap().is_none() {}
+
+    assert_eq!(core.pc, 0x3000);
+}
+
+#[test]
+fn nmi_too_late_to_hijack_brk() {
+    let bus = interrupt_bus();
+    let program = vec![0x00, 0x00];
+    let mut core = Core::new(bus, program).unwrap();
+    for _ in 0..5 {
+        core.tick().unwrap();
+    }
+    core.set_nmi(true);
+    while core.tick().unwrap().is_none() {}
+
+    assert_eq!(core.pc, 0x2000);
+
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x3000);
+}
+
+#[test]
+fn halted_core_does_not_tick() {
+    let bus = MockBus::new();
+    let program = vec![0x02];
+    let mut core = Core::new(bus, program).unwrap();
+    assert!(core.step().is_err());
+    assert!(matches!(
+        core.tick(),
+        Err(CoreError::Halted(HaltReason::Jammed))
+    ));
+}
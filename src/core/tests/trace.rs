@@ -0,0 +1,185 @@
+use std::{cell::RefCell, io, rc::Rc};
+
+use crate::core::{Core, PowerOn, Tracer};
+
+use super::*;
+
+/// A buffer the test can still read after handing it to the tracer.
+#[derive(Clone, Default)]
+struct Log(Rc<RefCell<Vec<u8>>>);
+
+impl Log {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.borrow().clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl io::Write for Log {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Broken;
+
+impl io::Write for Broken {
+    fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+        Err(io::ErrorKind::BrokenPipe.into())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn trace(
+    bus: MockBus,
+    program: Vec<u8>,
+    steps: usize,
+    tracer: impl Fn(Log) -> Tracer,
+) -> Vec<String> {
+    let mut core = Core::new(bus, program).unwrap();
+    core.power_on(PowerOn::Zero);
+    let log = Log::default();
+    core.set_tracer(tracer(log.clone()));
+    for _ in 0..steps {
+        core.step().unwrap();
+    }
+    core.take_tracer().unwrap().finish().unwrap();
+    log.lines()
+}
+
+#[test]
+fn nestest_columns() {
+    let mut bus = MockBus::new();
+    bus.write(0x0015, 0x42);
+    // LDX #$05, LDA $10,X, STA $0200, JMP $0000
+    let program = vec![0xA2, 0x05, 0xB5, 0x10, 0x8D, 0x00, 0x02, 0x4C, 0x00, 0x00];
+    let lines = trace(bus, program, 4, Tracer::new);
+
+    assert_eq!(
+        lines,
+        [
+            "0000  A2 05     LDX #$05                        A:00 X:00 Y:00 P:24 SP:FD CYC:7",
+            "0002  B5 10     LDA $10,X @ 15 = 42             A:00 X:05 Y:00 P:24 SP:FD CYC:9",
+            "0004  8D 00 02  STA $0200 = 00                  A:42 X:05 Y:00 P:24 SP:FD CYC:13",
+            "0007  4C 00 00  JMP $0000                       A:42 X:05 Y:00 P:24 SP:FD CYC:17",
+        ]
+    );
+}
+
+#[test]
+fn resolves_indirect_operands() {
+    let mut bus = MockBus::new();
+    bus.write(0x0080, 0x00);
+    bus.write(0x0081, 0x03);
+    bus.write(0x0303, 0x5A);
+    bus.write(0x02FF, 0x20);
+    bus.write(0x0200, 0x00);
+    // LDX #$01, LDY #$03, LDA ($7F,X), LDA ($80),Y, *ISC $80, JMP ($02FF)
+    let program = vec![
+        0xA2, 0x01, 0xA0, 0x03, 0xA1, 0x7F, 0xB1, 0x80, 0xE7, 0x80, 0x6C, 0xFF, 0x02,
+    ];
+    let lines = trace(bus, program, 6, Tracer::new);
+
+    assert_eq!(
+        lines[2],
+        "0004  A1 7F     LDA ($7F,X) @ 80 = 0300 = 00    A:00 X:01 Y:03 P:24 SP:FD CYC:11"
+    );
+    assert_eq!(
+        lines[3],
+        "0006  B1 80     LDA ($80),Y = 0300 @ 0303 = 5A  A:00 X:01 Y:03 P:26 SP:FD CYC:17"
+    );
+    assert_eq!(
+        lines[4],
+        "0008  E7 80    *ISB $80 = 00                    A:5A X:01 Y:03 P:24 SP:FD CYC:22"
+    );
+    // the NMOS page wrap fetches the high byte from $0200
+    assert_eq!(
+        lines[5],
+        "000A  6C FF 02  JMP ($02FF) = 0020              A:58 X:01 Y:03 P:25 SP:FD CYC:27"
+    );
+}
+
+#[test]
+fn branch_targets() {
+    // BNE back to itself after LDX #$01
+    let program = vec![0xA2, 0x01, 0xD0, 0xFE];
+    let lines = trace(MockBus::new(), program, 2, Tracer::new);
+
+    assert!(lines[1].starts_with("0002  D0 FE     BNE $0002 "));
+}
+
+#[test]
+fn address_filter() {
+    let program = vec![0xEA, 0xEA, 0xEA, 0xEA];
+    let lines = trace(MockBus::new(), program, 4, |log| {
+        Tracer::new(log).addresses(0x0001..=0x0002)
+    });
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("0001  EA        NOP"));
+    assert!(lines[1].starts_with("0002  EA        NOP"));
+}
+
+#[test]
+fn cycle_filter() {
+    let program = vec![0xEA, 0xEA, 0xEA, 0xEA];
+    let lines = trace(MockBus::new(), program, 4, |log| {
+        Tracer::new(log).cycles(9..13)
+    });
+
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].ends_with("CYC:9"));
+    assert!(lines[1].ends_with("CYC:11"));
+}
+
+#[test]
+fn ppu_column() {
+    let program = vec![0xEA];
+    let lines = trace(MockBus::new(), program, 1, |log| {
+        Tracer::new(log).ppu_column()
+    });
+
+    assert!(lines[0].ends_with("SP:FD PPU:  0, 21 CYC:7"));
+}
+
+#[test]
+fn interrupts_are_not_traced() {
+    let mut bus = MockBus::new();
+    bus.write(0xFFFA, 0x00);
+    bus.write(0xFFFB, 0x30);
+    let program = vec![0xEA, 0xEA];
+    let mut core = Core::new(bus, program).unwrap();
+    let log = Log::default();
+    core.set_tracer(Tracer::new(log.clone()));
+    core.step().unwrap();
+    core.set_nmi(true);
+    core.step().unwrap();
+    core.step().unwrap();
+
+    let lines = log.lines();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("0000  EA "));
+    assert!(lines[1].starts_with("3000  00 "));
+}
+
+#[test]
+fn write_errors() {
+    let mut core = Core::new(MockBus::new(), vec![0xEA, 0xEA]).unwrap();
+    core.set_tracer(Tracer::new(Broken));
+    core.step().unwrap();
+    core.step().unwrap();
+
+    let result = core.take_tracer().unwrap().finish();
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+}
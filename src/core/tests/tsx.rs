@@ -8,7 +8,7 @@ fn tsx() {
     let program = vec![0xBA];
     let mut core = Core::new(bus, program).unwrap();
     core.sp = 0x69;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.idx, 0x69);
     assert!(verify_clocks(&core, 2));
@@ -8,7 +8,7 @@ fn txa() {
     let program = vec![0x8A];
     let mut core = Core::new(bus, program).unwrap();
     core.idx = 0x69;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 2));
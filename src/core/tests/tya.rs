@@ -8,7 +8,7 @@ fn tya() {
     let program = vec![0x98];
     let mut core = Core::new(bus, program).unwrap();
     core.idy = 0x69;
-    core.step();
+    core.step().unwrap();
 
     assert_eq!(core.acc, 0x69);
     assert!(verify_clocks(&core, 2));
@@ -0,0 +1,435 @@
+use crate::{
+    core::{Core, HaltReason, Variant},
+    error::CoreError,
+};
+
+use super::*;
+
+#[test]
+fn lax_zeropage() {
+    let mut bus = MockBus::new();
+    let program = vec![0xA7, 0x20];
+    bus.write(0x0020, 0x81);
+    let mut core = Core::new(bus, program).unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x81);
+    assert_eq!(core.idx, 0x81);
+    assert!(core.status.negative());
+    assert!(verify_clocks(&core, 3));
+}
+
+#[test]
+fn lax_absolute_y_page_crossed() {
+    let mut bus = MockBus::new();
+    let program = vec![0xBF, 0xff, 0x20];
+    bus.write(0x2100, 0x42);
+    let mut core = Core::new(bus, program).unwrap();
+    core.idy = 0x01;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x42);
+    assert_eq!(core.idx, 0x42);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn sax_zeropage_y() {
+    let bus = MockBus::new();
+    let program = vec![0x97, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0b1100_1100;
+    core.idx = 0b1010_1010;
+    core.idy = 0x05;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0025), 0b1000_1000);
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn slo_absolute_x() {
+    let mut bus = MockBus::new();
+    let program = vec![0x1F, 0x33, 0x13];
+    bus.write(0x1337, 0b1000_0001);
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x04;
+    core.acc = 0b0001_0000;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0b0000_0010);
+    assert_eq!(core.acc, 0b0001_0010);
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 7));
+}
+
+#[test]
+fn rla_zeropage() {
+    let mut bus = MockBus::new();
+    let program = vec![0x27, 0x20];
+    bus.write(0x0020, 0b0100_0000);
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_carry(true);
+    core.acc = 0xff;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0b1000_0001);
+    assert_eq!(core.acc, 0b1000_0001);
+    assert!(!core.status.carry());
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn sre_indexed_indirect() {
+    let mut bus = MockBus::new();
+    let program = vec![0x43, 0x20];
+    bus.write(0x0022, 0x37);
+    bus.write(0x0023, 0x13);
+    bus.write(0x1337, 0b0000_0011);
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x02;
+    core.acc = 0b0000_0011;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0b0000_0001);
+    assert_eq!(core.acc, 0b0000_0010);
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 8));
+}
+
+#[test]
+fn rra_indirect_indexed() {
+    let mut bus = MockBus::new();
+    let program = vec![0x73, 0x20];
+    bus.write(0x0020, 0x33);
+    bus.write(0x0021, 0x13);
+    bus.write(0x1337, 0x02);
+    let mut core = Core::new(bus, program).unwrap();
+    core.idy = 0x04;
+    core.acc = 0x10;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x01);
+    assert_eq!(core.acc, 0x11);
+    assert!(verify_clocks(&core, 8));
+}
+
+#[test]
+fn dcp_zeropage() {
+    let mut bus = MockBus::new();
+    let program = vec![0xC7, 0x20];
+    bus.write(0x0020, 0x43);
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x42;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0x42);
+    assert!(core.status.zero());
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn isc_absolute() {
+    let mut bus = MockBus::new();
+    let program = vec![0xEF, 0x37, 0x13];
+    bus.write(0x1337, 0x07);
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_carry(true);
+    core.acc = 0x10;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x08);
+    assert_eq!(core.acc, 0x08);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn anc() {
+    let bus = MockBus::new();
+    let program = vec![0x0B, 0xf0];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x81;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x80);
+    assert!(core.status.negative());
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn alr() {
+    let bus = MockBus::new();
+    let program = vec![0x4B, 0x03];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0xff;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x01);
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn arr() {
+    let bus = MockBus::new();
+    let program = vec![0x6B, 0xff];
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_carry(true);
+    core.acc = 0x80;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0xc0);
+    assert!(core.status.carry());
+    assert!(core.status.overflow());
+    assert!(core.status.negative());
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn sbx() {
+    let bus = MockBus::new();
+    let program = vec![0xCB, 0x02];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0x0f;
+    core.idx = 0xfc;
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0x0a);
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn sbc_immediate_alias() {
+    let bus = MockBus::new();
+    let program = vec![0xEB, 0x08];
+    let mut core = Core::new(bus, program).unwrap();
+    core.status.set_carry(true);
+    core.acc = 0x10;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x08);
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn xaa_uses_magic_constant() {
+    let bus = MockBus::new();
+    let program = vec![0x8B, 0xff, 0x8B, 0xff];
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x0f;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x0e);
+
+    core.set_magic_constant(0xff);
+    core.acc = 0x00;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x0f);
+}
+
+#[test]
+fn lxa_uses_magic_constant() {
+    let bus = MockBus::new();
+    let program = vec![0xAB, 0x3c];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_magic_constant(0x00);
+    core.acc = 0x37;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x34);
+    assert_eq!(core.idx, 0x34);
+}
+
+#[test]
+fn las() {
+    let mut bus = MockBus::new();
+    let program = vec![0xBB, 0x33, 0x13];
+    bus.write(0x1337, 0xf3);
+    let mut core = Core::new(bus, program).unwrap();
+    core.idy = 0x04;
+    core.sp = 0x3f;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x33);
+    assert_eq!(core.idx, 0x33);
+    assert_eq!(core.sp, 0x33);
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn las_uses_unstable_magic() {
+    let mut bus = MockBus::new();
+    let program = vec![0xBB, 0x33, 0x13];
+    bus.write(0x1337, 0xf3);
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_unstable_magic(Some(0x0f));
+    core.idy = 0x04;
+    core.sp = 0x3f;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x03);
+    assert_eq!(core.sp, 0x03);
+}
+
+#[test]
+fn shx_absolute_y() {
+    let bus = MockBus::new();
+    let program = vec![0x9E, 0x00, 0x13];
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0xff;
+    core.idy = 0x37;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0x14);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn shy_page_crossed_corrupts_address() {
+    let bus = MockBus::new();
+    let program = vec![0x9C, 0xff, 0x12];
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x01;
+    core.idy = 0x03;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0300), 0x03);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn shx_uses_unstable_magic() {
+    let bus = MockBus::new();
+    let program = vec![0x9E, 0x00, 0x13, 0x9E, 0xff, 0x12];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_unstable_magic(Some(0xff));
+    core.idx = 0xff;
+    core.idy = 0x37;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x1337), 0xff);
+
+    // the stored value still replaces the high byte on a page crossing
+    core.set_unstable_magic(Some(0x03));
+    core.idy = 0x01;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0300), 0x03);
+}
+
+#[test]
+fn tas() {
+    let bus = MockBus::new();
+    let program = vec![0x9B, 0x00, 0x13];
+    let mut core = Core::new(bus, program).unwrap();
+    core.acc = 0xf7;
+    core.idx = 0x7f;
+    core.idy = 0x37;
+    core.step().unwrap();
+
+    assert_eq!(core.sp, 0x77);
+    assert_eq!(core.bus_mut().read(0x1337), 0x14);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn multi_byte_nops() {
+    let bus = MockBus::new();
+    let program = vec![0x1A, 0x80, 0xff, 0x04, 0x20, 0x14, 0x20, 0x0C, 0x37, 0x13];
+    let mut core = Core::new(bus, program).unwrap();
+    let expected = [(0x01, 2), (0x03, 2), (0x05, 3), (0x07, 4), (0x0a, 4)];
+
+    for (pc, clocks) in expected {
+        core.bus_mut().write(0xc10c, 0);
+        core.step().unwrap();
+        assert_eq!(core.pc, pc);
+        assert!(verify_clocks(&core, clocks));
+    }
+}
+
+#[test]
+fn nop_absolute_x_page_crossed() {
+    let bus = MockBus::new();
+    let program = vec![0x1C, 0xff, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.idx = 0x01;
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x03);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn jam_halts() {
+    let bus = MockBus::new();
+    let program = vec![0x02];
+    let mut core = Core::new(bus, program).unwrap();
+
+    assert!(matches!(
+        core.step(),
+        Err(CoreError::Jam {
+            opcode: 0x02,
+            address: 0x0000
+        })
+    ));
+    assert_eq!(core.halt_reason(), Some(HaltReason::Jammed));
+}
+
+#[test]
+fn documented_opcode_halts() {
+    let bus = MockBus::new();
+    let mut core = Core::new(bus, vec![0xEA]).unwrap();
+    core.decode_undocumented(0xEA);
+
+    assert_eq!(core.halt_reason(), Some(HaltReason::IllegalOpcode));
+}
+
+#[test]
+fn disabled_undocumented_opcodes_halt() {
+    let bus = MockBus::new();
+    let program = vec![0xA7, 0x20];
+    let mut core = Core::new(bus, program).unwrap();
+    core.set_undocumented_opcodes(false);
+
+    assert!(matches!(
+        core.step(),
+        Err(CoreError::IllegalOpcode {
+            opcode: 0xA7,
+            address: 0x0000
+        })
+    ));
+    assert_eq!(core.halt_reason(), Some(HaltReason::IllegalOpcode));
+    assert_eq!(core.acc, 0x00);
+}
+
+#[test]
+fn ricoh_decodes_undocumented() {
+    let bus = MockBus::new();
+    let program = vec![0xA7, 0x20];
+    let mut core = Core::with_variant(bus, program, Variant::Ricoh2A03).unwrap();
+    core.bus_mut().write(0x0020, 0x69);
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert_eq!(core.idx, 0x69);
+    assert_eq!(core.halt_reason(), None);
+}
+
+#[test]
+fn ricoh_isc_is_binary() {
+    let bus = MockBus::new();
+    let program = vec![0xE7, 0x20];
+    let mut core = Core::with_variant(bus, program, Variant::Ricoh2A03).unwrap();
+    core.bus_mut().write(0x0020, 0x09);
+    core.status.set_decimal(true);
+    core.status.set_carry(true);
+    core.acc = 0x20;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0020), 0x0a);
+    assert_eq!(core.acc, 0x16);
+}
@@ -0,0 +1,447 @@
+use crate::core::{AccessKind, Core, Variant};
+
+use super::*;
+
+fn w65c816(bus: MockBus, program: Vec<u8>) -> Core<MockBus> {
+    Core::with_variant(bus, program, Variant::W65C816).unwrap()
+}
+
+/// CLC; XCE — switches the core into native mode.
+const NATIVE: [u8; 2] = [0x18, 0xFB];
+
+fn native(bus: MockBus, program: &[u8]) -> Core<MockBus> {
+    let mut core = w65c816(bus, [&NATIVE[..], program].concat());
+    core.step().unwrap();
+    core.step().unwrap();
+    core
+}
+
+#[test]
+fn powers_up_in_emulation_mode() {
+    let bus = MockBus::new();
+    let program = vec![0xA9, 0x42];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert!(core.emulation_mode());
+    assert_eq!(core.acc, 0x42);
+    assert!(verify_clocks(&core, 2));
+}
+
+#[test]
+fn emulation_stack_stays_in_page_one() {
+    let bus = MockBus::new();
+    let program = vec![0xA2, 0x00, 0x9A, 0x48];
+    let mut core = w65c816(bus, program);
+    core.acc = 0x69;
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0100), 0x69);
+    assert_eq!(core.sp, 0xff);
+    assert_eq!(core.sp_hi, 0x01);
+}
+
+#[test]
+fn emulation_pea_goes_below_page_one() {
+    let bus = MockBus::new();
+    let program = vec![0xF4, 0x34, 0x12];
+    let mut core = w65c816(bus, program);
+    core.sp = 0x00;
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x0100), 0x12);
+    assert_eq!(core.bus_mut().read(0x00ff), 0x34);
+    // S itself goes back to page 1
+    assert_eq!(core.sp, 0xfe);
+    assert_eq!(core.sp_hi, 0x01);
+}
+
+#[test]
+fn emulation_rtl_goes_above_page_one() {
+    let mut bus = MockBus::new();
+    bus.write(0x0200, 0x33);
+    bus.write(0x0201, 0x13);
+    bus.write(0x0202, 0x00);
+    let program = vec![0x6B];
+    let mut core = w65c816(bus, program);
+    core.sp = 0xff;
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1334);
+    assert_eq!(core.sp, 0x02);
+    assert_eq!(core.sp_hi, 0x01);
+}
+
+#[test]
+fn xce() {
+    let bus = MockBus::new();
+    let core = native(bus, &[]);
+
+    assert!(!core.emulation_mode());
+    // the old emulation bit ends up in carry
+    assert!(core.status.carry());
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn rep_lda_immediate_16bit() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xC2, 0x20, 0xA9, 0x34, 0x12]);
+    core.step().unwrap();
+
+    assert!(!core.status.memory_8bit());
+    assert!(verify_clocks(&core, 7));
+
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x34);
+    assert_eq!(core.acc_hi, 0x12);
+    assert!(verify_clocks(&core, 10));
+}
+
+#[test]
+fn rep_ignored_in_emulation_mode() {
+    let bus = MockBus::new();
+    let program = vec![0xC2, 0x30];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert!(core.status.memory_8bit());
+    assert!(core.status.index_8bit());
+}
+
+#[test]
+fn sep_index_clears_high_bytes() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xC2, 0x10, 0xA2, 0xcd, 0xab, 0xE2, 0x10]);
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.idx_hi, 0xab);
+
+    core.step().unwrap();
+
+    assert_eq!(core.idx, 0xcd);
+    assert_eq!(core.idx_hi, 0x00);
+}
+
+#[test]
+fn adc_16bit() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xC2, 0x20, 0x18, 0x69, 0x01, 0x00]);
+    core.set_c(0x00ff);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.c(), 0x0100);
+    assert!(!core.status.carry());
+    assert!(!core.status.negative());
+}
+
+#[test]
+fn adc_16bit_decimal() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xC2, 0x20, 0xF8, 0x18, 0x69, 0x01, 0x00]);
+    core.set_c(0x9999);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.c(), 0x0000);
+    assert!(core.status.carry());
+    assert!(core.status.zero());
+}
+
+#[test]
+fn sbc_16bit() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xC2, 0x20, 0x38, 0xE9, 0x01, 0x00]);
+    core.set_c(0x0000);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.c(), 0xffff);
+    assert!(!core.status.carry());
+    assert!(core.status.negative());
+}
+
+#[test]
+fn eight_bit_accumulator_preserves_b() {
+    let bus = MockBus::new();
+    let program = vec![0xA9, 0x42, 0xEB];
+    let mut core = w65c816(bus, program);
+    core.acc_hi = 0x69;
+    core.step().unwrap();
+
+    assert_eq!(core.acc_hi, 0x69);
+
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert_eq!(core.acc_hi, 0x42);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn lda_absolute_long() {
+    let mut bus = MockBus::new();
+    bus.write(0x1337, 0x69);
+    let program = vec![0xAF, 0x37, 0x13, 0x00];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 5));
+}
+
+#[test]
+fn lda_indirect_long() {
+    let mut bus = MockBus::new();
+    bus.write(0x0020, 0x37);
+    bus.write(0x0021, 0x13);
+    bus.write(0x0022, 0x00);
+    bus.write(0x1337, 0x69);
+    let program = vec![0xA7, 0x20];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn lda_direct_page() {
+    let mut bus = MockBus::new();
+    bus.write(0x1320, 0x69);
+    let program = vec![0xA5, 0x20];
+    let mut core = w65c816(bus, program);
+    core.dp = 0x1300;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 3));
+}
+
+#[test]
+fn direct_page_word_wraps_in_bank_0() {
+    let bus = MockBus::new();
+    // REP #$20; LDA $FF; LDA $FFFF
+    let mut core = native(bus, &[0xC2, 0x20, 0xA5, 0xFF, 0xAD, 0xFF, 0xFF]);
+    core.step().unwrap();
+    core.dp = 0xff00;
+    core.start_bus_trace();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    let addresses: Vec<u32> = core
+        .bus_trace()
+        .unwrap()
+        .cycles()
+        .iter()
+        .filter(|cycle| cycle.kind == AccessKind::Data)
+        .map(|cycle| cycle.address)
+        .collect();
+    // absolute addressing carries into the next bank instead
+    assert_eq!(addresses, vec![0x00ffff, 0x000000, 0x00ffff, 0x010000]);
+}
+
+#[test]
+fn lda_direct_page_unaligned() {
+    let mut bus = MockBus::new();
+    bus.write(0x1321, 0x69);
+    let program = vec![0xA5, 0x20];
+    let mut core = w65c816(bus, program);
+    core.dp = 0x1301;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn lda_stack_relative() {
+    let mut bus = MockBus::new();
+    bus.write(0x01f2, 0x69);
+    let program = vec![0xA3, 0x02];
+    let mut core = w65c816(bus, program);
+    core.sp = 0xf0;
+    core.step().unwrap();
+
+    assert_eq!(core.acc, 0x69);
+    assert!(verify_clocks(&core, 4));
+}
+
+#[test]
+fn mvn() {
+    let mut bus = MockBus::new();
+    bus.write(0x1000, 0x11);
+    bus.write(0x1001, 0x22);
+    bus.write(0x1002, 0x33);
+    // REP #$30; MVN $00,$00
+    let mut core = native(bus, &[0xC2, 0x30, 0x54, 0x00, 0x00]);
+    core.step().unwrap();
+    core.set_c(0x0002);
+    core.set_x(0x1000);
+    core.set_y(0x2000);
+
+    for _ in 0..3 {
+        core.step().unwrap();
+    }
+
+    assert_eq!(core.bus_mut().read(0x2000), 0x11);
+    assert_eq!(core.bus_mut().read(0x2001), 0x22);
+    assert_eq!(core.bus_mut().read(0x2002), 0x33);
+    assert_eq!(core.c(), 0xffff);
+    assert_eq!(core.x(), 0x1003);
+    assert_eq!(core.y(), 0x2003);
+    assert_eq!(core.pc, 0x0007);
+    assert!(verify_clocks(&core, 7 + 3 * 7));
+}
+
+#[test]
+fn jsl_rtl() {
+    let mut bus = MockBus::new();
+    bus.write(0x1337, 0x6B);
+    let program = vec![0x22, 0x37, 0x13, 0x00];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    assert_eq!(core.bus_mut().read(0x01ff), 0x00);
+    assert_eq!(core.bus_mut().read(0x01fe), 0x00);
+    assert_eq!(core.bus_mut().read(0x01fd), 0x03);
+    assert!(verify_clocks(&core, 8));
+
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x0004);
+    assert_eq!(core.sp, 0xff);
+    assert!(verify_clocks(&core, 14));
+}
+
+#[test]
+fn jsr_pushes_return_address_minus_one() {
+    let bus = MockBus::new();
+    let program = vec![0x20, 0x37, 0x13];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    assert_eq!(core.bus_mut().read(0x01fe), 0x02);
+    assert!(verify_clocks(&core, 6));
+}
+
+#[test]
+fn pea() {
+    let bus = MockBus::new();
+    let mut core = native(bus, &[0xF4, 0x37, 0x13]);
+    core.step().unwrap();
+
+    assert_eq!(core.bus_mut().read(0x01ff), 0x13);
+    assert_eq!(core.bus_mut().read(0x01fe), 0x37);
+    assert_eq!(core.s(), 0x01fd);
+    assert!(verify_clocks(&core, 9));
+}
+
+#[test]
+fn cop_native() {
+    let mut bus = MockBus::new();
+    bus.write(0xffe4, 0x37);
+    bus.write(0xffe5, 0x13);
+    let mut core = native(bus, &[0x02, 0x00]);
+    core.pbr = 0x00;
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x1337);
+    // PBR, PCH, PCL, P
+    assert_eq!(core.bus_mut().read(0x01ff), 0x00);
+    assert_eq!(core.bus_mut().read(0x01fe), 0x00);
+    assert_eq!(core.bus_mut().read(0x01fd), 0x04);
+    assert!(core.status.interrupt());
+    assert!(verify_clocks(&core, 12));
+}
+
+#[test]
+fn branch_backwards() {
+    let bus = MockBus::new();
+    let program = vec![0xEA, 0xEA, 0x80, 0xFC];
+    let mut core = w65c816(bus, program);
+    core.step().unwrap();
+    core.step().unwrap();
+    core.step().unwrap();
+
+    assert_eq!(core.pc, 0x0000);
+}
+
+#[test]
+fn only_stp_halts() {
+    for opcode in 0..=0xff_u8 {
+        let bus = MockBus::new();
+        let program = vec![opcode, 0x00, 0x00, 0x00];
+        let mut core = w65c816(bus, program);
+        core.step().unwrap();
+
+        assert_eq!(
+            core.halt_reason().is_some(),
+            opcode == 0xDB,
+            "opcode {opcode:#04x}"
+        );
+    }
+}
+
+fn rmw_cycles(core: &mut Core<MockBus>) -> Vec<(u32, u8, bool)> {
+    core.start_bus_trace();
+    core.step().unwrap();
+    core.stop_bus_trace()
+        .unwrap()
+        .cycles()
+        .iter()
+        .map(|cycle| (cycle.address, cycle.data, cycle.write))
+        .collect()
+}
+
+#[test]
+fn rmw_emulation_writes_old_value_back() {
+    let mut bus = MockBus::new();
+    bus.write(0x0010, 0x41);
+    let mut core = w65c816(bus, vec![0xE6, 0x10]);
+
+    assert_eq!(
+        rmw_cycles(&mut core),
+        [
+            (0x0000, 0xE6, false),
+            (0x0001, 0x10, false),
+            (0x0010, 0x41, false),
+            (0x0010, 0x41, true),
+            (0x0010, 0x42, true),
+        ]
+    );
+}
+
+#[test]
+fn rmw_native_idles_on_operand() {
+    let mut bus = MockBus::new();
+    bus.write(0x0010, 0xFF);
+    bus.write(0x0011, 0x12);
+    // REP #$20, INC $10
+    let mut core = native(bus, &[0xC2, 0x20, 0xE6, 0x10]);
+    core.step().unwrap();
+
+    assert_eq!(
+        rmw_cycles(&mut core),
+        [
+            (0x0004, 0xE6, false),
+            (0x0005, 0x10, false),
+            (0x0010, 0xFF, false),
+            (0x0011, 0x12, false),
+            (0x0010, 0xFF, false),
+            (0x0011, 0x13, true),
+            (0x0010, 0x00, true),
+        ]
+    );
+}
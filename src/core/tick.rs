@@ -0,0 +1,446 @@
+//! Cycle-by-cycle execution.
+//!
+//! Instructions are written as straight-line code that performs all of
+//! their bus accesses in one go, so they can't be suspended halfway. To
+//! advance a single cycle, [`Core::tick`] restores the registers from the
+//! start of the instruction and runs it again. The accesses that already
+//! happened on earlier ticks are answered from a log without touching the
+//! bus, the next one is performed for real, and anything after it is
+//! discarded. The instruction is finished on the tick where that real
+//! access turns out to be its last. Otherwise the registers are left as
+//! they were when the instruction reached its first discarded access, so
+//! the progress made so far can be inspected between ticks.
+//!
+//! Only the CPU itself is re-run this way. Everything that reaches
+//! outside of it, like bus accesses, the tracer and trap handlers,
+//! happens once.
+//!
+//! Interrupt lines are sampled on real cycles only, so the decisions that
+//! depend on them are logged as well and replayed with the value they had
+//! when the CPU first made them. Likewise, V set by the SO pin is logged
+//! with the access it arrived on.
+
+use crate::traits::Bus;
+
+use crate::error::StateError;
+
+use super::{
+    flags::Flags,
+    save::{Reader, Writer},
+    Core, CoreError, HaltReason, StepOutcome,
+};
+
+/// Everything an instruction can change inside the CPU.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Registers {
+    acc: u8,
+    idx: u8,
+    idy: u8,
+    sp: u8,
+    pc: u16,
+    acc_hi: u8,
+    idx_hi: u8,
+    idy_hi: u8,
+    sp_hi: u8,
+    dp: u16,
+    dbr: u8,
+    pbr: u8,
+    emulation: bool,
+    status: Flags,
+    halt: Option<HaltReason>,
+    waiting: bool,
+    jammed: Option<u8>,
+    irq_pending: bool,
+}
+
+impl Registers {
+    pub(super) fn save(&self, w: &mut Writer) {
+        w.u8(self.acc);
+        w.u8(self.idx);
+        w.u8(self.idy);
+        w.u8(self.sp);
+        w.u16(self.pc);
+        w.u8(self.acc_hi);
+        w.u8(self.idx_hi);
+        w.u8(self.idy_hi);
+        w.u8(self.sp_hi);
+        w.u16(self.dp);
+        w.u8(self.dbr);
+        w.u8(self.pbr);
+        w.bool(self.emulation);
+        w.flags(self.status);
+        w.halt(self.halt);
+        w.bool(self.waiting);
+        w.bool(self.jammed.is_some());
+        w.u8(self.jammed.unwrap_or_default());
+        w.bool(self.irq_pending);
+    }
+
+    pub(super) fn load(r: &mut Reader<'_>) -> Result<Self, StateError> {
+        Ok(Self {
+            acc: r.u8()?,
+            idx: r.u8()?,
+            idy: r.u8()?,
+            sp: r.u8()?,
+            pc: r.u16()?,
+            acc_hi: r.u8()?,
+            idx_hi: r.u8()?,
+            idy_hi: r.u8()?,
+            sp_hi: r.u8()?,
+            dp: r.u16()?,
+            dbr: r.u8()?,
+            pbr: r.u8()?,
+            emulation: r.bool()?,
+            status: r.flags()?,
+            halt: r.halt()?,
+            waiting: r.bool()?,
+            jammed: match (r.bool()?, r.u8()?) {
+                (true, opcode) => Some(opcode),
+                (false, _) => None,
+            },
+            irq_pending: r.bool()?,
+        })
+    }
+}
+
+/// Progress of the instruction that [`Core::tick`] or [`Core::step`] is
+/// working through.
+#[derive(Debug, Default, Clone)]
+pub(super) struct Replay {
+    /// Registers at the start of the instruction, while one is in progress.
+    start: Option<Registers>,
+    /// Set while a tick or a step is running an instruction.
+    active: bool,
+    /// Data of every access already performed, 0 for writes, and whether
+    /// SO set V on it.
+    accesses: Vec<(u8, bool)>,
+    /// SO set V since the last logged access.
+    overflow: bool,
+    decisions: Vec<bool>,
+    /// Accesses performed for real before the current tick or step.
+    done: usize,
+    /// How many accesses after those the current call may perform.
+    limit: usize,
+    /// The cycle and stolen cycle counters when the instruction started.
+    started: (u64, u64),
+    /// Set when RDY held the CPU during the current call.
+    stalled: bool,
+    /// The registers where the current call stopped running for real.
+    held: Option<Registers>,
+    cursor: usize,
+    decision_cursor: usize,
+}
+
+/// How to handle the next access of a re-run instruction.
+pub(super) enum Access {
+    /// The logged data, and whether SO set V on this cycle.
+    Replayed(u8, bool),
+    Real,
+    Discarded,
+}
+
+impl Replay {
+    /// Saves an instruction in progress between ticks. Everything else is
+    /// set up again by the next tick.
+    pub(super) fn save(&self, w: &mut Writer) {
+        let Some(start) = &self.start else {
+            w.bool(false);
+            return;
+        };
+        w.bool(true);
+        start.save(w);
+        w.u32(self.accesses.len() as u32);
+        for &(byte, overflow) in &self.accesses {
+            w.u8(byte);
+            w.bool(overflow);
+        }
+        w.bool(self.overflow);
+        w.u32(self.decisions.len() as u32);
+        for &decision in &self.decisions {
+            w.bool(decision);
+        }
+        w.u64(self.started.0);
+        w.u64(self.started.1);
+    }
+
+    pub(super) fn load(r: &mut Reader<'_>) -> Result<Self, StateError> {
+        if !r.bool()? {
+            return Ok(Self::default());
+        }
+        let start = Registers::load(r)?;
+        let accesses = (0..r.u32()?)
+            .map(|_| Ok((r.u8()?, r.bool()?)))
+            .collect::<Result<_, StateError>>()?;
+        let overflow = r.bool()?;
+        let decisions = (0..r.u32()?).map(|_| r.bool()).collect::<Result<_, _>>()?;
+        Ok(Self {
+            start: Some(start),
+            accesses,
+            overflow,
+            decisions,
+            started: (r.u64()?, r.u64()?),
+            ..Self::default()
+        })
+    }
+
+    pub(super) fn in_progress(&self) -> bool {
+        self.start.is_some()
+    }
+
+    /// The cycle counters from the start of the instruction.
+    pub(super) fn started(&self) -> (u64, u64) {
+        self.started
+    }
+
+    /// Whether RDY can hold the CPU. Accesses made outside of an
+    /// instruction, by the reset sequence or a trap handler, can't be
+    /// picked up again later, so they don't wait.
+    pub(super) fn can_stall(&self) -> bool {
+        self.active
+    }
+
+    /// Gives up the rest of the instruction after RDY stole a cycle. The
+    /// next tick or step starts over from the access that was held.
+    pub(super) fn stall(&mut self) {
+        self.stalled = true;
+    }
+
+    /// Whether the CPU is running for real, outside of an instruction or
+    /// after the logged accesses of one, as opposed to re-running what
+    /// happened on earlier ticks or running ahead with discarded data.
+    pub(super) fn live(&self) -> bool {
+        !self.active || (!self.stalled && self.cursor > self.done && self.cursor <= self.end())
+    }
+
+    /// The cursor position past the last access the current call may
+    /// perform for real.
+    fn end(&self) -> usize {
+        self.done.saturating_add(self.limit)
+    }
+
+    /// Forgets the finished instruction, keeping the logs' memory for the
+    /// next one.
+    fn finish(&mut self) {
+        self.start = None;
+        self.accesses.clear();
+        self.decisions.clear();
+        self.overflow = false;
+    }
+
+    /// Lets the rest of the instruction run for real, without logging.
+    /// Returns what to hand back to [`Replay::resume`].
+    pub(super) fn suspend(&mut self) -> bool {
+        std::mem::replace(&mut self.active, false)
+    }
+
+    pub(super) fn resume(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    pub(super) fn next_access(&mut self) -> Access {
+        if !self.active {
+            return Access::Real;
+        }
+
+        if self.stalled {
+            return Access::Discarded;
+        }
+
+        let index = self.cursor;
+        self.cursor += 1;
+        if index < self.done {
+            let (byte, overflow) = self.accesses[index];
+            Access::Replayed(byte, overflow)
+        } else if index < self.end() {
+            Access::Real
+        } else {
+            Access::Discarded
+        }
+    }
+
+    /// Logs a real access. Returns whether SO set V since the previous
+    /// one, which may have happened between ticks.
+    pub(super) fn record(&mut self, byte: u8) -> bool {
+        if !self.active {
+            return false;
+        }
+
+        let overflow = std::mem::take(&mut self.overflow);
+        self.accesses.push((byte, overflow));
+        overflow
+    }
+
+    /// Notes that SO set V. Outside of an instruction the flag is simply
+    /// kept, but in the middle of one the registers are about to be
+    /// restored, so it is logged with the next access.
+    pub(super) fn overflow_set(&mut self) {
+        if self.in_progress() {
+            self.overflow = true;
+        }
+    }
+
+    /// The logged outcome of the next decision, `None` if it has to be
+    /// made now.
+    fn next_decision(&mut self) -> Option<bool> {
+        if !self.active {
+            return None;
+        }
+
+        if let Some(&decision) = self.decisions.get(self.decision_cursor) {
+            self.decision_cursor += 1;
+            return Some(decision);
+        }
+
+        // past the real accesses, the lines haven't been sampled yet
+        if self.stalled || self.cursor > self.end() {
+            return Some(false);
+        }
+
+        None
+    }
+
+    fn record_decision(&mut self, decision: bool) {
+        if self.active {
+            self.decisions.push(decision);
+            self.decision_cursor += 1;
+        }
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Advances the CPU by exactly one clock cycle, performing one bus
+    /// access. Returns the outcome once the cycle finishes an instruction
+    /// or an interrupt sequence, and `None` while one is still in
+    /// progress. Errors are the same as for [`Core::step`].
+    ///
+    /// Between ticks the registers show the current instruction as far
+    /// as it got, such as the program counter moving past each operand
+    /// byte. An opcode handed to a [`Trap`][super::Trap] is the exception:
+    /// the handler runs once, so its accesses all happen on the tick
+    /// that fetches the opcode. [`Core::step`] and [`Core::run`] can be
+    /// mixed freely with `tick`.
+    pub fn tick(&mut self) -> Result<Option<StepOutcome>, CoreError> {
+        let (outcome, finished) = self.advance(1)?;
+        Ok(finished.then_some(outcome))
+    }
+
+    /// Runs the current instruction, performing at most `limit` accesses
+    /// beyond those of earlier calls. Returns what it executed so far,
+    /// and whether that finished it.
+    pub(super) fn advance(&mut self, limit: usize) -> Result<(StepOutcome, bool), CoreError> {
+        if let Some(reason) = self.halt {
+            return Err(CoreError::Halted(reason));
+        }
+
+        let start = match self.replay.start {
+            Some(start) => {
+                self.restore_registers(start);
+                start
+            }
+            None => {
+                self.replay_edges();
+                self.trace();
+                self.replay.started = (self.cycles, self.stolen);
+                self.save_registers()
+            }
+        };
+
+        let replay = &mut self.replay;
+        replay.start = Some(start);
+        replay.active = true;
+        replay.done = replay.accesses.len();
+        replay.limit = limit;
+        replay.cursor = 0;
+        replay.decision_cursor = 0;
+        replay.stalled = false;
+        replay.held = None;
+
+        let outcome = self.execute();
+
+        self.replay.active = false;
+        if self.replay.stalled || self.replay.cursor > self.replay.end() {
+            let held = self.replay.held.take().unwrap_or(start);
+            self.restore_registers(held);
+            return Ok((outcome, false));
+        }
+
+        self.replay.finish();
+        self.checkpoint();
+        self.sync_movie();
+        self.finish_step(outcome).map(|outcome| (outcome, true))
+    }
+
+    /// Notes the registers where a tick stops running for real, at the
+    /// first access it won't perform.
+    pub(super) fn hold(&mut self) {
+        if self.replay.held.is_none() {
+            self.replay.held = Some(self.save_registers());
+        }
+    }
+
+    /// Evaluates a condition on the interrupt lines. While replaying, the
+    /// answer comes from the log so that a line which changed on a later
+    /// cycle isn't seen too early.
+    pub(super) fn decide(&mut self, live: impl FnOnce(&mut Self) -> bool) -> bool {
+        if let Some(decision) = self.replay.next_decision() {
+            return decision;
+        }
+
+        let decision = live(self);
+        self.replay.record_decision(decision);
+        decision
+    }
+
+    /// Checks for a latched NMI and acknowledges it.
+    pub(super) fn take_nmi(&mut self) -> bool {
+        self.decide(|core| {
+            let pending = core.nmi_pending;
+            core.nmi_pending = false;
+            pending
+        })
+    }
+
+    pub(super) fn save_registers(&self) -> Registers {
+        Registers {
+            acc: self.acc,
+            idx: self.idx,
+            idy: self.idy,
+            sp: self.sp,
+            pc: self.pc,
+            acc_hi: self.acc_hi,
+            idx_hi: self.idx_hi,
+            idy_hi: self.idy_hi,
+            sp_hi: self.sp_hi,
+            dp: self.dp,
+            dbr: self.dbr,
+            pbr: self.pbr,
+            emulation: self.emulation,
+            status: self.status,
+            halt: self.halt,
+            waiting: self.waiting,
+            jammed: self.jammed,
+            irq_pending: self.irq_pending,
+        }
+    }
+
+    pub(super) fn restore_registers(&mut self, registers: Registers) {
+        self.acc = registers.acc;
+        self.idx = registers.idx;
+        self.idy = registers.idy;
+        self.sp = registers.sp;
+        self.pc = registers.pc;
+        self.acc_hi = registers.acc_hi;
+        self.idx_hi = registers.idx_hi;
+        self.idy_hi = registers.idy_hi;
+        self.sp_hi = registers.sp_hi;
+        self.dp = registers.dp;
+        self.dbr = registers.dbr;
+        self.pbr = registers.pbr;
+        self.emulation = registers.emulation;
+        self.status = registers.status;
+        self.halt = registers.halt;
+        self.waiting = registers.waiting;
+        self.jammed = registers.jammed;
+        self.irq_pending = registers.irq_pending;
+    }
+}
@@ -0,0 +1,428 @@
+//! Instruction traces in the format of nestest.log.
+//!
+//! The logs written by Nintendulator, and the nestest.log that comes with
+//! the nestest ROM, are what emulators traditionally diff against:
+//!
+//! ```text
+//! C000  4C F5 C5  JMP $C5F5                       A:00 X:00 Y:00 P:24 SP:FD CYC:7
+//! ```
+//!
+//! Each line is written just before the instruction runs: its address,
+//! its bytes, the disassembly with the operand resolved the way the CPU
+//! is about to resolve it, the registers and the cycle count. Opcodes
+//! that aren't documented are marked with `*`. Memory is read through
+//! [`Bus::peek`], so tracing never disturbs a device; whatever the bus
+//! can't peek shows up as `??`.
+//!
+//! On the 65C816, addresses outside bank 0 are printed with their bank,
+//! and in native mode the registers are printed at their full width.
+
+use std::{
+    fmt,
+    io::{self, Write},
+    ops::{Range, RangeInclusive},
+};
+
+use crate::traits::Bus;
+
+use super::{
+    addressing::{Mode, Offset},
+    Core, Instruction, Variant, MOS6507_ADDR_MASK,
+};
+
+/// Writes a line to any [`Write`] for every instruction the core runs,
+/// set with [`Core::set_tracer`].
+pub struct Tracer {
+    out: Box<dyn Write>,
+    addresses: RangeInclusive<u32>,
+    cycles: Range<u64>,
+    ppu: bool,
+    error: Option<io::Error>,
+}
+
+impl fmt::Debug for Tracer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tracer")
+            .field("addresses", &self.addresses)
+            .field("cycles", &self.cycles)
+            .field("ppu", &self.ppu)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Tracer {
+    /// Traces every instruction to `out`. Tracing writes a lot of small
+    /// lines, so files are best wrapped in a [`io::BufWriter`].
+    pub fn new(out: impl Write + 'static) -> Self {
+        Self {
+            out: Box::new(out),
+            addresses: 0..=u32::MAX,
+            cycles: 0..u64::MAX,
+            ppu: false,
+            error: None,
+        }
+    }
+
+    /// Only traces instructions whose opcode is fetched from `range`,
+    /// including the 65C816 program bank.
+    pub fn addresses(mut self, range: RangeInclusive<u32>) -> Self {
+        self.addresses = range;
+        self
+    }
+
+    /// Only traces instructions that start on a cycle in `window`, as
+    /// counted by [`Core::cycles`].
+    pub fn cycles(mut self, window: Range<u64>) -> Self {
+        self.cycles = window;
+        self
+    }
+
+    /// Adds the `PPU:` column of nestest.log with the scanline and dot.
+    /// The core knows nothing about the PPU, so they are worked out from
+    /// the cycle count at three dots per cycle, which only holds for an
+    /// NTSC NES with rendering off, as during nestest.
+    pub fn ppu_column(mut self) -> Self {
+        self.ppu = true;
+        self
+    }
+
+    /// Flushes the output. Returns the first error writing to it, after
+    /// which the tracer stopped writing.
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(error) => Err(error),
+            None => self.out.flush(),
+        }
+    }
+
+    fn wants(&self, addr: u32, cycle: u64) -> bool {
+        self.error.is_none() && self.addresses.contains(&addr) && self.cycles.contains(&cycle)
+    }
+
+    fn write(&mut self, line: &str) {
+        if let Err(error) = writeln!(self.out, "{line}") {
+            self.error = Some(error);
+        }
+    }
+}
+
+impl<B: Bus> Core<B> {
+    /// Starts tracing every instruction, replacing the tracer already
+    /// set, if any.
+    pub fn set_tracer(&mut self, tracer: Tracer) {
+        self.tracer = Some(tracer);
+    }
+
+    /// Stops tracing and hands the tracer back, to be
+    /// [finished][Tracer::finish].
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.tracer.take()
+    }
+
+    /// Traces the instruction about to start, unless an interrupt or a
+    /// WAI is going to take its place.
+    pub(super) fn trace(&mut self) {
+        let Some(tracer) = &self.tracer else {
+            return;
+        };
+        let pc = self.program_addr();
+        let busy = self.nmi_pending || self.irq_pending || self.waiting || self.jammed.is_some();
+        if busy || !tracer.wants(pc, self.cycles) {
+            return;
+        }
+
+        let line = self.trace_line(pc);
+        if let Some(tracer) = &mut self.tracer {
+            tracer.write(&line);
+        }
+    }
+
+    fn trace_line(&self, pc: u32) -> String {
+        let instruction = self
+            .peek(pc)
+            .map(|opcode| (opcode, &self.variant.instructions()[opcode as usize]));
+        let length = match instruction {
+            Some((opcode, instruction)) => self.length(opcode, instruction),
+            None => 1,
+        };
+
+        let bytes: Vec<_> = (0..length as u16)
+            .map(|offset| hex(self.peek(self.program_offset(pc, offset))))
+            .collect();
+        let (mark, text) = match instruction {
+            Some((_, instruction)) => {
+                let mark = if instruction.documented { ' ' } else { '*' };
+                let mnemonic = mnemonic(instruction);
+                let text = match self.operand(pc, instruction, length) {
+                    Some(operand) if operand.is_empty() => mnemonic.to_string(),
+                    Some(operand) => format!("{mnemonic} {operand}"),
+                    None => format!("{mnemonic} ???"),
+                };
+                (mark, text)
+            }
+            None => (' ', "???".to_string()),
+        };
+
+        let mut line = format!(
+            "{pc:04X}  {:<9}{mark}{text:<32}{}",
+            bytes.join(" "),
+            self.registers()
+        );
+        if self.tracer.as_ref().is_some_and(|tracer| tracer.ppu) {
+            let dot = self.cycles * 3;
+            line += &format!(" PPU:{:>3},{:>3}", dot / 341 % 262, dot % 341);
+        }
+        line + &format!(" CYC:{}", self.cycles)
+    }
+
+    fn registers(&self) -> String {
+        if self.emulation {
+            format!(
+                "A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X}",
+                self.acc,
+                self.idx,
+                self.idy,
+                self.state().status_byte(),
+                self.sp
+            )
+        } else {
+            format!(
+                "A:{:04X} X:{:04X} Y:{:04X} P:{:02X} SP:{:04X}",
+                self.c(),
+                self.x(),
+                self.y(),
+                self.state().status_byte(),
+                self.s()
+            )
+        }
+    }
+
+    /// The length of the instruction, taking into account the immediate
+    /// operands that grow with the 65C816 register widths.
+    fn length(&self, opcode: u8, instruction: &Instruction) -> u8 {
+        if self.variant != Variant::W65C816 || instruction.mode != Mode::Immediate {
+            return instruction.length;
+        }
+
+        match opcode {
+            0x09 | 0x29 | 0x49 | 0x69 | 0x89 | 0xa9 | 0xc9 | 0xe9 if !self.m8() => 3,
+            0xa0 | 0xa2 | 0xc0 | 0xe0 if !self.x8() => 3,
+            _ => instruction.length,
+        }
+    }
+
+    /// Disassembles the operand, resolving it against the registers and
+    /// memory as they are now. `None` if its bytes can't be peeked.
+    fn operand(&self, pc: u32, instruction: &Instruction, length: u8) -> Option<String> {
+        let bytes: Vec<u8> = (1..length as u16)
+            .map(|offset| self.peek(self.program_offset(pc, offset)))
+            .collect::<Option<_>>()?;
+        let byte = |index: usize| bytes.get(index - 1).copied().unwrap_or_default();
+        let op = byte(1);
+        let word = u16::from_le_bytes([op, byte(2)]);
+        let long = u32::from_le_bytes([op, byte(2), byte(3), 0]);
+
+        let bank = (self.dbr as u32) << 16;
+        let jump = matches!(instruction.mnemonic, "JMP" | "JSR" | "JML" | "JSL" | "PEA");
+        let operand = match instruction.mode {
+            Mode::Implied => String::new(),
+            Mode::Accumulator => "A".to_string(),
+            Mode::Immediate if length == 3 => format!("#${word:04X}"),
+            Mode::Immediate => format!("#${op:02X}"),
+            Mode::ZeroPage(Offset::None) => {
+                let addr = self.direct(op as u16);
+                format!("${op:02X} = {}", hex(self.peek(addr)))
+            }
+            Mode::ZeroPage(offset) => {
+                let (name, index) = self.index(offset);
+                let addr = self.direct((op as u16).wrapping_add(index));
+                format!("${op:02X},{name} @ {addr:02X} = {}", hex(self.peek(addr)))
+            }
+            Mode::Absolute(Offset::None) if jump => format!("${word:04X}"),
+            Mode::Absolute(Offset::None) => {
+                format!("${word:04X} = {}", hex(self.peek(bank | word as u32)))
+            }
+            Mode::Absolute(offset) => {
+                let (name, index) = self.index(offset);
+                let addr = self.data_offset(bank | word as u32, index);
+                format!("${word:04X},{name} @ {addr:04X} = {}", hex(self.peek(addr)))
+            }
+            Mode::Indirect => {
+                // the NMOS parts don't carry into the high byte of the
+                // pointer
+                let high = match self.variant.is_cmos() {
+                    true => word.wrapping_add(1),
+                    false => (word & 0xff00) | (word.wrapping_add(1) & 0x00ff),
+                };
+                let target = self.peek_pointer(word as u32, high as u32);
+                format!("(${word:04X}) = {}", hex_word(target))
+            }
+            Mode::IndexedIndirect => {
+                let offset = (op as u16).wrapping_add(self.x());
+                let (low, high) = (self.direct(offset), self.direct(offset.wrapping_add(1)));
+                let pointer = self.peek_pointer(low, high);
+                let value = pointer.and_then(|pointer| self.peek(bank | pointer as u32));
+                format!(
+                    "(${op:02X},X) @ {low:02X} = {} = {}",
+                    hex_word(pointer),
+                    hex(value)
+                )
+            }
+            Mode::IndirectIndexed => {
+                let (low, high) = (self.direct(op as u16), self.direct(op as u16 + 1));
+                let pointer = self.peek_pointer(low, high);
+                let addr = pointer.map(|pointer| self.data_offset(bank | pointer as u32, self.y()));
+                format!(
+                    "(${op:02X}),Y = {} @ {} = {}",
+                    hex_word(pointer),
+                    hex_addr(addr),
+                    hex(addr.and_then(|addr| self.peek(addr)))
+                )
+            }
+            Mode::ZeroPageIndirect => {
+                let (low, high) = (self.direct(op as u16), self.direct(op as u16 + 1));
+                let pointer = self.peek_pointer(low, high);
+                let value = pointer.and_then(|pointer| self.peek(bank | pointer as u32));
+                format!("(${op:02X}) = {} = {}", hex_word(pointer), hex(value))
+            }
+            Mode::AbsoluteIndexedIndirect => {
+                let low = word.wrapping_add(self.x());
+                let program_bank = pc & 0xff_0000;
+                let pointer = self.peek_pointer(
+                    program_bank | low as u32,
+                    program_bank | low.wrapping_add(1) as u32,
+                );
+                format!("(${word:04X},X) = {}", hex_word(pointer))
+            }
+            Mode::Relative => format!("${:04X}", branch(pc, 2, op as i8 as u16)),
+            Mode::RelativeLong => format!("${:04X}", branch(pc, 3, word)),
+            Mode::ZeroPageRelative => {
+                format!("${op:02X},${:04X}", branch(pc, 3, byte(2) as i8 as u16))
+            }
+            Mode::AbsoluteLong(Offset::None) if jump => format!("${long:06X}"),
+            Mode::AbsoluteLong(Offset::None) => {
+                format!("${long:06X} = {}", hex(self.peek(long)))
+            }
+            Mode::AbsoluteLong(offset) => {
+                let (name, index) = self.index(offset);
+                let addr = self.data_offset(long, index);
+                format!("${long:06X},{name} @ {addr:06X} = {}", hex(self.peek(addr)))
+            }
+            Mode::AbsoluteIndirectLong => {
+                let bytes = [0, 1, 2].map(|offset| self.peek(word.wrapping_add(offset) as u32));
+                let target = match bytes {
+                    [Some(low), Some(high), Some(bank)] => {
+                        format!("{:06X}", u32::from_le_bytes([low, high, bank, 0]))
+                    }
+                    _ => "??????".to_string(),
+                };
+                format!("[${word:04X}] = {target}")
+            }
+            Mode::IndirectLong | Mode::IndirectLongIndexed => {
+                let bytes = [0, 1, 2].map(|offset| self.peek(self.direct(op as u16 + offset)));
+                let pointer = match bytes {
+                    [Some(low), Some(high), Some(bank)] => {
+                        Some(u32::from_le_bytes([low, high, bank, 0]))
+                    }
+                    _ => None,
+                };
+                let pointer_text = pointer.map_or("??????".to_string(), |p| format!("{p:06X}"));
+                if instruction.mode == Mode::IndirectLong {
+                    let value = pointer.and_then(|pointer| self.peek(pointer));
+                    format!("[${op:02X}] = {pointer_text} = {}", hex(value))
+                } else {
+                    let addr = pointer.map(|pointer| self.data_offset(pointer, self.y()));
+                    format!(
+                        "[${op:02X}],Y = {pointer_text} @ {} = {}",
+                        hex_addr(addr),
+                        hex(addr.and_then(|addr| self.peek(addr)))
+                    )
+                }
+            }
+            Mode::StackRelative => {
+                let addr = self.s().wrapping_add(op as u16) as u32;
+                format!("${op:02X},S @ {addr:04X} = {}", hex(self.peek(addr)))
+            }
+            Mode::StackRelativeIndirectIndexed => {
+                let low = self.s().wrapping_add(op as u16);
+                let pointer = self.peek_pointer(low as u32, low.wrapping_add(1) as u32);
+                let addr = pointer.map(|pointer| self.data_offset(bank | pointer as u32, self.y()));
+                format!(
+                    "(${op:02X},S),Y = {} @ {} = {}",
+                    hex_word(pointer),
+                    hex_addr(addr),
+                    hex(addr.and_then(|addr| self.peek(addr)))
+                )
+            }
+            // the destination bank comes first in memory, but last in
+            // the assembler syntax
+            Mode::BlockMove => format!("${:02X},${op:02X}", byte(2)),
+        };
+        Some(operand)
+    }
+
+    fn index(&self, offset: Offset) -> (char, u16) {
+        match offset {
+            Offset::X => ('X', self.x()),
+            Offset::Y => ('Y', self.y()),
+            Offset::None => (' ', 0),
+        }
+    }
+
+    /// What a read of `addr` would return, as far as the bus can tell
+    /// without performing it.
+    fn peek(&self, addr: u32) -> Option<u8> {
+        let addr = match self.variant {
+            Variant::Mos6507 => addr & MOS6507_ADDR_MASK,
+            _ => addr,
+        };
+        if self.is_port(addr) {
+            return Some(self.peek_port(addr));
+        }
+        self.bus.peek(addr)
+    }
+
+    fn peek_pointer(&self, low: u32, high: u32) -> Option<u16> {
+        Some(u16::from_le_bytes([self.peek(low)?, self.peek(high)?]))
+    }
+
+    /// The address of an instruction byte. The program counter wraps
+    /// within its bank.
+    fn program_offset(&self, pc: u32, offset: u16) -> u32 {
+        (pc & 0xff_0000) | (pc as u16).wrapping_add(offset) as u32
+    }
+
+    /// Adds an index to a data address. Only the 65C816 carries into the
+    /// bank.
+    fn data_offset(&self, base: u32, index: u16) -> u32 {
+        match self.variant {
+            Variant::W65C816 => (base + index as u32) & 0xff_ffff,
+            _ => (base as u16).wrapping_add(index) as u32,
+        }
+    }
+}
+
+/// nestest.log spells ISC the other common way.
+fn mnemonic(instruction: &Instruction) -> &'static str {
+    match instruction.mnemonic {
+        "ISC" => "ISB",
+        mnemonic => mnemonic,
+    }
+}
+
+fn branch(pc: u32, length: u16, offset: u16) -> u16 {
+    (pc as u16).wrapping_add(length).wrapping_add(offset)
+}
+
+fn hex(byte: Option<u8>) -> String {
+    byte.map_or("??".to_string(), |byte| format!("{byte:02X}"))
+}
+
+fn hex_word(word: Option<u16>) -> String {
+    word.map_or("????".to_string(), |word| format!("{word:04X}"))
+}
+
+fn hex_addr(addr: Option<u32>) -> String {
+    addr.map_or("????".to_string(), |addr| format!("{addr:04X}"))
+}
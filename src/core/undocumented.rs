@@ -0,0 +1,334 @@
+//! The undocumented opcodes of the NMOS 6502.
+//!
+//! Most of these combine a read-modify-write instruction with an ALU
+//! instruction that shares its decode lines. The stable ones behave the
+//! same on every chip. XAA and LXA mix the accumulator with an analog,
+//! chip-dependent value, which is modelled by [`Core::set_magic_constant`].
+//! The SHA/SHX/SHY/TAS stores use the common `value & (H + 1)` behavior,
+//! including the high byte glitch when the indexing crosses a page, and
+//! LAS loads `M & SP`. Both can be given a fixed value to AND with
+//! instead through [`Core::set_unstable_magic`].
+
+use crate::traits::Bus;
+
+use super::{
+    addressing::{Mode, Offset},
+    Core, HaltReason,
+};
+
+impl<B: Bus> Core<B> {
+    pub(super) fn decode_undocumented(&mut self, byte: u8) {
+        match byte {
+            0x02 | 0x12 | 0x22 | 0x32 | 0x42 | 0x52 | 0x62 | 0x72 | 0x92 | 0xB2 | 0xD2 | 0xF2 => {
+                self.unknown_opcode(byte, HaltReason::Jammed)
+            }
+            0x03 => self.slo(Mode::IndexedIndirect),
+            0x04 | 0x44 | 0x64 => self.nop(Mode::ZeroPage(Offset::None)),
+            0x07 => self.slo(Mode::ZeroPage(Offset::None)),
+            0x0B | 0x2B => self.anc(),
+            0x0C => self.nop(Mode::Absolute(Offset::None)),
+            0x0F => self.slo(Mode::Absolute(Offset::None)),
+            0x13 => self.slo(Mode::IndirectIndexed),
+            0x14 | 0x34 | 0x54 | 0x74 | 0xD4 | 0xF4 => self.nop(Mode::ZeroPage(Offset::X)),
+            0x17 => self.slo(Mode::ZeroPage(Offset::X)),
+            0x1A | 0x3A | 0x5A | 0x7A | 0xDA | 0xFA => self.nop(Mode::Implied),
+            0x1B => self.slo(Mode::Absolute(Offset::Y)),
+            0x1C | 0x3C | 0x5C | 0x7C | 0xDC | 0xFC => self.nop(Mode::Absolute(Offset::X)),
+            0x1F => self.slo(Mode::Absolute(Offset::X)),
+            0x23 => self.rla(Mode::IndexedIndirect),
+            0x27 => self.rla(Mode::ZeroPage(Offset::None)),
+            0x2F => self.rla(Mode::Absolute(Offset::None)),
+            0x33 => self.rla(Mode::IndirectIndexed),
+            0x37 => self.rla(Mode::ZeroPage(Offset::X)),
+            0x3B => self.rla(Mode::Absolute(Offset::Y)),
+            0x3F => self.rla(Mode::Absolute(Offset::X)),
+            0x43 => self.sre(Mode::IndexedIndirect),
+            0x47 => self.sre(Mode::ZeroPage(Offset::None)),
+            0x4B => self.alr(),
+            0x4F => self.sre(Mode::Absolute(Offset::None)),
+            0x53 => self.sre(Mode::IndirectIndexed),
+            0x57 => self.sre(Mode::ZeroPage(Offset::X)),
+            0x5B => self.sre(Mode::Absolute(Offset::Y)),
+            0x5F => self.sre(Mode::Absolute(Offset::X)),
+            0x63 => self.rra(Mode::IndexedIndirect),
+            0x67 => self.rra(Mode::ZeroPage(Offset::None)),
+            0x6B => self.arr(),
+            0x6F => self.rra(Mode::Absolute(Offset::None)),
+            0x73 => self.rra(Mode::IndirectIndexed),
+            0x77 => self.rra(Mode::ZeroPage(Offset::X)),
+            0x7B => self.rra(Mode::Absolute(Offset::Y)),
+            0x7F => self.rra(Mode::Absolute(Offset::X)),
+            0x80 | 0x82 | 0x89 | 0xC2 | 0xE2 => self.nop(Mode::Immediate),
+            0x83 => self.sax(Mode::IndexedIndirect),
+            0x87 => self.sax(Mode::ZeroPage(Offset::None)),
+            0x8B => self.xaa(),
+            0x8F => self.sax(Mode::Absolute(Offset::None)),
+            0x93 => self.sha(Mode::IndirectIndexed),
+            0x97 => self.sax(Mode::ZeroPage(Offset::Y)),
+            0x9B => self.tas(),
+            0x9C => self.shy(),
+            0x9E => self.shx(),
+            0x9F => self.sha(Mode::Absolute(Offset::Y)),
+            0xA3 => self.lax(Mode::IndexedIndirect),
+            0xA7 => self.lax(Mode::ZeroPage(Offset::None)),
+            0xAB => self.lxa(),
+            0xAF => self.lax(Mode::Absolute(Offset::None)),
+            0xB3 => self.lax(Mode::IndirectIndexed),
+            0xB7 => self.lax(Mode::ZeroPage(Offset::Y)),
+            0xBB => self.las(),
+            0xBF => self.lax(Mode::Absolute(Offset::Y)),
+            0xC3 => self.dcp(Mode::IndexedIndirect),
+            0xC7 => self.dcp(Mode::ZeroPage(Offset::None)),
+            0xCB => self.sbx(),
+            0xCF => self.dcp(Mode::Absolute(Offset::None)),
+            0xD3 => self.dcp(Mode::IndirectIndexed),
+            0xD7 => self.dcp(Mode::ZeroPage(Offset::X)),
+            0xDB => self.dcp(Mode::Absolute(Offset::Y)),
+            0xDF => self.dcp(Mode::Absolute(Offset::X)),
+            0xE3 => self.isc(Mode::IndexedIndirect),
+            0xE7 => self.isc(Mode::ZeroPage(Offset::None)),
+            0xEB => self.sbc(Mode::Immediate),
+            0xEF => self.isc(Mode::Absolute(Offset::None)),
+            0xF3 => self.isc(Mode::IndirectIndexed),
+            0xF7 => self.isc(Mode::ZeroPage(Offset::X)),
+            0xFB => self.isc(Mode::Absolute(Offset::Y)),
+            0xFF => self.isc(Mode::Absolute(Offset::X)),
+            // the documented decoder only sends the opcodes it leaves
+            // unassigned, all of which are listed above
+            _ => self.halt(HaltReason::IllegalOpcode),
+        }
+    }
+
+    pub(super) fn nop(&mut self, mode: Mode) {
+        match mode {
+            Mode::Implied => self.dummy_read(self.pc),
+            Mode::Immediate => {
+                self.fetch();
+            }
+            Mode::ZeroPage(offset) => {
+                let addr = self.get_zeropage(offset);
+                self.read_bus(addr);
+            }
+            Mode::Absolute(offset) => {
+                let (addr, _) = self.get_absolute(offset);
+                self.read_bus(addr);
+            }
+            _ => self.invalid_mode(),
+        }
+    }
+
+    fn alr(&mut self) {
+        let byte = self.fetch();
+        self.acc &= byte;
+        self.status.set_carry(self.acc & 0x01 != 0);
+        self.acc >>= 1;
+        self.set_nz(self.acc);
+    }
+
+    fn anc(&mut self) {
+        let byte = self.fetch();
+        self.acc &= byte;
+        self.set_nz(self.acc);
+        self.status.set_carry(self.status.negative());
+    }
+
+    fn arr(&mut self) {
+        let byte = self.fetch();
+        let and = self.acc & byte;
+        let carry = self.status.carry() as u8;
+        self.acc = (and >> 1) | (carry << 7);
+
+        if !self.decimal_mode() {
+            self.set_nz(self.acc);
+            self.status.set_carry(self.acc & 0x40 != 0);
+            self.status
+                .set_overflow(((self.acc >> 6) ^ (self.acc >> 5)) & 0x01 != 0);
+            return;
+        }
+
+        // decimal mode fixes up each nybble of the rotated value
+        self.status.set_negative(carry != 0);
+        self.status.set_zero(self.acc == 0);
+        self.status.set_overflow((and ^ self.acc) & 0x40 != 0);
+
+        if (and & 0x0f) + (and & 0x01) > 0x05 {
+            self.acc = (self.acc & 0xf0) | (self.acc.wrapping_add(0x06) & 0x0f);
+        }
+
+        let high_fixup = (and as u16 & 0xf0) + (and as u16 & 0x10) > 0x50;
+        if high_fixup {
+            self.acc = self.acc.wrapping_add(0x60);
+        }
+        self.status.set_carry(high_fixup);
+    }
+
+    fn dcp(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = byte.wrapping_sub(1);
+        self.write_bus(addr, byte);
+
+        self.set_nz(self.acc.wrapping_sub(byte));
+        self.status.set_carry(self.acc >= byte);
+    }
+
+    fn isc(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = byte.wrapping_add(1);
+        self.write_bus(addr, byte);
+
+        if self.decimal_mode() {
+            self.sbc_decimal(byte);
+        } else {
+            self.add_with_carry(!byte);
+        }
+    }
+
+    fn las(&mut self) {
+        let (addr, _) = self.get_absolute(Offset::Y);
+        let byte = self.read_bus(addr) & self.sp & self.unstable_magic.unwrap_or(0xff);
+        self.acc = byte;
+        self.idx = byte;
+        self.sp = byte;
+        self.set_nz(byte);
+    }
+
+    fn lax(&mut self, mode: Mode) {
+        let addr = match mode {
+            Mode::ZeroPage(offset) => self.get_zeropage(offset),
+            Mode::Absolute(offset) => self.get_absolute(offset).0,
+            Mode::IndexedIndirect => self.get_indexed_indirect(),
+            Mode::IndirectIndexed => self.get_indirect_indexed().0,
+            _ => return self.invalid_mode(),
+        };
+
+        self.acc = self.read_bus(addr);
+        self.idx = self.acc;
+        self.set_nz(self.acc);
+    }
+
+    fn lxa(&mut self) {
+        let byte = self.fetch();
+        self.acc = (self.acc | self.magic) & byte;
+        self.idx = self.acc;
+        self.set_nz(self.acc);
+    }
+
+    fn rla(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let carry = self.status.carry() as u8;
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = self.shift_byte_left(byte) | carry;
+        self.write_bus(addr, byte);
+
+        self.acc &= byte;
+        self.set_nz(self.acc);
+    }
+
+    fn rra(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let carry = self.status.carry() as u8;
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = self.shift_byte_right(byte) | carry << 7;
+        self.write_bus(addr, byte);
+
+        if self.decimal_mode() {
+            self.adc_decimal(byte);
+        } else {
+            self.add_with_carry(byte);
+        }
+    }
+
+    fn sax(&mut self, mode: Mode) {
+        let addr = match mode {
+            Mode::ZeroPage(offset) => self.get_zeropage(offset),
+            Mode::Absolute(offset) => self.get_absolute(offset).0,
+            Mode::IndexedIndirect => self.get_indexed_indirect(),
+            _ => return self.invalid_mode(),
+        };
+
+        self.write_bus(addr, self.acc & self.idx);
+    }
+
+    fn sbx(&mut self) {
+        let byte = self.fetch();
+        let and = self.acc & self.idx;
+        self.idx = and.wrapping_sub(byte);
+        self.status.set_carry(and >= byte);
+        self.set_nz(self.idx);
+    }
+
+    fn sha(&mut self, mode: Mode) {
+        let value = self.acc & self.idx;
+        self.unstable_store(mode, value);
+    }
+
+    fn shx(&mut self) {
+        self.unstable_store(Mode::Absolute(Offset::Y), self.idx);
+    }
+
+    fn shy(&mut self) {
+        self.unstable_store(Mode::Absolute(Offset::X), self.idy);
+    }
+
+    fn slo(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = self.shift_byte_left(byte);
+        self.write_bus(addr, byte);
+
+        self.acc |= byte;
+        self.set_nz(self.acc);
+    }
+
+    fn sre(&mut self, mode: Mode) {
+        let addr = self.rmw_address(mode);
+        let byte = self.read_bus(addr);
+        self.modify_cycle(addr, byte);
+        let byte = self.shift_byte_right(byte);
+        self.write_bus(addr, byte);
+
+        self.acc ^= byte;
+        self.set_nz(self.acc);
+    }
+
+    fn tas(&mut self) {
+        self.sp = self.acc & self.idx;
+        self.unstable_store(Mode::Absolute(Offset::Y), self.sp);
+    }
+
+    fn xaa(&mut self) {
+        let byte = self.fetch();
+        self.acc = (self.acc | self.magic) & self.idx & byte;
+        self.set_nz(self.acc);
+    }
+
+    /// Stores `value & (H + 1)`, where H is the high byte of the base
+    /// address, or `value & magic` when an unstable magic value is set.
+    /// When the index crosses a page, the stored value also replaces the
+    /// high byte of the effective address.
+    fn unstable_store(&mut self, mode: Mode, value: u8) {
+        let (addr, crossed) = match mode {
+            Mode::Absolute(offset) => self.get_absolute_store(offset),
+            Mode::IndirectIndexed => self.get_indirect_indexed_store(),
+            _ => return self.invalid_mode(),
+        };
+
+        let (low, high) = self.bytes_from_addr(addr);
+        let base_high = if crossed { high.wrapping_sub(1) } else { high };
+        let value = value & self.unstable_magic.unwrap_or(base_high.wrapping_add(1));
+        let addr = if crossed {
+            self.addr_from_bytes(low, value)
+        } else {
+            addr
+        };
+
+        self.write_bus(addr, value);
+    }
+}
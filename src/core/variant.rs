@@ -0,0 +1,64 @@
+/// The CPU personality emulated by a [`Core`][super::Core].
+///
+/// The variant decides which opcodes are decoded and how the quirks
+/// that differ between chips in the family are handled.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Variant {
+    /// The original NMOS 6502.
+    #[default]
+    Nmos6502,
+    /// The WDC 65C02, including the Rockwell bit instructions and
+    /// WAI/STP. Decimal mode produces valid N/V/Z flags at the cost of
+    /// one extra cycle, and all unassigned opcodes are NOPs.
+    Cmos65C02,
+    /// The WDC 65C816. It powers up in emulation mode, which behaves like
+    /// a 65C02 without the Rockwell bit instructions, and can switch to
+    /// native mode with 16-bit registers and a 24-bit address bus.
+    W65C816,
+    /// The Ricoh 2A03 (NTSC) and 2A07 (PAL) used in the NES and Famicom.
+    /// The core is an NMOS 6502 with the decimal mode circuitry cut, so
+    /// SED and CLD still toggle the D flag but ADC and SBC always work
+    /// in binary. The undocumented opcodes behave as on the NMOS part.
+    Ricoh2A03,
+    /// The MOS 6510 used in the Commodore 64. An NMOS 6502 with an
+    /// on-chip I/O port mapped at $0000 (direction) and $0001 (data).
+    Mos6510,
+    /// The MOS 6507 used in the Atari 2600. An NMOS 6502 in a smaller
+    /// package with only 13 address lines, so every address is mirrored
+    /// every 8K, and no IRQ or NMI pins.
+    Mos6507,
+}
+
+impl Variant {
+    /// Whether the variant is built on the CMOS process, which fixes
+    /// most of the NMOS quirks.
+    pub fn is_cmos(self) -> bool {
+        matches!(self, Variant::Cmos65C02 | Variant::W65C816)
+    }
+
+    /// Whether the variant is built on the NMOS process, and so decodes
+    /// the undocumented opcodes.
+    pub fn is_nmos(self) -> bool {
+        matches!(
+            self,
+            Variant::Nmos6502 | Variant::Ricoh2A03 | Variant::Mos6510 | Variant::Mos6507
+        )
+    }
+
+    /// Whether the package has IRQ and NMI pins.
+    pub fn has_interrupts(self) -> bool {
+        self != Variant::Mos6507
+    }
+
+    /// Whether the package has the SO (set overflow) pin. The 6510 and
+    /// 6507 gave it up for other signals, and the 2A03 and 65C816 don't
+    /// have one.
+    pub fn has_so_pin(self) -> bool {
+        matches!(self, Variant::Nmos6502 | Variant::Cmos65C02)
+    }
+
+    /// Whether the D flag switches ADC and SBC into decimal mode.
+    pub fn has_decimal_mode(self) -> bool {
+        self != Variant::Ricoh2A03
+    }
+}
@@ -0,0 +1,1212 @@
+//! The 65C816 instruction set.
+//!
+//! The 65C816 reuses the 6502 opcode map but widens it: the accumulator
+//! and index registers can be 16 bits wide (selected by the M and X
+//! flags), zero page becomes a relocatable direct page, and addresses
+//! gain a data or program bank byte. In emulation mode the registers are
+//! forced to 8 bits and the stack is confined to page 1, so existing 6502
+//! code keeps working. The opcodes that are new on the 65C816, such as
+//! PEA, JSL and RTL, still address the stack with all of S there and can
+//! reach outside page 1 before S is put back in it.
+//!
+//! Since every instruction has to be aware of register width and banks,
+//! the 65C816 gets its own decoder instead of sharing the 6502 handlers.
+
+use crate::traits::Bus;
+
+use super::{
+    addressing::{Mode, Offset},
+    AccessKind, Core, IRQ_VECTOR, NMI_VECTOR,
+};
+
+const COP_VECTOR: u16 = 0xfff4;
+const NATIVE_COP_VECTOR: u16 = 0xffe4;
+const NATIVE_BRK_VECTOR: u16 = 0xffe6;
+const NATIVE_NMI_VECTOR: u16 = 0xffea;
+const NATIVE_IRQ_VECTOR: u16 = 0xffee;
+
+/// Mask for the 24-bit address bus.
+const ADDR_MASK: u32 = 0x00ff_ffff;
+
+impl<B: Bus> Core<B> {
+    pub(super) fn decode_816(&mut self, byte: u8) {
+        match byte {
+            0x00 => self.brk_816(),
+            0x02 => self.cop(),
+            0x04 => self.tsb_816(Mode::ZeroPage(Offset::None)),
+            0x06 => self.shift_816(byte, Mode::ZeroPage(Offset::None)),
+            0x08 => self.php_816(),
+            0x0A => self.shift_816(byte, Mode::Accumulator),
+            0x0B => self.phd(),
+            0x0C => self.tsb_816(Mode::Absolute(Offset::None)),
+            0x0E => self.shift_816(byte, Mode::Absolute(Offset::None)),
+            0x10 => self.branch_816(!self.status.negative()),
+            0x14 => self.trb_816(Mode::ZeroPage(Offset::None)),
+            0x16 => self.shift_816(byte, Mode::ZeroPage(Offset::X)),
+            0x18 => self.clc(),
+            0x1A => self.inc_816(Mode::Accumulator),
+            0x1B => self.tcs(),
+            0x1C => self.trb_816(Mode::Absolute(Offset::None)),
+            0x1E => self.shift_816(byte, Mode::Absolute(Offset::X)),
+            0x20 => self.jsr_816(Mode::Absolute(Offset::None)),
+            0x22 => self.jsl(),
+            0x24 => self.bit_816(Mode::ZeroPage(Offset::None)),
+            0x26 => self.shift_816(byte, Mode::ZeroPage(Offset::None)),
+            0x28 => self.plp_816(),
+            0x2A => self.shift_816(byte, Mode::Accumulator),
+            0x2B => self.pld(),
+            0x2C => self.bit_816(Mode::Absolute(Offset::None)),
+            0x2E => self.shift_816(byte, Mode::Absolute(Offset::None)),
+            0x30 => self.branch_816(self.status.negative()),
+            0x34 => self.bit_816(Mode::ZeroPage(Offset::X)),
+            0x36 => self.shift_816(byte, Mode::ZeroPage(Offset::X)),
+            0x38 => self.sec(),
+            0x3A => self.dec_816(Mode::Accumulator),
+            0x3B => self.tsc(),
+            0x3C => self.bit_816(Mode::Absolute(Offset::X)),
+            0x3E => self.shift_816(byte, Mode::Absolute(Offset::X)),
+            0x40 => self.rti_816(),
+            0x42 => self.wdm(),
+            0x44 => self.block_move(false),
+            0x46 => self.shift_816(byte, Mode::ZeroPage(Offset::None)),
+            0x48 => self.pha_816(),
+            0x4A => self.shift_816(byte, Mode::Accumulator),
+            0x4B => self.phk(),
+            0x4C => self.jmp_816(Mode::Absolute(Offset::None)),
+            0x4E => self.shift_816(byte, Mode::Absolute(Offset::None)),
+            0x50 => self.branch_816(!self.status.overflow()),
+            0x54 => self.block_move(true),
+            0x56 => self.shift_816(byte, Mode::ZeroPage(Offset::X)),
+            0x58 => self.cli(),
+            0x5A => self.phy_816(),
+            0x5B => self.tcd(),
+            0x5C => self.jmp_816(Mode::AbsoluteLong(Offset::None)),
+            0x5E => self.shift_816(byte, Mode::Absolute(Offset::X)),
+            0x60 => self.rts_816(),
+            0x62 => self.per(),
+            0x64 => self.stz_816(Mode::ZeroPage(Offset::None)),
+            0x66 => self.shift_816(byte, Mode::ZeroPage(Offset::None)),
+            0x68 => self.pla_816(),
+            0x6A => self.shift_816(byte, Mode::Accumulator),
+            0x6B => self.rtl(),
+            0x6C => self.jmp_816(Mode::Indirect),
+            0x6E => self.shift_816(byte, Mode::Absolute(Offset::None)),
+            0x70 => self.branch_816(self.status.overflow()),
+            0x74 => self.stz_816(Mode::ZeroPage(Offset::X)),
+            0x76 => self.shift_816(byte, Mode::ZeroPage(Offset::X)),
+            0x78 => self.sei(),
+            0x7A => self.ply_816(),
+            0x7B => self.tdc(),
+            0x7C => self.jmp_816(Mode::AbsoluteIndexedIndirect),
+            0x7E => self.shift_816(byte, Mode::Absolute(Offset::X)),
+            0x80 => self.branch_816(true),
+            0x82 => self.brl(),
+            0x84 => self.store_index(Mode::ZeroPage(Offset::None), false),
+            0x86 => self.store_index(Mode::ZeroPage(Offset::None), true),
+            0x88 => self.dey_816(),
+            0x89 => self.bit_816(Mode::Immediate),
+            0x8A => self.txa_816(),
+            0x8B => self.phb(),
+            0x8C => self.store_index(Mode::Absolute(Offset::None), false),
+            0x8E => self.store_index(Mode::Absolute(Offset::None), true),
+            0x90 => self.branch_816(!self.status.carry()),
+            0x94 => self.store_index(Mode::ZeroPage(Offset::X), false),
+            0x96 => self.store_index(Mode::ZeroPage(Offset::Y), true),
+            0x98 => self.tya_816(),
+            0x9A => self.txs_816(),
+            0x9B => self.txy(),
+            0x9C => self.stz_816(Mode::Absolute(Offset::None)),
+            0x9E => self.stz_816(Mode::Absolute(Offset::X)),
+            0xA0 => self.load_index(Mode::Immediate, false),
+            0xA2 => self.load_index(Mode::Immediate, true),
+            0xA4 => self.load_index(Mode::ZeroPage(Offset::None), false),
+            0xA6 => self.load_index(Mode::ZeroPage(Offset::None), true),
+            0xA8 => self.tay_816(),
+            0xAA => self.tax_816(),
+            0xAB => self.plb(),
+            0xAC => self.load_index(Mode::Absolute(Offset::None), false),
+            0xAE => self.load_index(Mode::Absolute(Offset::None), true),
+            0xB0 => self.branch_816(self.status.carry()),
+            0xB4 => self.load_index(Mode::ZeroPage(Offset::X), false),
+            0xB6 => self.load_index(Mode::ZeroPage(Offset::Y), true),
+            0xB8 => self.clv(),
+            0xBA => self.tsx_816(),
+            0xBB => self.tyx(),
+            0xBC => self.load_index(Mode::Absolute(Offset::X), false),
+            0xBE => self.load_index(Mode::Absolute(Offset::Y), true),
+            0xC0 => self.compare_index(Mode::Immediate, false),
+            0xC2 => self.rep(),
+            0xC4 => self.compare_index(Mode::ZeroPage(Offset::None), false),
+            0xC6 => self.dec_816(Mode::ZeroPage(Offset::None)),
+            0xC8 => self.iny_816(),
+            0xCA => self.dex_816(),
+            0xCB => self.wai(),
+            0xCC => self.compare_index(Mode::Absolute(Offset::None), false),
+            0xCE => self.dec_816(Mode::Absolute(Offset::None)),
+            0xD0 => self.branch_816(!self.status.zero()),
+            0xD4 => self.pei(),
+            0xD6 => self.dec_816(Mode::ZeroPage(Offset::X)),
+            0xD8 => self.cld(),
+            0xDA => self.phx_816(),
+            0xDB => self.stp(),
+            0xDC => self.jmp_816(Mode::AbsoluteIndirectLong),
+            0xDE => self.dec_816(Mode::Absolute(Offset::X)),
+            0xE0 => self.compare_index(Mode::Immediate, true),
+            0xE2 => self.sep(),
+            0xE4 => self.compare_index(Mode::ZeroPage(Offset::None), true),
+            0xE6 => self.inc_816(Mode::ZeroPage(Offset::None)),
+            0xE8 => self.inx_816(),
+            0xEA => self.idle(), // NOP
+            0xEB => self.xba(),
+            0xEC => self.compare_index(Mode::Absolute(Offset::None), true),
+            0xEE => self.inc_816(Mode::Absolute(Offset::None)),
+            0xF0 => self.branch_816(self.status.zero()),
+            0xF4 => self.pea(),
+            0xF6 => self.inc_816(Mode::ZeroPage(Offset::X)),
+            0xF8 => self.sed(),
+            0xFA => self.plx_816(),
+            0xFB => self.xce(),
+            0xFC => self.jsr_816(Mode::AbsoluteIndexedIndirect),
+            0xFE => self.inc_816(Mode::Absolute(Offset::X)),
+            // everything left is one of the eight accumulator instructions,
+            // with the addressing mode encoded in the low five bits
+            _ => {
+                let mode = match byte & 0x1F {
+                    0x01 => Mode::IndexedIndirect,
+                    0x03 => Mode::StackRelative,
+                    0x05 => Mode::ZeroPage(Offset::None),
+                    0x07 => Mode::IndirectLong,
+                    0x09 => Mode::Immediate,
+                    0x0D => Mode::Absolute(Offset::None),
+                    0x0F => Mode::AbsoluteLong(Offset::None),
+                    0x11 => Mode::IndirectIndexed,
+                    0x12 => Mode::ZeroPageIndirect,
+                    0x13 => Mode::StackRelativeIndirectIndexed,
+                    0x15 => Mode::ZeroPage(Offset::X),
+                    0x17 => Mode::IndirectLongIndexed,
+                    0x19 => Mode::Absolute(Offset::Y),
+                    0x1D => Mode::Absolute(Offset::X),
+                    0x1F => Mode::AbsoluteLong(Offset::X),
+                    _ => unreachable!("unassigned 65C816 opcode {byte:#04x}"),
+                };
+                self.accumulator_op(byte >> 5, mode);
+            }
+        }
+    }
+}
+
+// registers
+impl<B: Bus> Core<B> {
+    pub(super) fn m8(&self) -> bool {
+        self.emulation || self.status.memory_8bit()
+    }
+
+    pub(super) fn x8(&self) -> bool {
+        self.emulation || self.status.index_8bit()
+    }
+
+    /// The full 16-bit accumulator, regardless of the M flag.
+    pub(super) fn c(&self) -> u16 {
+        u16::from_le_bytes([self.acc, self.acc_hi])
+    }
+
+    pub(super) fn set_c(&mut self, value: u16) {
+        [self.acc, self.acc_hi] = value.to_le_bytes();
+    }
+
+    fn a(&self) -> u16 {
+        if self.m8() {
+            self.acc as u16
+        } else {
+            self.c()
+        }
+    }
+
+    /// Sets the accumulator. In 8-bit mode the hidden B register is untouched.
+    fn set_a(&mut self, value: u16) {
+        if self.m8() {
+            self.acc = value as u8;
+        } else {
+            self.set_c(value);
+        }
+    }
+
+    pub(super) fn x(&self) -> u16 {
+        u16::from_le_bytes([self.idx, self.idx_hi])
+    }
+
+    pub(super) fn set_x(&mut self, value: u16) {
+        self.idx = value as u8;
+        self.idx_hi = if self.x8() { 0 } else { (value >> 8) as u8 };
+    }
+
+    pub(super) fn y(&self) -> u16 {
+        u16::from_le_bytes([self.idy, self.idy_hi])
+    }
+
+    pub(super) fn set_y(&mut self, value: u16) {
+        self.idy = value as u8;
+        self.idy_hi = if self.x8() { 0 } else { (value >> 8) as u8 };
+    }
+
+    pub(super) fn s(&self) -> u16 {
+        u16::from_le_bytes([self.sp, self.sp_hi])
+    }
+
+    /// Sets the stack pointer. In emulation mode it stays in page 1.
+    fn set_s(&mut self, value: u16) {
+        self.sp = value as u8;
+        self.sp_hi = if self.emulation {
+            0x01
+        } else {
+            (value >> 8) as u8
+        };
+    }
+
+    fn set_nz_width(&mut self, value: u16, wide: bool) {
+        if wide {
+            self.status.set_zero(value == 0);
+            self.status.set_negative(value & 0x8000 != 0);
+        } else {
+            self.set_nz(value as u8);
+        }
+    }
+
+    /// Loads the native status byte and applies the side effects of the
+    /// M and X flags. Emulation mode keeps both flags set.
+    fn set_status_816(&mut self, byte: u8) {
+        if self.emulation {
+            self.status.set_from_stack(byte);
+            return;
+        }
+
+        self.status.set_native_byte(byte);
+        if self.status.index_8bit() {
+            self.idx_hi = 0;
+            self.idy_hi = 0;
+        }
+    }
+}
+
+// memory and stack
+impl<B: Bus> Core<B> {
+    /// An internal operation cycle. The 65C816 marks these as invalid
+    /// addresses, but the bus still sees the program counter.
+    fn idle(&mut self) {
+        self.read_as(AccessKind::Dummy, self.program_addr());
+    }
+
+    fn fetch_word(&mut self) -> u16 {
+        let low = self.fetch();
+        let high = self.fetch();
+        self.addr_from_bytes(low, high)
+    }
+
+    fn read_word(&mut self, mode: Mode, addr: u32, wide: bool) -> u16 {
+        let low = self.read_long(addr);
+        if !wide {
+            return low as u16;
+        }
+        let high = self.read_long(high_byte(mode, addr));
+        u16::from_le_bytes([low, high])
+    }
+
+    fn write_word(&mut self, mode: Mode, addr: u32, value: u16, wide: bool) {
+        let [low, high] = value.to_le_bytes();
+        self.write_long(addr, low);
+        if wide {
+            self.write_long(high_byte(mode, addr), high);
+        }
+    }
+
+    fn push_816(&mut self, byte: u8) {
+        let s = self.s();
+        self.write_as(AccessKind::Stack, s as u32, byte);
+        self.set_s(s.wrapping_sub(1));
+    }
+
+    fn pull_816(&mut self) -> u8 {
+        self.set_s(self.s().wrapping_add(1));
+        self.read_as(AccessKind::Stack, self.s() as u32)
+    }
+
+    fn push_word(&mut self, value: u16) {
+        let [low, high] = value.to_le_bytes();
+        self.push_816(high);
+        self.push_816(low);
+    }
+
+    fn pull_word(&mut self) -> u16 {
+        let low = self.pull_816();
+        let high = self.pull_816();
+        u16::from_le_bytes([low, high])
+    }
+
+    /// Pushes a register that is `wide` bits wide, high byte first.
+    fn push_width(&mut self, value: u16, wide: bool) {
+        if wide {
+            self.push_word(value);
+        } else {
+            self.push_816(value as u8);
+        }
+    }
+
+    fn pull_width(&mut self, wide: bool) -> u16 {
+        if wide {
+            self.pull_word()
+        } else {
+            self.pull_816() as u16
+        }
+    }
+
+    /// Pushes with all 16 bits of S. The opcodes the 65C816 added do so
+    /// even in emulation mode, which lets them reach below page 1; they
+    /// put S back in page 1 with `set_s` once they are done.
+    fn push_unbounded(&mut self, byte: u8) {
+        let s = self.s();
+        self.write_as(AccessKind::Stack, s as u32, byte);
+        [self.sp, self.sp_hi] = s.wrapping_sub(1).to_le_bytes();
+    }
+
+    fn pull_unbounded(&mut self) -> u8 {
+        let s = self.s().wrapping_add(1);
+        [self.sp, self.sp_hi] = s.to_le_bytes();
+        self.read_as(AccessKind::Stack, s as u32)
+    }
+
+    fn push_word_unbounded(&mut self, value: u16) {
+        let [low, high] = value.to_le_bytes();
+        self.push_unbounded(high);
+        self.push_unbounded(low);
+    }
+
+    fn pull_word_unbounded(&mut self) -> u16 {
+        let low = self.pull_unbounded();
+        let high = self.pull_unbounded();
+        u16::from_le_bytes([low, high])
+    }
+}
+
+/// The address of the high byte of a word operand. Direct page and stack
+/// relative operands wrap within bank 0; the others carry into the next
+/// bank.
+fn high_byte(mode: Mode, addr: u32) -> u32 {
+    match mode {
+        Mode::ZeroPage(_) | Mode::StackRelative => (addr as u16).wrapping_add(1) as u32,
+        _ => (addr + 1) & ADDR_MASK,
+    }
+}
+
+// addressing
+impl<B: Bus> Core<B> {
+    /// An address within the direct page. In emulation mode with the low
+    /// byte of D clear, the direct page wraps like the 6502 zero page.
+    pub(super) fn direct(&self, offset: u16) -> u32 {
+        if self.emulation && self.dp & 0xff == 0 {
+            (self.dp | (offset & 0xff)) as u32
+        } else {
+            self.dp.wrapping_add(offset) as u32
+        }
+    }
+
+    /// Fetches a direct page operand, spending the extra cycle taken
+    /// when D isn't page-aligned.
+    fn fetch_direct(&mut self) -> u8 {
+        let byte = self.fetch();
+        if self.dp & 0xff != 0 {
+            self.idle();
+        }
+        byte
+    }
+
+    fn read_pointer(&mut self, offset: u16, long: bool) -> u32 {
+        let low = self.read_long(self.direct(offset));
+        let high = self.read_long(self.direct(offset.wrapping_add(1)));
+        let bank = if long {
+            self.read_long(self.direct(offset.wrapping_add(2)))
+        } else {
+            self.dbr
+        };
+        u32::from_le_bytes([low, high, bank, 0])
+    }
+
+    /// Adds an index to a 24-bit base address, spending the extra cycle
+    /// for a page crossing, a 16-bit index or a write.
+    fn index_address(&mut self, base: u32, index: u16, write: bool) -> u32 {
+        let addr = (base + index as u32) & ADDR_MASK;
+        if write || !self.x8() || (base & 0xff00) != (addr & 0xff00) {
+            self.idle();
+        }
+        addr
+    }
+
+    /// Resolves the effective 24-bit address for a data access.
+    fn address_816(&mut self, mode: Mode, write: bool) -> u32 {
+        match mode {
+            Mode::ZeroPage(offset) => {
+                let byte = self.fetch_direct() as u16;
+                let index = match offset {
+                    Offset::None => return self.direct(byte),
+                    Offset::X => self.x(),
+                    Offset::Y => self.y(),
+                };
+                self.idle();
+                self.direct(byte.wrapping_add(index))
+            }
+            Mode::Absolute(offset) => {
+                let base = ((self.dbr as u32) << 16) | self.fetch_word() as u32;
+                match offset {
+                    Offset::None => base,
+                    Offset::X => self.index_address(base, self.x(), write),
+                    Offset::Y => self.index_address(base, self.y(), write),
+                }
+            }
+            Mode::AbsoluteLong(offset) => {
+                let low = self.fetch();
+                let high = self.fetch();
+                let bank = self.fetch();
+                let base = u32::from_le_bytes([low, high, bank, 0]);
+                match offset {
+                    Offset::X => (base + self.x() as u32) & ADDR_MASK,
+                    _ => base,
+                }
+            }
+            Mode::IndexedIndirect => {
+                let byte = self.fetch_direct() as u16;
+                self.idle();
+                self.read_pointer(byte.wrapping_add(self.x()), false)
+            }
+            Mode::IndirectIndexed => {
+                let byte = self.fetch_direct() as u16;
+                let base = self.read_pointer(byte, false);
+                self.index_address(base, self.y(), write)
+            }
+            Mode::ZeroPageIndirect => {
+                let byte = self.fetch_direct() as u16;
+                self.read_pointer(byte, false)
+            }
+            Mode::IndirectLong => {
+                let byte = self.fetch_direct() as u16;
+                self.read_pointer(byte, true)
+            }
+            Mode::IndirectLongIndexed => {
+                let byte = self.fetch_direct() as u16;
+                let base = self.read_pointer(byte, true);
+                (base + self.y() as u32) & ADDR_MASK
+            }
+            Mode::StackRelative => {
+                let byte = self.fetch() as u16;
+                self.idle();
+                self.s().wrapping_add(byte) as u32
+            }
+            Mode::StackRelativeIndirectIndexed => {
+                let byte = self.fetch() as u16;
+                self.idle();
+                let ptr = self.s().wrapping_add(byte);
+                let low = self.read_long(ptr as u32);
+                let high = self.read_long(ptr.wrapping_add(1) as u32);
+                self.idle();
+                let base = u32::from_le_bytes([low, high, self.dbr, 0]);
+                (base + self.y() as u32) & ADDR_MASK
+            }
+            _ => self.invalid_mode(),
+        }
+    }
+
+    /// Reads an operand of the given width, from the instruction stream
+    /// for immediate mode and from memory otherwise.
+    fn operand_816(&mut self, mode: Mode, wide: bool) -> u16 {
+        if mode == Mode::Immediate {
+            return if wide {
+                self.fetch_word()
+            } else {
+                self.fetch() as u16
+            };
+        }
+
+        let addr = self.address_816(mode, false);
+        self.read_word(mode, addr, wide)
+    }
+
+    /// Runs a read-modify-write cycle on memory or the accumulator.
+    fn modify_816(&mut self, mode: Mode, op: impl FnOnce(&mut Self, u16, bool) -> u16) {
+        let wide = !self.m8();
+        if mode == Mode::Accumulator {
+            self.idle();
+            let value = op(self, self.a(), wide);
+            self.set_a(value);
+            return;
+        }
+
+        let addr = self.address_816(mode, true);
+        let value = self.read_word(mode, addr, wide);
+        // like the NMOS parts, emulation mode writes the unmodified value
+        // back; native mode spends an internal cycle on the operand
+        if self.emulation {
+            self.write_as(AccessKind::Dummy, addr, value as u8);
+        } else {
+            self.read_as(AccessKind::Dummy, addr);
+        }
+        let value = op(self, value, wide);
+
+        // 16-bit read-modify-write stores the high byte first
+        let [low, high] = value.to_le_bytes();
+        if wide {
+            self.write_long(high_byte(mode, addr), high);
+        }
+        self.write_long(addr, low);
+    }
+}
+
+// arithmetic
+impl<B: Bus> Core<B> {
+    fn accumulator_op(&mut self, op: u8, mode: Mode) {
+        let wide = !self.m8();
+        if op == 4 {
+            let addr = self.address_816(mode, true);
+            self.write_word(mode, addr, self.a(), wide);
+            return;
+        }
+
+        let value = self.operand_816(mode, wide);
+        match op {
+            0 => self.set_a(self.a() | value),
+            1 => self.set_a(self.a() & value),
+            2 => self.set_a(self.a() ^ value),
+            3 => self.add_816(value, false),
+            5 => self.set_a(value),
+            6 => self.compare_816(self.a(), value, wide),
+            7 => self.add_816(value, true),
+            _ => unreachable!(),
+        }
+
+        if op != 6 {
+            self.set_nz_width(self.a(), wide);
+        }
+    }
+
+    fn add_816(&mut self, value: u16, subtract: bool) {
+        let wide = !self.m8();
+        let (mask, sign) = if wide {
+            (0xffff_u32, 0x8000)
+        } else {
+            (0x00ff_u32, 0x0080)
+        };
+        let acc = self.a() as u32;
+        let carry = self.status.carry() as u32;
+        let operand = if subtract {
+            !(value as u32) & mask
+        } else {
+            value as u32
+        };
+
+        let sum = acc + operand + carry;
+        let binary = sum & mask;
+        let mut overflow = (!(acc ^ operand) & (acc ^ binary) & sign) != 0;
+        let (res, carry) = if !self.status.decimal() {
+            (binary, sum > mask)
+        } else if subtract {
+            self.decimal_sub(acc, value as u32, carry, wide)
+        } else {
+            let (res, carry, unadjusted) = self.decimal_add(acc, operand, carry, wide);
+            overflow = (!(acc ^ operand) & (acc ^ unadjusted) & sign) != 0;
+            (res, carry)
+        };
+
+        self.set_a(res as u16);
+        self.status.set_carry(carry);
+        self.status.set_overflow(overflow);
+    }
+
+    /// Decimal addition one nybble at a time. Also returns the result
+    /// before the top digit was adjusted, which is where V comes from.
+    fn decimal_add(&self, acc: u32, value: u32, carry: u32, wide: bool) -> (u32, bool, u32) {
+        let digits = if wide { 4 } else { 2 };
+        let mut carry = carry;
+        let mut res = 0;
+        let mut unadjusted = 0;
+
+        for digit in 0..digits {
+            let shift = digit * 4;
+            let mut sum = ((acc >> shift) & 0x0f) + ((value >> shift) & 0x0f) + carry;
+            unadjusted = res | (sum << shift);
+            if sum > 0x09 {
+                sum += 0x06;
+            }
+            carry = (sum > 0x0f) as u32;
+            res |= (sum & 0x0f) << shift;
+        }
+
+        (res, carry != 0, unadjusted)
+    }
+
+    fn decimal_sub(&self, acc: u32, value: u32, carry: u32, wide: bool) -> (u32, bool) {
+        let digits = if wide { 4 } else { 2 };
+        let mut borrow = 1 - carry as i32;
+        let mut res = 0;
+
+        for digit in 0..digits {
+            let shift = digit * 4;
+            let mut diff =
+                ((acc >> shift) & 0x0f) as i32 - ((value >> shift) & 0x0f) as i32 - borrow;
+            borrow = (diff < 0) as i32;
+            if diff < 0 {
+                diff += 0x0a;
+            }
+            res |= ((diff as u32) & 0x0f) << shift;
+        }
+
+        (res, borrow == 0)
+    }
+
+    fn compare_816(&mut self, register: u16, value: u16, wide: bool) {
+        self.set_nz_width(register.wrapping_sub(value), wide);
+        self.status.set_carry(register >= value);
+    }
+
+    fn compare_index(&mut self, mode: Mode, x: bool) {
+        let wide = !self.x8();
+        let value = self.operand_816(mode, wide);
+        let register = if x { self.x() } else { self.y() };
+        self.compare_816(register, value, wide);
+    }
+
+    fn bit_816(&mut self, mode: Mode) {
+        let wide = !self.m8();
+        let value = self.operand_816(mode, wide);
+        self.status.set_zero(value & self.a() == 0);
+
+        // the immediate form only affects Z
+        if mode != Mode::Immediate {
+            let sign = if wide { 0x8000 } else { 0x80 };
+            self.status.set_negative(value & sign != 0);
+            self.status.set_overflow(value & (sign >> 1) != 0);
+        }
+    }
+
+    /// ASL, ROL, LSR and ROR, selected by bits 5 and 6 of the opcode.
+    fn shift_816(&mut self, byte: u8, mode: Mode) {
+        self.modify_816(mode, |core, value, wide| {
+            let sign = if wide { 0x8000 } else { 0x80 };
+            let carry = core.status.carry() as u16;
+            let res = match (byte >> 5) & 0x03 {
+                0 => value << 1,
+                1 => (value << 1) | carry,
+                2 => value >> 1,
+                _ => (value >> 1) | if carry != 0 { sign } else { 0 },
+            };
+            let carry_out = if byte & 0x40 == 0 {
+                value & sign != 0
+            } else {
+                value & 0x01 != 0
+            };
+            let res = if wide { res } else { res & 0xff };
+            core.status.set_carry(carry_out);
+            core.set_nz_width(res, wide);
+            res
+        });
+    }
+
+    fn inc_816(&mut self, mode: Mode) {
+        self.modify_816(mode, |core, value, wide| {
+            let res = if wide {
+                value.wrapping_add(1)
+            } else {
+                (value as u8).wrapping_add(1) as u16
+            };
+            core.set_nz_width(res, wide);
+            res
+        });
+    }
+
+    fn dec_816(&mut self, mode: Mode) {
+        self.modify_816(mode, |core, value, wide| {
+            let res = if wide {
+                value.wrapping_sub(1)
+            } else {
+                (value as u8).wrapping_sub(1) as u16
+            };
+            core.set_nz_width(res, wide);
+            res
+        });
+    }
+
+    fn tsb_816(&mut self, mode: Mode) {
+        self.modify_816(mode, |core, value, _| {
+            core.status.set_zero(value & core.a() == 0);
+            value | core.a()
+        });
+    }
+
+    fn trb_816(&mut self, mode: Mode) {
+        self.modify_816(mode, |core, value, _| {
+            core.status.set_zero(value & core.a() == 0);
+            value & !core.a()
+        });
+    }
+}
+
+// loads, stores and transfers
+impl<B: Bus> Core<B> {
+    fn load_index(&mut self, mode: Mode, x: bool) {
+        let wide = !self.x8();
+        let value = self.operand_816(mode, wide);
+        if x {
+            self.set_x(value);
+        } else {
+            self.set_y(value);
+        }
+        self.set_nz_width(value, wide);
+    }
+
+    fn store_index(&mut self, mode: Mode, x: bool) {
+        let value = if x { self.x() } else { self.y() };
+        let addr = self.address_816(mode, true);
+        self.write_word(mode, addr, value, !self.x8());
+    }
+
+    fn stz_816(&mut self, mode: Mode) {
+        let addr = self.address_816(mode, true);
+        self.write_word(mode, addr, 0, !self.m8());
+    }
+
+    fn inx_816(&mut self) {
+        self.idle();
+        let value = self.x().wrapping_add(1);
+        self.set_x(value);
+        self.set_nz_width(self.x(), !self.x8());
+    }
+
+    fn iny_816(&mut self) {
+        self.idle();
+        let value = self.y().wrapping_add(1);
+        self.set_y(value);
+        self.set_nz_width(self.y(), !self.x8());
+    }
+
+    fn dex_816(&mut self) {
+        self.idle();
+        let value = self.x().wrapping_sub(1);
+        self.set_x(value);
+        self.set_nz_width(self.x(), !self.x8());
+    }
+
+    fn dey_816(&mut self) {
+        self.idle();
+        let value = self.y().wrapping_sub(1);
+        self.set_y(value);
+        self.set_nz_width(self.y(), !self.x8());
+    }
+
+    fn tax_816(&mut self) {
+        self.idle();
+        self.set_x(self.c());
+        self.set_nz_width(self.x(), !self.x8());
+    }
+
+    fn tay_816(&mut self) {
+        self.idle();
+        self.set_y(self.c());
+        self.set_nz_width(self.y(), !self.x8());
+    }
+
+    fn txa_816(&mut self) {
+        self.idle();
+        self.set_a(self.x());
+        self.set_nz_width(self.a(), !self.m8());
+    }
+
+    fn tya_816(&mut self) {
+        self.idle();
+        self.set_a(self.y());
+        self.set_nz_width(self.a(), !self.m8());
+    }
+
+    fn tsx_816(&mut self) {
+        self.idle();
+        self.set_x(self.s());
+        self.set_nz_width(self.x(), !self.x8());
+    }
+
+    fn txs_816(&mut self) {
+        self.idle();
+        self.set_s(self.x());
+    }
+
+    fn txy(&mut self) {
+        self.idle();
+        self.set_y(self.x());
+        self.set_nz_width(self.y(), !self.x8());
+    }
+
+    fn tyx(&mut self) {
+        self.idle();
+        self.set_x(self.y());
+        self.set_nz_width(self.x(), !self.x8());
+    }
+
+    fn tcd(&mut self) {
+        self.idle();
+        self.dp = self.c();
+        self.set_nz_width(self.dp, true);
+    }
+
+    fn tdc(&mut self) {
+        self.idle();
+        self.set_c(self.dp);
+        self.set_nz_width(self.dp, true);
+    }
+
+    fn tcs(&mut self) {
+        self.idle();
+        self.set_s(self.c());
+    }
+
+    fn tsc(&mut self) {
+        self.idle();
+        self.set_c(self.s());
+        self.set_nz_width(self.c(), true);
+    }
+
+    fn xba(&mut self) {
+        self.idle();
+        self.idle();
+        std::mem::swap(&mut self.acc, &mut self.acc_hi);
+        self.set_nz(self.acc);
+    }
+
+    fn xce(&mut self) {
+        self.idle();
+        let carry = self.status.carry();
+        self.status.set_carry(self.emulation);
+        self.emulation = carry;
+
+        if self.emulation {
+            self.status.set_memory_8bit(true);
+            self.status.set_index_8bit(true);
+            self.idx_hi = 0;
+            self.idy_hi = 0;
+            self.sp_hi = 0x01;
+        }
+    }
+
+    fn rep(&mut self) {
+        let mask = self.fetch();
+        self.idle();
+        let byte = self.status.native_byte() & !mask;
+        self.set_status_816(byte);
+    }
+
+    fn sep(&mut self) {
+        let mask = self.fetch();
+        self.idle();
+        let byte = self.status.native_byte() | mask;
+        self.set_status_816(byte);
+    }
+
+    /// MVN and MVP move one byte per execution and rewind the program
+    /// counter until the 16-bit accumulator underflows, so interrupts can
+    /// be taken in the middle of a block move.
+    fn block_move(&mut self, increment: bool) {
+        let dst = self.fetch();
+        let src = self.fetch();
+        self.dbr = dst;
+
+        let byte = self.read_long(((src as u32) << 16) | self.x() as u32);
+        self.write_long(((dst as u32) << 16) | self.y() as u32, byte);
+        self.idle();
+        self.idle();
+
+        let (x, y) = if increment {
+            (self.x().wrapping_add(1), self.y().wrapping_add(1))
+        } else {
+            (self.x().wrapping_sub(1), self.y().wrapping_sub(1))
+        };
+        self.set_x(x);
+        self.set_y(y);
+
+        let count = self.c().wrapping_sub(1);
+        self.set_c(count);
+        if count != 0xffff {
+            self.pc = self.pc.wrapping_sub(3);
+        }
+    }
+
+    fn wdm(&mut self) {
+        // reserved for future expansion, behaves as a two byte NOP
+        self.fetch();
+    }
+}
+
+// stack
+impl<B: Bus> Core<B> {
+    fn pha_816(&mut self) {
+        self.idle();
+        self.push_width(self.a(), !self.m8());
+    }
+
+    fn phx_816(&mut self) {
+        self.idle();
+        self.push_width(self.x(), !self.x8());
+    }
+
+    fn phy_816(&mut self) {
+        self.idle();
+        self.push_width(self.y(), !self.x8());
+    }
+
+    fn pla_816(&mut self) {
+        self.idle();
+        self.idle();
+        let value = self.pull_width(!self.m8());
+        self.set_a(value);
+        self.set_nz_width(value, !self.m8());
+    }
+
+    fn plx_816(&mut self) {
+        self.idle();
+        self.idle();
+        let value = self.pull_width(!self.x8());
+        self.set_x(value);
+        self.set_nz_width(value, !self.x8());
+    }
+
+    fn ply_816(&mut self) {
+        self.idle();
+        self.idle();
+        let value = self.pull_width(!self.x8());
+        self.set_y(value);
+        self.set_nz_width(value, !self.x8());
+    }
+
+    fn php_816(&mut self) {
+        self.idle();
+        let byte = if self.emulation {
+            self.status.stack_byte(true)
+        } else {
+            self.status.native_byte()
+        };
+        self.push_816(byte);
+    }
+
+    fn plp_816(&mut self) {
+        self.idle();
+        self.idle();
+        let byte = self.pull_816();
+        self.set_status_816(byte);
+    }
+
+    fn phb(&mut self) {
+        self.idle();
+        self.push_816(self.dbr);
+    }
+
+    fn plb(&mut self) {
+        self.idle();
+        self.idle();
+        self.dbr = self.pull_816();
+        self.set_nz(self.dbr);
+    }
+
+    fn phd(&mut self) {
+        self.idle();
+        self.push_word_unbounded(self.dp);
+        self.set_s(self.s());
+    }
+
+    fn pld(&mut self) {
+        self.idle();
+        self.idle();
+        self.dp = self.pull_word_unbounded();
+        self.set_s(self.s());
+        self.set_nz_width(self.dp, true);
+    }
+
+    fn phk(&mut self) {
+        self.idle();
+        self.push_816(self.pbr);
+    }
+
+    fn pea(&mut self) {
+        let value = self.fetch_word();
+        self.push_word_unbounded(value);
+        self.set_s(self.s());
+    }
+
+    fn pei(&mut self) {
+        let byte = self.fetch_direct() as u16;
+        let low = self.read_long(self.direct(byte));
+        let high = self.read_long(self.direct(byte.wrapping_add(1)));
+        self.push_word_unbounded(u16::from_le_bytes([low, high]));
+        self.set_s(self.s());
+    }
+
+    fn per(&mut self) {
+        let offset = self.fetch_word();
+        self.idle();
+        self.push_word_unbounded(self.pc.wrapping_add(offset));
+        self.set_s(self.s());
+    }
+}
+
+// control flow
+impl<B: Bus> Core<B> {
+    fn branch_816(&mut self, condition: bool) {
+        let offset = self.fetch() as i8;
+        if !condition {
+            return;
+        }
+
+        self.idle();
+        let target = self.pc.wrapping_add(offset as u16);
+        // only emulation mode pays for crossing a page
+        if self.emulation && (target & 0xff00) != (self.pc & 0xff00) {
+            self.idle();
+        }
+        self.pc = target;
+    }
+
+    fn brl(&mut self) {
+        let offset = self.fetch_word();
+        self.idle();
+        self.pc = self.pc.wrapping_add(offset);
+    }
+
+    fn jmp_816(&mut self, mode: Mode) {
+        match mode {
+            Mode::Absolute(_) => self.pc = self.fetch_word(),
+            Mode::AbsoluteLong(_) => {
+                let addr = self.fetch_word();
+                self.pbr = self.fetch();
+                self.pc = addr;
+            }
+            Mode::Indirect => {
+                let ptr = self.fetch_word();
+                let low = self.read_long(ptr as u32);
+                let high = self.read_long(ptr.wrapping_add(1) as u32);
+                self.pc = u16::from_le_bytes([low, high]);
+            }
+            Mode::AbsoluteIndexedIndirect => {
+                let ptr = self.fetch_word().wrapping_add(self.x());
+                self.idle();
+                self.pc = self.read_program_word(ptr);
+            }
+            Mode::AbsoluteIndirectLong => {
+                let ptr = self.fetch_word();
+                let low = self.read_long(ptr as u32);
+                let high = self.read_long(ptr.wrapping_add(1) as u32);
+                self.pbr = self.read_long(ptr.wrapping_add(2) as u32);
+                self.pc = u16::from_le_bytes([low, high]);
+            }
+            _ => self.invalid_mode(),
+        }
+    }
+
+    /// Reads a pointer out of the program bank, used by the `(abs,X)` jumps.
+    fn read_program_word(&mut self, ptr: u16) -> u16 {
+        let bank = (self.pbr as u32) << 16;
+        let low = self.read_long(bank | ptr as u32);
+        let high = self.read_long(bank | ptr.wrapping_add(1) as u32);
+        u16::from_le_bytes([low, high])
+    }
+
+    fn jsr_816(&mut self, mode: Mode) {
+        match mode {
+            Mode::Absolute(_) => {
+                let addr = self.fetch_word();
+                self.idle();
+                self.push_word(self.pc.wrapping_sub(1));
+                self.pc = addr;
+            }
+            Mode::AbsoluteIndexedIndirect => {
+                let low = self.fetch();
+                self.push_word(self.pc);
+                let high = self.fetch();
+                self.idle();
+                let ptr = u16::from_le_bytes([low, high]).wrapping_add(self.x());
+                self.pc = self.read_program_word(ptr);
+            }
+            _ => self.invalid_mode(),
+        }
+    }
+
+    fn jsl(&mut self) {
+        let addr = self.fetch_word();
+        self.push_unbounded(self.pbr);
+        self.idle();
+        let bank = self.fetch();
+        self.push_word_unbounded(self.pc.wrapping_sub(1));
+        self.set_s(self.s());
+        self.pbr = bank;
+        self.pc = addr;
+    }
+
+    fn rts_816(&mut self) {
+        self.idle();
+        self.idle();
+        let addr = self.pull_word();
+        self.idle();
+        self.pc = addr.wrapping_add(1);
+    }
+
+    fn rtl(&mut self) {
+        self.idle();
+        self.idle();
+        let addr = self.pull_word_unbounded();
+        self.pbr = self.pull_unbounded();
+        self.set_s(self.s());
+        self.pc = addr.wrapping_add(1);
+    }
+
+    fn rti_816(&mut self) {
+        self.idle();
+        self.idle();
+        let byte = self.pull_816();
+        self.set_status_816(byte);
+        self.pc = self.pull_word();
+        if !self.emulation {
+            self.pbr = self.pull_816();
+        }
+    }
+
+    fn brk_816(&mut self) {
+        self.fetch();
+        self.push_vector_816(IRQ_VECTOR, true);
+    }
+
+    fn cop(&mut self) {
+        self.fetch();
+        self.push_vector_816(COP_VECTOR, true);
+    }
+
+    /// The interrupt sequence shared by BRK, COP, IRQ and NMI. Native mode
+    /// also pushes the program bank and uses its own set of vectors.
+    /// `vector` is always the emulation mode vector.
+    pub(super) fn push_vector_816(&mut self, vector: u16, software: bool) {
+        let status = if self.emulation {
+            self.push_word(self.pc);
+            self.status.stack_byte(software)
+        } else {
+            self.push_816(self.pbr);
+            self.push_word(self.pc);
+            self.status.native_byte()
+        };
+        self.push_816(status);
+
+        let vector = match vector {
+            _ if self.emulation => vector,
+            NMI_VECTOR => NATIVE_NMI_VECTOR,
+            COP_VECTOR => NATIVE_COP_VECTOR,
+            _ if software => NATIVE_BRK_VECTOR,
+            _ => NATIVE_IRQ_VECTOR,
+        };
+
+        self.status.set_interrupt(true);
+        self.status.set_decimal(false);
+        self.pbr = 0;
+
+        let low = self.read_as(AccessKind::Vector, vector as u32);
+        let high = self.read_as(AccessKind::Vector, vector.wrapping_add(1) as u32);
+        self.pc = u16::from_le_bytes([low, high]);
+    }
+}
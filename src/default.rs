@@ -1,4 +1,7 @@
-use crate::{error::BusError, traits::Bus};
+use crate::{
+    error::{BusError, StateError},
+    traits::Bus,
+};
 
 #[derive(Debug)]
 pub struct DefaultBus {
@@ -46,6 +49,36 @@ impl Bus for DefaultBus {
     fn dump_rom(&self) -> Vec<u8> {
         self.rom.to_vec()
     }
+
+    fn peek(&self, addr: u32) -> Option<u8> {
+        let addr = addr as u16 as usize;
+        match addr {
+            0x0000..=0x7fff => Some(self.ram[addr]),
+            _ => Some(self.rom[addr - 0x8000]),
+        }
+    }
+
+    fn power_on(&mut self, ram: &mut dyn Iterator<Item = u8>) {
+        self.ram
+            .iter_mut()
+            .zip(ram)
+            .for_each(|(cell, byte)| *cell = byte);
+    }
+
+    fn save_state(&self, state: &mut Vec<u8>) {
+        state.extend_from_slice(&self.ram);
+        state.extend_from_slice(&self.rom);
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        if state.len() != self.ram.len() + self.rom.len() {
+            return Err(StateError::Invalid("DefaultBus state has the wrong size"));
+        }
+        let (ram, rom) = state.split_at(self.ram.len());
+        self.ram.copy_from_slice(ram);
+        self.rom.copy_from_slice(rom);
+        Ok(())
+    }
 }
 
 impl Default for DefaultBus {
@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::core::{HaltReason, Variant};
+
 #[derive(Debug, Error)]
 pub enum BusError {
     #[error("program too large: ROM size: {rom_size}, program size: {prog_size}")]
@@ -10,4 +12,34 @@ pub enum BusError {
 pub enum CoreError {
     #[error("problem initializing the bus: {0}")]
     BusInitFailed(#[from] BusError),
+    #[error("illegal opcode {opcode:#04x} at {address:#06x}")]
+    IllegalOpcode { opcode: u8, address: u32 },
+    #[error("JAM opcode {opcode:#04x} at {address:#06x} locked up the core")]
+    Jam { opcode: u8, address: u32 },
+    #[error("invalid addressing mode for {instruction}, opcode {opcode:#04x} at {address:#06x}")]
+    InvalidAddressingMode {
+        instruction: &'static str,
+        opcode: u8,
+        address: u32,
+    },
+    #[error("the core is halted: {0}")]
+    Halted(HaltReason),
+}
+
+#[derive(Debug, Error)]
+pub enum StateError {
+    #[error("not a save state")]
+    BadMagic,
+    #[error("save state version {0} is newer than this crate supports")]
+    UnsupportedVersion(u16),
+    #[error("save state is truncated")]
+    Truncated,
+    #[error("checksum mismatch in the {0:?} chunk")]
+    Checksum(String),
+    #[error("save state has no {0:?} chunk")]
+    MissingChunk(&'static str),
+    #[error("save state is for a {found:?}, not a {expected:?}")]
+    WrongVariant { expected: Variant, found: Variant },
+    #[error("invalid save state: {0}")]
+    Invalid(&'static str),
 }
@@ -1,4 +1,7 @@
-use crate::error::BusError;
+use crate::{
+    core::AccessKind,
+    error::{BusError, StateError},
+};
 use std::fmt::Debug;
 
 /// The `Bus` trait defines the interface for the system bus.
@@ -16,4 +19,192 @@ pub trait Bus: 'static + Debug {
     fn on_clock(&mut self);
     fn load_rom(&mut self, prog: Vec<u8>) -> Result<(), BusError>;
     fn dump_rom(&self) -> Vec<u8>;
+
+    /// Fills RAM with its power-on contents, taking one byte from `ram`
+    /// per cell in address order; the iterator never ends. Called by
+    /// [`Core::power_on`][super::core::Core::power_on] before the reset
+    /// sequence. The default leaves memory alone.
+    fn power_on(&mut self, _ram: &mut dyn Iterator<Item = u8>) {}
+
+    /// Reads from the full 24-bit address space of the 65C816.
+    /// Bank-aware buses should override this; the default ignores the bank.
+    fn read_long(&mut self, addr: u32) -> u8 {
+        self.read(addr as u16)
+    }
+
+    /// Writes to the full 24-bit address space of the 65C816.
+    /// Bank-aware buses should override this; the default ignores the bank.
+    fn write_long(&mut self, addr: u32, byte: u8) {
+        self.write(addr as u16, byte)
+    }
+
+    /// Reads like [`Bus::read_long`], but also tells the bus what the CPU
+    /// is reading for and on which cycle, as counted by
+    /// [`Core::cycles`][super::core::Core::cycles]. This is what the core
+    /// calls for every read cycle. Buses that need to tell an opcode
+    /// fetch or a vector pull from a data read override it, for things
+    /// like bank switching on fetches, remapping the vectors as the
+    /// 65C02 VPB pin allows, or logging code and data. The default
+    /// forwards to [`Bus::read_long`].
+    fn read_access(&mut self, addr: u32, _kind: AccessKind, _cycle: u64) -> u8 {
+        self.read_long(addr)
+    }
+
+    /// Writes like [`Bus::write_long`], but also tells the bus what the
+    /// CPU is writing for and on which cycle. This is what the core calls
+    /// for every write cycle. The default forwards to [`Bus::write_long`].
+    fn write_access(&mut self, addr: u32, byte: u8, _kind: AccessKind, _cycle: u64) {
+        self.write_long(addr, byte)
+    }
+
+    /// Reads a byte without the side effects of [`Bus::read`], for
+    /// tracers and debuggers. Returns `None` where the bus can't tell
+    /// what a read would return without performing it, such as device
+    /// registers that change when read. The default can't peek at all.
+    fn peek(&self, _addr: u32) -> Option<u8> {
+        None
+    }
+
+    /// Level of the IRQ line as driven by devices on the bus.
+    /// Returns `true` while any device is holding the line asserted.
+    fn irq(&self) -> bool {
+        false
+    }
+
+    /// Level of the NMI line as driven by devices on the bus.
+    /// The [`Core`][super::core::Core] samples it every cycle and
+    /// latches an interrupt on the transition to asserted.
+    fn nmi(&self) -> bool {
+        false
+    }
+
+    /// Level of the SO (set overflow) line as driven by devices on the
+    /// bus. Returns `true` while any device is holding it asserted; the
+    /// [`Core`][super::core::Core] sets V when it becomes asserted.
+    fn so(&self) -> bool {
+        false
+    }
+
+    /// Level of the RDY line as driven by devices on the bus. Returns
+    /// `false` while any device pulls it low to take the bus for DMA;
+    /// the [`Core`][super::core::Core] then waits on its next read cycle.
+    fn rdy(&self) -> bool {
+        true
+    }
+
+    /// Level of the 6510 I/O port pins that external hardware drives.
+    /// Only bits configured as inputs are used. Defaults to all high,
+    /// as if every pin had a pull-up.
+    fn port_input(&self) -> u8 {
+        0xff
+    }
+
+    /// Called whenever a 6510 writes its I/O port at $0000/$0001, and on
+    /// reset. `data` is the output register and `ddr` the direction
+    /// register; bits set in `ddr` are driven by the CPU. Systems use
+    /// this for things like C64 memory banking.
+    fn port_changed(&mut self, _data: u8, _ddr: u8) {}
+
+    /// Appends the state of the bus to a save state: RAM, device
+    /// registers and anything else [`Bus::load_state`] needs to bring it
+    /// back. The core checksums it, but the layout is up to the bus, so
+    /// it should carry its own version if it may change. The default
+    /// saves nothing.
+    fn save_state(&self, _state: &mut Vec<u8>) {}
+
+    /// Restores what [`Bus::save_state`] wrote. The default accepts
+    /// anything and changes nothing.
+    fn load_state(&mut self, _state: &[u8]) -> Result<(), StateError> {
+        Ok(())
+    }
+
+    /// Called by a 6507 before every access with the full 16-bit address
+    /// the CPU generated. The following `read` or `write` only receives
+    /// the 13 bits that reach the pins, so this is the only way to tell
+    /// an access to $FFFC from one to $1FFC.
+    fn unmasked_address(&mut self, _addr: u16) {}
+}
+
+/// Lets a boxed bus, including `Box<dyn Bus>`, drive a
+/// [`Core`][super::core::Core] through dynamic dispatch.
+impl<B: Bus + ?Sized> Bus for Box<B> {
+    fn read(&mut self, addr: u16) -> u8 {
+        (**self).read(addr)
+    }
+
+    fn write(&mut self, addr: u16, byte: u8) {
+        (**self).write(addr, byte)
+    }
+
+    fn on_clock(&mut self) {
+        (**self).on_clock()
+    }
+
+    fn load_rom(&mut self, prog: Vec<u8>) -> Result<(), BusError> {
+        (**self).load_rom(prog)
+    }
+
+    fn dump_rom(&self) -> Vec<u8> {
+        (**self).dump_rom()
+    }
+
+    fn power_on(&mut self, ram: &mut dyn Iterator<Item = u8>) {
+        (**self).power_on(ram)
+    }
+
+    fn read_long(&mut self, addr: u32) -> u8 {
+        (**self).read_long(addr)
+    }
+
+    fn write_long(&mut self, addr: u32, byte: u8) {
+        (**self).write_long(addr, byte)
+    }
+
+    fn read_access(&mut self, addr: u32, kind: AccessKind, cycle: u64) -> u8 {
+        (**self).read_access(addr, kind, cycle)
+    }
+
+    fn write_access(&mut self, addr: u32, byte: u8, kind: AccessKind, cycle: u64) {
+        (**self).write_access(addr, byte, kind, cycle)
+    }
+
+    fn peek(&self, addr: u32) -> Option<u8> {
+        (**self).peek(addr)
+    }
+
+    fn irq(&self) -> bool {
+        (**self).irq()
+    }
+
+    fn nmi(&self) -> bool {
+        (**self).nmi()
+    }
+
+    fn so(&self) -> bool {
+        (**self).so()
+    }
+
+    fn rdy(&self) -> bool {
+        (**self).rdy()
+    }
+
+    fn port_input(&self) -> u8 {
+        (**self).port_input()
+    }
+
+    fn port_changed(&mut self, data: u8, ddr: u8) {
+        (**self).port_changed(data, ddr)
+    }
+
+    fn save_state(&self, state: &mut Vec<u8>) {
+        (**self).save_state(state)
+    }
+
+    fn load_state(&mut self, state: &[u8]) -> Result<(), StateError> {
+        (**self).load_state(state)
+    }
+
+    fn unmasked_address(&mut self, addr: u16) {
+        (**self).unmasked_address(addr)
+    }
 }